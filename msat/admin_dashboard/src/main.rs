@@ -31,10 +31,57 @@ use tokio::{
 use shared_components::{
     backend::{
         self, 
+        get_collisions,
         get_config, 
         get_duties_for_teacher, 
         get_lessons_by_class_id, 
         get_lessons_by_teacher_id, 
+        get_cover_lessons,
+        suggest_substitutes,
+        date_or_today,
+        parse_date,
+        get_calendar,
+        get_groups,
+        get_teacher_workload,
+        get_curriculum_compliance,
+        get_qualifications,
+        get_qualified_teachers,
+        get_availability,
+        get_availability_violations,
+        get_classroom_features,
+        get_subject_features,
+        get_now_and_next,
+        get_free_classrooms,
+        get_free_classrooms_at,
+        get_room_schedule,
+        get_day_schedule,
+        get_timetable_between,
+        get_bell_schedules,
+        get_bell_schedule_for_date,
+        get_lesson_hours_for_date,
+        get_breaks_for_date,
+        insert_booking,
+        classroom_filter_from_args,
+        owner_from_args,
+        moment_or_now,
+        workload_to_csv,
+        get_schedule_for_date,
+        get_year_and_semester,
+        get_draft,
+        generate_draft,
+        generate_duties,
+        apply_draft,
+        rollover_year,
+        insert_lesson_block,
+        insert_co_teacher,
+        insert_draft_co_teacher,
+        insert_duty,
+        insert_draft_duty,
+        insert_draft_lesson,
+        insert_lesson_override,
+        copy_to_draft,
+        get_draft_changes,
+        has_draft,
         manipulate_database, 
         MainpulationType, 
         Request, 
@@ -271,32 +318,62 @@ async fn handle_custom_request
                 1 => {
                     if let Some(class_id) = args.get("class_id") 
                     {
-                        if let Ok(class) = class_id.parse::<u16>()
+                        if let (Ok(class), Some(date)) = (class_id.parse::<u16>(), date_or_today(args.get("date")))
                         {
-                            match get_lessons_by_class_id(class, &*db.lock().await){
+                            match get_lessons_by_class_id(class, date, &*db.lock().await){
                                 Ok(lessons) => {
-                                type LessonData = (String, String, String, String, String);
-                                let mut unwrapped_lessons : BTreeMap<(u8, u16), LessonData> = 
+                                // last field is last lesson hour covered by cell
+                                type LessonData = (String, String, String, String, String, Option<String>, u16);
+                                // lessons of class groups take place at the same time
+                                let mut unwrapped_lessons : BTreeMap<(u8, u16), Vec<LessonData>> = 
                                     BTreeMap::new();
                                 for lesson in lessons{
                                     if let (Some(teacher), Some(classroom), Some(subject), Some(lessonh), Some(weekd), 
                                         Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
                                     (lesson.teacher, lesson.classroom, lesson.subject, lesson.lessonh.lesson_hour, 
                                      lesson.weekday, lesson.lessonh.start_hour, lesson.lessonh.start_minute, 
-                                     lesson.lessonh.end_hour, lesson.lessonh.start_minute)
+                                     lesson.lessonh.end_hour, lesson.lessonh.end_minutes)
                                     {
-                                        unwrapped_lessons.insert(
-                                        (weekd, lessonh), 
+                                        let teacher = match lesson.status{
+                                            LessonStatus::Regular      => teacher,
+                                            LessonStatus::Substitution => format!("{} ({})", teacher, lang.english_or("substitution", "zastępstwo")),
+                                            LessonStatus::Cancelled    => lang.english_or("Cancelled", "Odwołana"),
+                                            LessonStatus::Changed      => format!("{} ({})", teacher, lang.english_or("changed", "zmiana"))
+                                        };
+                                        let teacher = match lesson.co_teachers{
+                                            Some(co_teachers) => format!("{}, {}", teacher, co_teachers),
+                                            None              => teacher
+                                        };
+                                        let end = format!("{:02}:{:02}", end_hour, end_minute);
+                                        // next hours of multi-slot lesson are merged into cell of its first hour
+                                        // (unless they were changed for that day)
+                                        if let Some((first_hour, _)) = lesson.block.filter(|(first_hour, _)| *first_hour < lessonh){
+                                            if let Some(merged) = unwrapped_lessons.get_mut(&(weekd, first_hour)).and_then(|parallel| 
+                                                parallel.iter_mut().find(|l| 
+                                                    (&l.0, &l.1, &l.2, &l.5, l.6 + 1) == (&subject, &classroom, &teacher, &lesson.group, lessonh)))
+                                            {
+                                                merged.4 = end;
+                                                merged.6 = lessonh;
+                                                continue;
+                                            }
+                                        }
+                                        unwrapped_lessons.entry((weekd, lessonh)).or_default().push(
                                         (subject, classroom, teacher, 
                                          format!("{:02}:{:02}", start_hour, start_minute), 
-                                         format!("{:02}:{:02}", end_hour, end_minute))
+                                         end,
+                                         lesson.group, lessonh)
                                         );
                                     }
                                 }
                                 let mut current_weekd : u8 = 0;
                                 let mut to_return     : String = "<table>".to_string();
                                 
+                                // lessons that start before other lesson of the cell ends are shown in the same cell
+                                let mut merged_until : (u8, u16) = (0, 0);
                                 for (weekd, lessonh) in unwrapped_lessons.keys(){
+                                    if (*weekd, *lessonh) <= merged_until{
+                                        continue;
+                                    }
                                     if &current_weekd != weekd{
                                         if current_weekd != 0{
                                             to_return.push_str("<tr>");
@@ -306,12 +383,29 @@ async fn handle_custom_request
                                         }
                                         current_weekd = *weekd;
                                     }
-                                    if let Some((subject, classroom, teacher, start, end)) = 
-                                        unwrapped_lessons.get(&(*weekd, *lessonh))
-                                    {
-                                        to_return.push_str(&format!("<td><p>{}</p><p>{}</p><p>{}</p><p>{}</p><p>{}</p></td>", 
+                                    let mut last_hour = *lessonh;
+                                    let mut parallel  = vec![];
+                                    for ((_, hour), lessons) in unwrapped_lessons.range((*weekd, *lessonh)..=(*weekd, u16::MAX)){
+                                        if *hour > last_hour{
+                                            break;
+                                        }
+                                        last_hour = lessons.iter().map(|l| l.6).fold(last_hour, u16::max);
+                                        parallel.extend(lessons);
+                                    }
+                                    merged_until = (*weekd, last_hour);
+                                    match last_hour > *lessonh{
+                                        true  => to_return.push_str(&format!("<td colspan='{}'>", last_hour - lessonh + 1)),
+                                        false => to_return.push_str("<td>")
+                                    }
+                                    for (subject, classroom, teacher, start, end, group, _) in parallel{
+                                        match group{
+                                            Some(group) => to_return.push_str(&format!("<div class='group'><p>{}</p>", group)),
+                                            None        => to_return.push_str("<div>")
+                                        }
+                                        to_return.push_str(&format!("<p>{}</p><p>{}</p><p>{}</p><p>{}</p><p>{}</p></div>", 
                                                 subject, classroom, teacher, start, end));
                                     }
+                                    to_return.push_str("</td>");
                                 }
                                 to_return.push_str("</table>");
                                 return to_return
@@ -326,9 +420,9 @@ async fn handle_custom_request
                 2 => {
                     if let Some(teacher_str) = args.get("teacher_id")
                     {
-                        if let Ok(teacher_id) = teacher_str.parse::<u16>()
+                        if let (Ok(teacher_id), Some(date)) = (teacher_str.parse::<u16>(), date_or_today(args.get("date")))
                         {
-                            if let Ok(duties) = get_duties_for_teacher(teacher_id, &*db.lock().await){
+                            if let Ok(duties) = get_duties_for_teacher(teacher_id, date, &*db.lock().await){
                                 let mut filtered : BTreeMap<(u16, u8), (String, String, String)> = BTreeMap::new();
                                 for d in duties{
                                     if let (Some(place), Some(weekday), Some(breakn), Some(starth), Some(startm), Some(endh), Some(endm)) = 
@@ -368,8 +462,8 @@ async fn handle_custom_request
                 }
                 3 => {
                     if let Some(teacherid_str) = args.get("teacher_id"){
-                        if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
-                            if let Ok(lessons) = get_lessons_by_teacher_id(teacher_id, &*db.lock().await){
+                        if let (Ok(teacher_id), Some(date)) = (teacherid_str.parse::<u16>(), date_or_today(args.get("date"))){
+                            if let Ok(lessons) = get_lessons_by_teacher_id(teacher_id, date, &*db.lock().await){
                                 type LessonData = (String, String, String, String);
                                 let mut unwrapped_lessons : BTreeMap<(String, u8, u16), LessonData> = 
                                     BTreeMap::new();
@@ -378,13 +472,23 @@ async fn handle_custom_request
                                         Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
                                     (lesson.class, lesson.classroom, lesson.subject, lesson.lessonh.lesson_hour, 
                                      lesson.weekday, lesson.lessonh.start_hour, lesson.lessonh.start_minute, 
-                                     lesson.lessonh.end_hour, lesson.lessonh.start_minute)
+                                     lesson.lessonh.end_hour, lesson.lessonh.end_minutes)
                                     {
+                                        let subject = match lesson.status{
+                                            LessonStatus::Regular      => subject,
+                                            LessonStatus::Substitution => format!("{} ({})", subject, lang.english_or("substitution", "zastępstwo")),
+                                            LessonStatus::Cancelled    => format!("{} ({})", subject, lang.english_or("cancelled", "odwołana")),
+                                            LessonStatus::Changed      => format!("{} ({})", subject, lang.english_or("changed", "zmiana"))
+                                        };
+                                        let subject = match lesson.group{
+                                            Some(group) => format!("{} - {}", subject, group),
+                                            None        => subject
+                                        };
                                         unwrapped_lessons.insert(
                                         (class, weekd, lessonh), 
                                         (subject, classroom, 
-                                         format!("{:2}:{:2}", start_hour, start_minute), 
-                                         format!("{:2}:{:2}", end_hour, end_minute))
+                                         format!("{:02}:{:02}", start_hour, start_minute), 
+                                         format!("{:02}:{:02}", end_hour, end_minute))
                                         );
                                     }
                                 }
@@ -590,6 +694,644 @@ async fn handle_custom_request
                         }
                     }
                 }
+                // Conflicts report
+                13 => {
+                    let db = db.lock().await;
+                    let current = match semester_from_args(&args, &db){
+                        Some(current) => current,
+                        None => return lang.english_or("<error><p>Invalid date</p></error>", "<error><p>Nieprawidłowa data</p></error>")
+                    };
+                    match current.and_then(|(semester, academic_year)| get_collisions(semester, academic_year, &db)){
+                        Ok(collisions) => {
+                            if collisions.is_empty(){
+                                return lang.english_or("<p>No conflicts found</p>", "<p>Nie znaleziono konfliktów</p>");
+                            }
+                            let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                lang.english_or("Conflict", "Konflikt"), lang.english_or("Weekday", "Dzień tygodnia"), 
+                                lang.english_or("Lesson hour", "Godzina lekcyjna"), lang.english_or("Occupied by", "Zajęte przez"), 
+                                lang.english_or("Classes", "Klasy"));
+                            for collision in collisions{
+                                let kind = match collision.kind{
+                                    ConflictKind::Teacher   => lang.english_or("Teacher", "Nauczyciel"),
+                                    ConflictKind::Classroom => lang.english_or("Classroom", "Sala"),
+                                    ConflictKind::Class     => lang.english_or("Class", "Klasa")
+                                };
+                                to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}, {}</td></tr>", 
+                                    kind, weekd_to_string(&lang, collision.weekday), collision.lesson_hour, collision.resource.1,
+                                    collision.classes[0].1, collision.classes[1].1));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return lang.english_or("<p>No current semester or academic year</p>", "<p>Brak bieżącego semestru lub roku szkolnego</p>");
+                            }
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Draft timetable
+                14 => {
+                    if let (Some(semester_str), Some(ay_str)) = (args.get("semester"), args.get("academic_year")){
+                        if let (Ok(semester), Ok(academic_year)) = (semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            let class_id = args.get("class_id").and_then(|s| s.parse::<u16>().ok());
+                            match get_draft(semester, academic_year, class_id, &*db.lock().await){
+                                Ok(lessons) => {
+                                    if lessons.is_empty(){
+                                        return lang.english_or("<p>Draft is empty</p>", "<p>Szkic jest pusty</p>");
+                                    }
+                                    let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                                        lang.english_or("Class", "Klasa"), lang.english_or("Weekday", "Dzień tygodnia"), 
+                                        lang.english_or("Lesson hour", "Godzina lekcyjna"), lang.english_or("Subject", "Przedmiot"),
+                                        lang.english_or("Teacher", "Nauczyciel"), lang.english_or("Classroom", "Sala"));
+                                    for lesson in lessons{
+                                        if let (Some(class), Some(weekd), Some(lessonh), Some(subject), Some(teacher), Some(classroom)) = 
+                                        (lesson.class, lesson.weekday, lesson.lessonh.lesson_hour, lesson.subject, lesson.teacher, lesson.classroom)
+                                        {
+                                            let class = match lesson.group{
+                                                Some(group) => format!("{} - {}", class, group),
+                                                None        => class
+                                            };
+                                            to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                                class, weekd_to_string(&lang, weekd), lessonh, subject, teacher, classroom));
+                                        }
+                                    }
+                                    to_return.push_str("</table>");
+                                    return to_return;
+                                }
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Lessons of absent teachers
+                15 => {
+                    if let Some(date) = date_or_today(args.get("date")){
+                        let db = db.lock().await;
+                        match get_cover_lessons(date, &db){
+                            Ok(lessons) => {
+                                if lessons.is_empty(){
+                                    return lang.english_or("<p>No lessons to cover</p>", "<p>Brak lekcji do zastąpienia</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                                    lang.english_or("Lesson hour", "Godzina lekcyjna"), lang.english_or("Class", "Klasa"),
+                                    lang.english_or("Subject", "Przedmiot"), lang.english_or("Absent teacher", "Nieobecny nauczyciel"),
+                                    lang.english_or("Substitute", "Zastępca"));
+                                for lesson in lessons{
+                                    let substitute = match lesson.substitute{
+                                        Some(substitute) => substitute,
+                                        None => {
+                                            match suggest_substitutes(date, lesson.class_id, lesson.group_id, lesson.lesson_hour, &db){
+                                                Ok(candidates) => {
+                                                    let candidates = candidates.iter()
+                                                        .map(|c| format!("{} ({}){}", c.teacher_name, c.teacher_id, if c.qualified {" *"} else {""}))
+                                                        .collect::<Vec<String>>();
+                                                    format!("{} {}", lang.english_or("Free:", "Wolni:"), candidates.join(", "))
+                                                }
+                                                Err(error) => {
+                                                    visual::error(Some(error), "Database Error");
+                                                    String::new()
+                                                }
+                                            }
+                                        }
+                                    };
+                                    let class = match lesson.group{
+                                        Some(group) => format!("{} ({}) - {} ({})", lesson.class, lesson.class_id, group, lesson.group_id),
+                                        None        => format!("{} ({})", lesson.class, lesson.class_id)
+                                    };
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                        lesson.lesson_hour, class, lesson.subject, lesson.teacher, substitute));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // School calendar
+                16 => {
+                    match get_calendar(&*db.lock().await){
+                        Ok(entries) => {
+                            if entries.is_empty(){
+                                return lang.english_or("<p>Calendar is empty</p>", "<p>Kalendarz jest pusty</p>");
+                            }
+                            let mut to_return = format!("<table><tr><th>ID</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                                lang.english_or("From", "Od"), lang.english_or("To", "Do"), 
+                                lang.english_or("Type", "Rodzaj"), lang.english_or("Name", "Nazwa"));
+                            for entry in entries{
+                                let day_type = match entry.day_type{
+                                    DayType::Holiday => lang.english_or("Holiday", "Święto"),
+                                    DayType::Exam    => lang.english_or("Exams", "Egzaminy"),
+                                    DayType::DayOff  => lang.english_or("Day off", "Dzień wolny")
+                                };
+                                to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                    entry.calendar_id, entry.start_date, entry.end_date, day_type, entry.name));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Schedule of every class for date
+                17 => {
+                    if let Some(date) = date_or_today(args.get("date")){
+                        match get_schedule_for_date(date, &*db.lock().await){
+                            Ok(lessons) => {
+                                if lessons.is_empty(){
+                                    return lang.english_or("<p>No lessons on this day</p>", "<p>Brak lekcji tego dnia</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                                    lang.english_or("Class", "Klasa"), lang.english_or("Lesson hour", "Godzina lekcyjna"), 
+                                    lang.english_or("Subject", "Przedmiot"), lang.english_or("Teacher", "Nauczyciel"), 
+                                    lang.english_or("Classroom", "Sala"));
+                                for lesson in lessons{
+                                    if let (Some(class), Some(lessonh), Some(subject), Some(teacher), Some(classroom)) = 
+                                    (lesson.class, lesson.lessonh.lesson_hour, lesson.subject, lesson.teacher, lesson.classroom)
+                                    {
+                                        let teacher = match lesson.status{
+                                            LessonStatus::Regular      => teacher,
+                                            LessonStatus::Substitution => format!("{} ({})", teacher, lang.english_or("substitution", "zastępstwo")),
+                                            LessonStatus::Cancelled    => lang.english_or("Cancelled", "Odwołana"),
+                                            LessonStatus::Changed      => format!("{} ({})", teacher, lang.english_or("changed", "zmiana"))
+                                        };
+                                        let teacher = match lesson.co_teachers{
+                                            Some(co_teachers) => format!("{}, {}", teacher, co_teachers),
+                                            None              => teacher
+                                        };
+                                        let class = match lesson.group{
+                                            Some(group) => format!("{} - {}", class, group),
+                                            None        => class
+                                        };
+                                        to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                            class, lessonh, subject, teacher, classroom));
+                                    }
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Groups of class
+                18 => {
+                    if let Some(class_id) = args.get("class_id").and_then(|s| s.parse::<u16>().ok()){
+                        match get_groups(class_id, &*db.lock().await){
+                            Ok(groups) => {
+                                if groups.is_empty(){
+                                    return lang.english_or("<p>Class isn't divided into groups</p>", "<p>Klasa nie jest podzielona na grupy</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>ID</th><th>{}</th></tr>", lang.english_or("Group", "Grupa"));
+                                for (group_id, group_name) in groups{
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", group_id, group_name));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Teacher workload (`format=csv` returns CSV instead of table)
+                19 => {
+                    let db = db.lock().await;
+                    let current = match semester_from_args(&args, &db){
+                        Some(current) => current,
+                        None => return lang.english_or("<error><p>Invalid date</p></error>", "<error><p>Nieprawidłowa data</p></error>")
+                    };
+                    match current.and_then(|(semester, academic_year)| get_teacher_workload(semester, academic_year, &db)){
+                        Ok(workload) => {
+                            if args.get("format").is_some_and(|format| format == "csv"){
+                                return workload_to_csv(&workload);
+                            }
+                            let mut to_return = format!("<table><tr><th>ID</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                lang.english_or("Teacher", "Nauczyciel"), lang.english_or("Lessons per week", "Lekcje w tygodniu"), 
+                                lang.english_or("Duties per week", "Dyżury w tygodniu"), lang.english_or("Pensum", "Pensum"), 
+                                lang.english_or("Status", "Status"));
+                            for teacher in workload{
+                                let status = match teacher.status(){
+                                    LoadStatus::Unknown => lang.english_or("Pensum not set", "Brak pensum"),
+                                    LoadStatus::Under   => lang.english_or("Underloaded", "Niedociążony"),
+                                    LoadStatus::Full    => lang.english_or("OK", "OK"),
+                                    LoadStatus::Over    => lang.english_or("Overloaded", "Przeciążony")
+                                };
+                                to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                    teacher.teacher_id, teacher.teacher_name, teacher.lessons, teacher.duties, 
+                                    teacher.pensum.map(|pensum| pensum.to_string()).unwrap_or_default(), status));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return lang.english_or("<p>No current semester or academic year</p>", "<p>Brak bieżącego semestru lub roku szkolnego</p>");
+                            }
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Curriculum compliance
+                20 => {
+                    let db = db.lock().await;
+                    let current = match semester_from_args(&args, &db){
+                        Some(current) => current,
+                        None => return lang.english_or("<error><p>Invalid date</p></error>", "<error><p>Nieprawidłowa data</p></error>")
+                    };
+                    let class_id = args.get("class_id").and_then(|s| s.parse::<u16>().ok());
+                    match current.and_then(|(semester, academic_year)| get_curriculum_compliance(semester, academic_year, class_id, &db)){
+                        Ok(compliance) => {
+                            if compliance.is_empty(){
+                                return lang.english_or("<p>No requirements or lessons found</p>", "<p>Nie znaleziono wymagań ani lekcji</p>");
+                            }
+                            let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                lang.english_or("Class", "Klasa"), lang.english_or("Subject", "Przedmiot"), 
+                                lang.english_or("Required hours", "Wymagane godziny"), lang.english_or("Scheduled hours", "Zaplanowane godziny"), 
+                                lang.english_or("Status", "Status"));
+                            for entry in compliance{
+                                let status = match entry.status(){
+                                    LoadStatus::Under                      => lang.english_or("Missing hours", "Brakuje godzin"),
+                                    LoadStatus::Full | LoadStatus::Unknown => lang.english_or("OK", "OK"),
+                                    LoadStatus::Over                       => lang.english_or("Too many hours", "Za dużo godzin")
+                                };
+                                to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                    entry.class, entry.subject, entry.required.map(|hours| hours.to_string()).unwrap_or("-".to_string()), 
+                                    entry.scheduled, status));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return lang.english_or("<p>No current semester or academic year</p>", "<p>Brak bieżącego semestru lub roku szkolnego</p>");
+                            }
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Qualifications of teacher
+                21 => {
+                    if let Some(teacher_id) = args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()){
+                        match get_qualifications(teacher_id, &*db.lock().await){
+                            Ok(subjects) => {
+                                if subjects.is_empty(){
+                                    return lang.english_or("<p>Teacher has no qualifications and can teach every subject</p>", 
+                                        "<p>Nauczyciel nie ma kwalifikacji i może uczyć każdego przedmiotu</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>ID</th><th>{}</th></tr>", lang.english_or("Subject", "Przedmiot"));
+                                for (subject_id, subject_name) in subjects{
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", subject_id, subject_name));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Teachers qualified to teach subject (options of datalist)
+                22 => {
+                    if let Some(subject_id) = args.get("subject_id").and_then(|s| s.parse::<u16>().ok()){
+                        match get_qualified_teachers(subject_id, &*db.lock().await){
+                            Ok(teachers) => {
+                                return teachers.iter()
+                                    .map(|(teacher_id, teacher_name)| format!("<option value='{}'>{}</option>", teacher_id, teacher_name))
+                                    .collect::<String>();
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Availability of teacher
+                23 => {
+                    if let Some(teacher_id) = args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()){
+                        match get_availability(teacher_id, &*db.lock().await){
+                            Ok(availability) => {
+                                if availability.is_empty(){
+                                    return lang.english_or("<p>Teacher is available at any time</p>", "<p>Nauczyciel jest dostępny w każdym czasie</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                    lang.english_or("Weekday", "Dzień tygodnia"), lang.english_or("Lesson hour", "Godzina lekcyjna"), 
+                                    lang.english_or("Availability", "Dostępność"));
+                                for (weekday, lesson_hour, availability) in availability{
+                                    let availability = match availability{
+                                        Availability::Unavailable => lang.english_or("Unavailable", "Niedostępny"),
+                                        Availability::Preferred   => lang.english_or("Preferred", "Preferowana"),
+                                        Availability::Neutral     => lang.english_or("Neutral", "Neutralna")
+                                    };
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                        weekd_to_string(&lang, weekday), lesson_hour, availability));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Lessons taking place when teacher is unavailable
+                24 => {
+                    let db = db.lock().await;
+                    let current = match semester_from_args(&args, &db){
+                        Some(current) => current,
+                        None => return lang.english_or("<error><p>Invalid date</p></error>", "<error><p>Nieprawidłowa data</p></error>")
+                    };
+                    match current.and_then(|(semester, academic_year)| get_availability_violations(semester, academic_year, &db)){
+                        Ok(violations) => {
+                            if violations.is_empty(){
+                                return lang.english_or("<p>Every lesson respects availability of teachers</p>", 
+                                    "<p>Wszystkie lekcje są zgodne z dostępnością nauczycieli</p>");
+                            }
+                            let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                lang.english_or("Teacher", "Nauczyciel"), lang.english_or("Weekday", "Dzień tygodnia"), 
+                                lang.english_or("Lesson hour", "Godzina lekcyjna"), lang.english_or("Class", "Klasa"));
+                            for violation in violations{
+                                to_return.push_str(&format!("<tr><td>{} ({})</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                    violation.teacher_name, violation.teacher_id, weekd_to_string(&lang, violation.weekday), 
+                                    violation.lesson_hour, violation.class));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return lang.english_or("<p>No current semester or academic year</p>", "<p>Brak bieżącego semestru lub roku szkolnego</p>");
+                            }
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Features of classroom or features required by subject
+                25 => {
+                    let db = db.lock().await;
+                    let features = match (args.get("classroom_id").and_then(|s| s.parse::<u16>().ok()), 
+                        args.get("subject_id").and_then(|s| s.parse::<u16>().ok()))
+                    {
+                        (Some(classroom_id), _) => get_classroom_features(classroom_id, &db),
+                        (None, Some(subject_id)) => get_subject_features(subject_id, &db),
+                        (None, None) => return lang.english_or("<p>Enter classroom or subject</p>", "<p>Podaj salę lub przedmiot</p>")
+                    };
+                    match features{
+                        Ok(features) => {
+                            if features.is_empty(){
+                                return lang.english_or("<p>No features</p>", "<p>Brak wyposażenia</p>");
+                            }
+                            return format!("<p>{}</p>", features.join(", "));
+                        }
+                        Err(error) => {
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Now and next of teacher, class or classroom
+                26 => {
+                    if let (Some(owner), Some(moment)) = (owner_from_args(&args), moment_or_now(args.get("date"), args.get("time"))){
+                        match get_now_and_next(owner, moment, &*db.lock().await){
+                            Ok(now_and_next) => {
+                                return format!("<table><tr><th>{}</th><td>{}</td></tr><tr><th>{}</th><td>{}</td></tr></table>",
+                                    lang.english_or("Now", "Teraz"), activity_to_string(&lang, &now_and_next.current),
+                                    lang.english_or("Next", "Następnie"), activity_to_string(&lang, &now_and_next.next));
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Free classrooms in lesson hour of weekday (or at date and time)
+                27 => {
+                    let filter = classroom_filter_from_args(&args);
+                    let db = db.lock().await;
+                    let classrooms = match (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok())){
+                        (Some(weekday), Some(lesson_hour)) => {
+                            let current = match semester_from_args(&args, &db){
+                                Some(current) => current,
+                                None => return lang.english_or("<error><p>Invalid date</p></error>", "<error><p>Nieprawidłowa data</p></error>")
+                            };
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            current.and_then(|(semester, academic_year)| 
+                                get_free_classrooms(weekday, lesson_hour, semester, academic_year, week, &filter, &db))
+                        }
+                        _ => match moment_or_now(args.get("date"), args.get("time")){
+                            Some(moment) => get_free_classrooms_at(moment, &filter, &db),
+                            None => return lang.english_or("<p>Enter correct date and time</p>", "<p>Podaj poprawną datę i godzinę</p>")
+                        }
+                    };
+                    match classrooms{
+                        Ok(classrooms) => {
+                            if classrooms.is_empty(){
+                                return lang.english_or("<p>There are no free classrooms</p>", "<p>Nie ma wolnych sal</p>");
+                            }
+                            let mut to_return = format!("<table><tr><th>ID</th><th>{}</th><th>{}</th></tr>", 
+                                lang.english_or("Classroom", "Sala"), lang.english_or("Capacity", "Pojemność"));
+                            for (classroom_id, classroom_name, capacity) in classrooms{
+                                to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>", classroom_id, classroom_name, 
+                                    capacity.map(|capacity| capacity.to_string()).unwrap_or("-".to_string())));
+                            }
+                            to_return.push_str("</table>");
+                            return to_return;
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return lang.english_or("<p>No lesson hour or semester at that time</p>", "<p>Brak godziny lekcyjnej lub semestru w tym czasie</p>");
+                            }
+                            visual::error(Some(error), "Database Error");
+                        }
+                    }
+                }
+                // Lessons and bookings of classroom on date
+                28 => {
+                    if let (Some(classroom_id), Some(date)) = (args.get("classroom_id").and_then(|s| s.parse::<u16>().ok()), date_or_today(args.get("date"))){
+                        match get_room_schedule(classroom_id, date, &*db.lock().await){
+                            Ok(schedule) => {
+                                if schedule.is_empty(){
+                                    return lang.english_or("<p>Classroom is free all day</p>", "<p>Sala jest wolna cały dzień</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                    lang.english_or("Time", "Czas"), lang.english_or("Owner", "Właściciel"), 
+                                    lang.english_or("Title", "Tytuł"), lang.english_or("Booking", "Rezerwacja"));
+                                for entry in schedule{
+                                    to_return.push_str(&format!("<tr><td>{:02}:{:02} - {:02}:{:02}</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                        entry.start_hour, entry.start_minute, entry.end_hour, entry.end_minute, entry.owner, entry.title,
+                                        entry.booking_id.map(|booking_id| booking_id.to_string()).unwrap_or("-".to_string())));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Lessons and events of teacher, class or classroom on date
+                29 => {
+                    if let (Some(owner), Some(date)) = (owner_from_args(&args), date_or_today(args.get("date"))){
+                        match get_day_schedule(owner, date, &*db.lock().await){
+                            Ok(schedule) => {
+                                let mut to_return = String::new();
+                                if schedule.lessons.is_empty(){
+                                    to_return.push_str(&lang.english_or("<p>No lessons on this day</p>", "<p>Brak lekcji tego dnia</p>"));
+                                }
+                                else{
+                                    to_return.push_str(&format!("<table><tr><th>{}</th><th>{}</th></tr>", 
+                                        lang.english_or("Lesson", "Lekcja"), lang.english_or("Status", "Status")));
+                                    for lesson in schedule.lessons{
+                                        let status = match lesson.status{
+                                            LessonStatus::Regular      => "-".to_string(),
+                                            LessonStatus::Substitution => lang.english_or("Substitution", "Zastępstwo"),
+                                            LessonStatus::Cancelled    => lang.english_or("Cancelled", "Odwołana"),
+                                            LessonStatus::Changed      => lang.english_or("Changed", "Zmiana")
+                                        };
+                                        to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", 
+                                            activity_to_string(&lang, &Activity::Lesson(lesson)), status));
+                                    }
+                                    to_return.push_str("</table>");
+                                }
+                                if !schedule.events.is_empty(){
+                                    to_return.push_str(&format!("<table><tr><th>ID</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                        lang.english_or("Time", "Czas"), lang.english_or("Event", "Wydarzenie"), lang.english_or("Classes", "Klasy"),
+                                        lang.english_or("Teachers", "Nauczyciele"), lang.english_or("Classrooms", "Sale"), 
+                                        lang.english_or("Cancels lessons", "Odwołuje lekcje")));
+                                    for event in schedule.events{
+                                        to_return.push_str(&format!("<tr><td>{}</td><td>{:02}:{:02} - {:02}:{:02}</td><td>{} ({})</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                            event.event_id, event.start_hour, event.start_minute, event.end_hour, event.end_minute, event.name, 
+                                            event_type_to_string(&lang, event.event_type), event.classes.unwrap_or_default(), 
+                                            event.teachers.unwrap_or_default(), event.classrooms.unwrap_or_default(),
+                                            if event.overrides_lessons {lang.english_or("Yes", "Tak")} else {lang.english_or("No", "Nie")}));
+                                    }
+                                    to_return.push_str("</table>");
+                                }
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Times of lesson hours and breaks on date
+                30 => {
+                    if let Some(date) = date_or_today(args.get("date")){
+                        let db = db.lock().await;
+                        match (get_bell_schedules(&db), get_bell_schedule_for_date(date, &db), get_lesson_hours_for_date(date, &db), get_breaks_for_date(date, &db)){
+                            (Ok(bell_schedules), Ok(bell_schedule), Ok(lesson_hours), Ok(breaks)) => {
+                                let mut to_return = match bell_schedule{
+                                    Some((_, name)) => format!("<p>{}: {}</p>", lang.english_or("Bell schedule", "Dzwonki"), name),
+                                    None            => lang.english_or("<p>Regular bell schedule</p>", "<p>Zwykłe dzwonki</p>")
+                                };
+                                for (header, hours) in [(lang.english_or("Lesson hour", "Godzina lekcyjna"), lesson_hours), (lang.english_or("Break", "Przerwa"), breaks)]{
+                                    to_return.push_str(&format!("<table><tr><th>{}</th><th>{}</th></tr>", header, lang.english_or("Time", "Czas")));
+                                    for hour in hours{
+                                        if let (Some(num), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                                        (hour.lesson_hour, hour.start_hour, hour.start_minute, hour.end_hour, hour.end_minutes)
+                                        {
+                                            to_return.push_str(&format!("<tr><td>{}</td><td>{:02}:{:02} - {:02}:{:02}</td></tr>", 
+                                                num, start_hour, start_minute, end_hour, end_minute));
+                                        }
+                                    }
+                                    to_return.push_str("</table>");
+                                }
+                                to_return.push_str(&format!("<table><tr><th>ID</th><th>{}</th></tr>", lang.english_or("Bell schedules", "Rodzaje dzwonków")));
+                                for (schedule_id, name) in bell_schedules{
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", schedule_id, name));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            (Err(error), _, _, _) | (_, Err(error), _, _) | (_, _, Err(error), _) | (_, _, _, Err(error)) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Lessons of teacher, class or classroom between two dates
+                32 => {
+                    if let Some(owner) = owner_from_args(&args){
+                        let (from, to) = match (args.get("from").and_then(|s| parse_date(s)), args.get("to").and_then(|s| parse_date(s))){
+                            (Some(from), Some(to)) => (from, to),
+                            _ => return lang.english_or("<error><p>Invalid date range</p></error>", "<error><p>Nieprawidłowy zakres dat</p></error>")
+                        };
+                        match get_timetable_between(owner, from, to, &*db.lock().await){
+                            Ok(lessons) => {
+                                if lessons.is_empty(){
+                                    return lang.english_or("<p>No lessons in this period</p>", "<p>Brak lekcji w tym okresie</p>");
+                                }
+                                let mut to_return = format!("<table><tr><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                    lang.english_or("Date", "Data"), lang.english_or("Lesson", "Lekcja"), lang.english_or("Status", "Status"));
+                                for lesson in lessons{
+                                    let status = match lesson.status{
+                                        LessonStatus::Regular      => "-".to_string(),
+                                        LessonStatus::Substitution => lang.english_or("Substitution", "Zastępstwo"),
+                                        LessonStatus::Cancelled    => lang.english_or("Cancelled", "Odwołana"),
+                                        LessonStatus::Changed      => lang.english_or("Changed", "Zmiana")
+                                    };
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                        lesson.date.clone().unwrap_or_default(), activity_to_string(&lang, &Activity::Lesson(lesson)), status));
+                                }
+                                to_return.push_str("</table>");
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Differences between draft and published timetable
+                31 => {
+                    if let (Some(semester), Some(academic_year)) = 
+                    (args.get("semester").and_then(|s| s.parse::<u8>().ok()), args.get("academic_year").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match get_draft_changes(semester, academic_year, &*db.lock().await){
+                            Ok(changes) => {
+                                if changes.lessons.is_empty() && changes.duties.is_empty(){
+                                    return lang.english_or("<p>Draft doesn't change anything</p>", "<p>Szkic niczego nie zmienia</p>");
+                                }
+                                let mut to_return = String::new();
+                                for (header, owner, hour, list) in [
+                                    (lang.english_or("Lessons", "Lekcje"), lang.english_or("Class", "Klasa"), lang.english_or("Lesson hour", "Godzina lekcyjna"), changes.lessons),
+                                    (lang.english_or("Duties", "Dyżury"), lang.english_or("Teacher", "Nauczyciel"), lang.english_or("Break", "Przerwa"), changes.duties)
+                                ]{
+                                    if list.is_empty(){
+                                        continue;
+                                    }
+                                    to_return.push_str(&format!("<h3>{}</h3><table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                        header, owner, lang.english_or("Weekday", "Dzień tygodnia"), hour, 
+                                        lang.english_or("Published", "Opublikowane"), lang.english_or("Draft", "Szkic")));
+                                    for change in list{
+                                        to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                            change.owner, weekd_to_string(&lang, change.weekday), change.hour, 
+                                            change.published.unwrap_or_else(|| "-".to_string()), change.draft.unwrap_or_else(|| "-".to_string())));
+                                    }
+                                    to_return.push_str("</table>");
+                                }
+                                return to_return;
+                            }
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -608,15 +1350,26 @@ async fn handle_custom_request
                         (weekday_str.parse::<u8>(),classid_str.parse::<u16>(),classroomid_str.parse::<u16>(),teacherid_str.parse::<u16>(),
                         subjectid_str.parse::<u16>(), semester_str.parse::<u8>(), academicyear_str.parse::<u8>(), lessonhour_str.parse::<u16>())
                         {
-                            match manipulate_database(
-                            MainpulationType::Insert
-                            (backend::POST::Lesson
-                            (Some((weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year)))), &*db.lock().await)
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            // number of consecutive lesson hours covered by lesson (e.g. 2 for double period)
+                            let length = args.get("length").and_then(|s| s.parse::<u16>().ok()).unwrap_or(1);
+                            let lesson = (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id);
+                            let db = db.lock().await;
+                            // lessons added to draft aren't visible until draft is applied; while semester 
+                            // has draft, every change goes there, otherwise it would be lost when draft is applied
+                            let result = match has_draft(semester, academic_year, &db).map(|open| open || args.get("draft").is_some_and(|s| s == "true")){
+                                Ok(true)   => insert_draft_lesson(lesson, length, &db),
+                                Ok(false)  => insert_lesson_block(lesson, length, &db),
+                                Err(error) => Err(ServerError::DatabaseError(error))
+                            };
+                            match result
                             {
                                 Ok(v) => return v,
-                                Err(error) => {
+                                Err(ServerError::DatabaseError(error)) => {
                                     visual::error(Some(error), "Database Error");
                                 }
+                                Err(error) => return error.to_response()
                             }
                         }
                     }
@@ -648,14 +1401,21 @@ async fn handle_custom_request
                         (weekday_str.parse::<u8>(),breaknum_str.parse::<u8>(),teacherid_str.parse::<u16>(),
                         semester_str.parse::<u8>(), academicyear_str.parse::<u8>(),placeid_str.parse::<u16>())
                         {
-                            match manipulate_database(MainpulationType::Insert
-                                (backend::POST::Duty(Some((weekday, break_num, teacher_id, place_id, semester, academic_year)))), 
-                                &*db.lock().await)
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let duty = (weekday, break_num, teacher_id, place_id, semester, academic_year, week);
+                            let db = db.lock().await;
+                            let result = match has_draft(semester, academic_year, &db).map(|open| open || args.get("draft").is_some_and(|s| s == "true")){
+                                Ok(true)   => insert_draft_duty(duty, &db),
+                                Ok(false)  => insert_duty(duty, &db),
+                                Err(error) => Err(ServerError::DatabaseError(error))
+                            };
+                            match result
                             {
                                 Ok(v) => return v,
-                                Err(error) => {
+                                Err(ServerError::DatabaseError(error)) => {
                                     visual::error(Some(error), "Database Error");
                                 }
+                                Err(error) => return error.to_response()
                             }
                         }
                     }
@@ -691,9 +1451,10 @@ async fn handle_custom_request
                     (args.get("semester"),args.get("semester_name"),args.get("start_date"),args.get("end_date"))
                     {
                         if let Ok(semester) = semester_str.parse::<u8>(){
+                            let week_cycle = args.get("week_cycle").and_then(|s| s.parse::<u8>().ok()).unwrap_or(1);
                             match manipulate_database(MainpulationType::Insert(
                                     backend::POST::Semester(
-                                        Some((semester, semester_name.to_string(), start_date.to_string(), end_date.to_string())))), &*db.lock().await)
+                                        Some((semester, semester_name.to_string(), start_date.to_string(), end_date.to_string(), week_cycle)))), &*db.lock().await)
                             {
                                 Ok(v) => return v,
                                 Err(error) => {
@@ -728,8 +1489,9 @@ async fn handle_custom_request
                 7 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("teacher_id"), args.get("teacher_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let pensum = args.get("pensum").and_then(|s| s.parse::<u8>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Teacher(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Teacher(Some ((teacher_id, teacher_name.to_string(), pensum)) )), &*db.lock().await)
                             {
                                 Ok(v) => return v,
                                 Err(error) => {
@@ -743,8 +1505,9 @@ async fn handle_custom_request
                 8 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("class_id"), args.get("class_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let students = args.get("students").and_then(|s| s.parse::<u16>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Class(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Class(Some ((teacher_id, teacher_name.to_string(), students)) )), &*db.lock().await)
                             {
                                 Ok(v) => return v,
                                 Err(error) => {
@@ -758,8 +1521,10 @@ async fn handle_custom_request
                 9 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("classroom_id"), args.get("classroom_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let corridor = args.get("place_id").and_then(|s| s.parse::<u16>().ok());
+                            let capacity = args.get("capacity").and_then(|s| s.parse::<u16>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Classroom(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Classroom(Some ((teacher_id, teacher_name.to_string(), corridor, capacity)) )), &*db.lock().await)
                             {
                                 Ok(v) => return v,
                                 Err(error) => {
@@ -799,60 +1564,569 @@ async fn handle_custom_request
                         }
                     }
                 }
-                _ => {}
-            }
-        }
-        RequestType::Other(val) => {
-            #[allow(warnings)]
-            match (val.as_str(), parsed_request.req_numb){
-                ("PAS", 0) => {
-                    if let (Some(password), Some(set_password)) = (&args.get("password"), get_config().await.and_then(|s| Some(s.password))){
-                        return (**password == set_password).to_string();
-                    }
-                    todo!()
-                }
-                ("DELETE", 0) => {
-                    if let (Some(classid_str), Some(weekd_str), Some(lessonh_str), Some(semester_str), Some(ay_str)) = (args.get("class_id"),
-                    args.get("weekday"), args.get("lesson_hour"), args.get("semester"), args.get("academic_year")){
-                        if let (Ok(class), Ok(weekd), Ok(lessonh), Ok(semester), Ok(academic_year)) = 
-                            (classid_str.parse::<u16>(), weekd_str.parse::<u8>(), lessonh_str.parse::<u16>(), semester_str.parse::<u8>(), ay_str.parse::<u8>()){
-                            match manipulate_database(
-                                MainpulationType::Delete(
-                                    backend::Delete::Lesson { class, weekd, lessonh, semester, academic_year }), 
+                // Requirement
+                12 => {
+                    if let (Some(classid_str), Some(subjectid_str), Some(teacherid_str), Some(hours_str), Some(semester_str), Some(ay_str)) = 
+                    (args.get("class_id"), args.get("subject_id"), args.get("teacher_id"), args.get("hours"), 
+                     args.get("semester"), args.get("academic_year"))
+                    {
+                        if let (Ok(class_id), Ok(subject_id), Ok(teacher_id), Ok(hours), Ok(semester), Ok(academic_year)) = 
+                        (classid_str.parse::<u16>(), subjectid_str.parse::<u16>(), teacherid_str.parse::<u16>(), hours_str.parse::<u8>(),
+                         semester_str.parse::<u8>(), ay_str.parse::<u8>())
+                        {
+                            let classroom_id = args.get("classroom_id").and_then(|s| s.parse::<u16>().ok());
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::Requirement(Some((class_id, subject_id, teacher_id, classroom_id, hours, semester, academic_year)))), 
                                 &*db.lock().await)
                             {
-                                Ok(response) => return response,
-                                Err(err) => {
-                                    visual::error(Some(err), "Database Error");
-                                    return lang.english_or("E-D0: We couldn't delete data from database", "E-D0: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
                                 }
                             }
                         }
                     }
                 }
-                ("DELETE", 1) => {
-                    if let (Some(teacherid_str), Some(weekd_str), Some(lessonh_str), Some(semester_str), Some(ay_str)) = (args.get("teacher_id"),
-                    args.get("weekday"), args.get("break_num"), args.get("semester"), args.get("academic_year")){
-                        if let (Ok(teacher_id), Ok(weekday), Ok(break_num), Ok(semester), Ok(academic_year)) = 
-                            (teacherid_str.parse::<u16>(), weekd_str.parse::<u8>(), lessonh_str.parse::<u8>(), semester_str.parse::<u8>(), ay_str.parse::<u8>()){
-                            match manipulate_database(
-                                MainpulationType::Delete(
-                                    backend::Delete::Duty { teacher_id, weekday, break_num, semester, academic_year }), 
-                                &*db.lock().await)
-                            {
-                                Ok(response) => return response,
-                                Err(err) => {
-                                    visual::error(Some(err), "Database Error");
-                                    return lang.english_or("E-D1: We couldn't delete data from database", "E-D1: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                // Generate draft timetable
+                13 => {
+                    if let (Some(semester_str), Some(ay_str)) = (args.get("semester"), args.get("academic_year")){
+                        if let (Ok(semester), Ok(academic_year)) = (semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            match generate_draft(semester, academic_year, &*db.lock().await){
+                                Ok(report) => {
+                                    let mut to_return = format!("<p>{} {}</p>", 
+                                        lang.english_or("Lessons in draft:", "Lekcje w szkicu:"), report.placed);
+                                    if !report.unplaced.is_empty(){
+                                        to_return.push_str(&format!("<p>{}</p><table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>", 
+                                            lang.english_or("Couldn't place:", "Nie udało się umieścić:"),
+                                            lang.english_or("Class ID", "Identyfikator klasy"), lang.english_or("Subject ID", "Identyfikator przedmiotu"),
+                                            lang.english_or("Teacher ID", "Identyfikator nauczyciela"), lang.english_or("Hours", "Godziny")));
+                                        for (requirement, hours) in report.unplaced{
+                                            to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                                                requirement.class_id, requirement.subject_id, requirement.teacher_id, hours));
+                                        }
+                                        to_return.push_str("</table>");
+                                    }
+                                    return to_return;
+                                }
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
                                 }
                             }
                         }
                     }
                 }
-                ("DELETE", 2) => {
-                    if let Some(id_str) = args.get("id"){
-                        if let Ok(id) = id_str.parse::<u16>(){
-                            match manipulate_database(MainpulationType::Delete(backend::Delete::Class { class: id }), &*db.lock().await)
+                // Apply draft timetable
+                14 => {
+                    if let (Some(semester_str), Some(ay_str)) = (args.get("semester"), args.get("academic_year")){
+                        if let (Ok(semester), Ok(academic_year)) = (semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            match apply_draft(semester, academic_year, &*db.lock().await){
+                                Ok(v) => return v,
+                                Err(ServerError::DatabaseError(error)) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                                Err(error) => return error.to_response()
+                            }
+                        }
+                    }
+                }
+                // Generate duty roster
+                15 => {
+                    if let (Some(semester_str), Some(ay_str)) = (args.get("semester"), args.get("academic_year")){
+                        if let (Ok(semester), Ok(academic_year)) = (semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            match generate_duties(semester, academic_year, &*db.lock().await){
+                                Ok(rosters) => {
+                                    let mut to_return = format!("<p>{} {}</p>", 
+                                        lang.english_or("New duties:", "Nowe dyżury:"), rosters.iter().map(|(_, roster)| roster.duties.len()).sum::<usize>());
+                                    to_return.push_str(&format!("<table><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                                        lang.english_or("Week", "Tydzień"), lang.english_or("Weekday", "Dzień tygodnia"), lang.english_or("Break num", "Przerwa"),
+                                        lang.english_or("Place ID", "Identyfikator miejsca"), lang.english_or("Teacher ID", "Identyfikator nauczyciela")));
+                                    for (week, roster) in rosters{
+                                        // week 0 means every week
+                                        let week = match week{
+                                            0 => lang.english_or("Every", "Każdy"),
+                                            _ => week.to_string()
+                                        };
+                                        for duty in roster.duties{
+                                            to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                                week, weekd_to_string(&lang, duty.weekday), duty.break_num, duty.place_id, duty.teacher_id));
+                                        }
+                                        for (weekday, break_num, place_id) in roster.unfilled{
+                                            to_return.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                                                week, weekd_to_string(&lang, weekday), break_num, place_id, lang.english_or("Nobody available", "Nikt nie jest dostępny")));
+                                        }
+                                    }
+                                    to_return.push_str("</table>");
+                                    return to_return;
+                                }
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Duty exclusion
+                16 => {
+                    if let Some(teacherid_str) = args.get("teacher_id"){
+                        if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let weekday   = args.get("weekday").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let break_num = args.get("break_num").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::DutyExclusion(Some((teacher_id, weekday, break_num)))), &*db.lock().await)
+                            {
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Absence
+                17 => {
+                    if let (Some(teacherid_str), Some(date_str)) = (args.get("teacher_id"), args.get("date")){
+                        if let (Ok(teacher_id), Some(date)) = (teacherid_str.parse::<u16>(), parse_date(date_str)){
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::Absence(Some((teacher_id, date)))), &*db.lock().await)
+                            {
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Substitution
+                18 => {
+                    if let (Some(date_str), Some(classid_str), Some(lessonh_str), Some(teacherid_str)) = 
+                    (args.get("date"), args.get("class_id"), args.get("lesson_hour"), args.get("teacher_id"))
+                    {
+                        if let (Some(date), Ok(class_id), Ok(lesson_hour), Ok(teacher_id)) = 
+                        (parse_date(date_str), classid_str.parse::<u16>(), lessonh_str.parse::<u16>(), teacherid_str.parse::<u16>())
+                        {
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::Substitution(Some((date, class_id, lesson_hour, teacher_id, group_id)))), &*db.lock().await)
+                            {
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // School calendar entry
+                19 => {
+                    if let (Some(id_str), Some(start_str), Some(end_str), Some(type_str), Some(name)) = 
+                    (args.get("calendar_id"), args.get("start_date"), args.get("end_date"), args.get("day_type"), args.get("name"))
+                    {
+                        if let (Ok(calendar_id), Some(start_date), Some(end_date), Some(day_type)) = 
+                        (id_str.parse::<u16>(), parse_date(start_str), parse_date(end_str), DayType::parse(type_str))
+                        {
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::CalendarEntry(Some((calendar_id, start_date, end_date, day_type, name.to_string())))), 
+                                &*db.lock().await)
+                            {
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Class group
+                20 => {
+                    if let (Some(groupid_str), Some(classid_str), Some(group_name)) = 
+                    (args.get("group_id"), args.get("class_id"), args.get("group_name"))
+                    {
+                        if let (Ok(group_id), Ok(class_id)) = (groupid_str.parse::<u16>(), classid_str.parse::<u16>()){
+                            match manipulate_database(MainpulationType::Insert(
+                                backend::POST::Group(Some((group_id, class_id, group_name.to_string())))), &*db.lock().await)
+                            {
+                                Ok(v) => return v,
+                                Err(error) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                            }
+                        }
+                    }
+                }
+                // Co-teacher of lesson
+                21 => {
+                    if let (Some(weekday_str), Some(classid_str), Some(lessonhour_str), Some(semester_str), Some(academicyear_str), Some(teacherid_str)) = 
+                    (args.get("weekday"), args.get("class_id"), args.get("lesson_hour"), args.get("semester"), args.get("academic_year"), args.get("teacher_id"))
+                    {
+                        if let (Ok(weekday), Ok(class_id), Ok(lesson_hour), Ok(semester), Ok(academic_year), Ok(teacher_id)) = 
+                        (weekday_str.parse::<u8>(), classid_str.parse::<u16>(), lessonhour_str.parse::<u16>(), semester_str.parse::<u8>(), 
+                         academicyear_str.parse::<u8>(), teacherid_str.parse::<u16>())
+                        {
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            let key = (weekday, class_id, lesson_hour, semester, academic_year, week, group_id);
+                            let db = db.lock().await;
+                            let result = match has_draft(semester, academic_year, &db){
+                                Ok(true)   => insert_draft_co_teacher(key, teacher_id, &db),
+                                Ok(false)  => insert_co_teacher(key, teacher_id, &db),
+                                Err(error) => Err(ServerError::DatabaseError(error))
+                            };
+                            match result
+                            {
+                                Ok(v) => return v,
+                                Err(ServerError::DatabaseError(error)) => {
+                                    visual::error(Some(error), "Database Error");
+                                }
+                                Err(error) => return error.to_response()
+                            }
+                        }
+                    }
+                }
+                // Qualification
+                22 => {
+                    if let (Some(teacher_id), Some(subject_id)) = 
+                    (args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()), args.get("subject_id").and_then(|s| s.parse::<u16>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::Qualification(Some((teacher_id, subject_id)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Availability
+                23 => {
+                    if let (Some(teacher_id), Some(weekday), Some(lesson_hour), Some(availability)) = 
+                    (args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()), args.get("weekday").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok()), args.get("availability").and_then(|s| Availability::parse(s)))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::Availability(Some((teacher_id, weekday, lesson_hour, availability)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Classroom feature
+                24 => {
+                    if let (Some(classroom_id), Some(feature)) = (args.get("classroom_id").and_then(|s| s.parse::<u16>().ok()), args.get("feature")){
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::ClassroomFeature(Some((classroom_id, feature.to_string())))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Feature required by subject
+                25 => {
+                    if let (Some(subject_id), Some(feature)) = (args.get("subject_id").and_then(|s| s.parse::<u16>().ok()), args.get("feature")){
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::SubjectFeature(Some((subject_id, feature.to_string())))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Booking
+                26 => {
+                    if let (Some(booking_id), Some(classroom_id), Some(date), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute), 
+                        Some(teacher_id), Some(purpose)) = 
+                    (args.get("booking_id").and_then(|s| s.parse::<u16>().ok()), args.get("classroom_id").and_then(|s| s.parse::<u16>().ok()),
+                     args.get("date").and_then(|s| parse_date(s)), args.get("start_hour").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("start_minute").and_then(|s| s.parse::<u8>().ok()), args.get("end_hour").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_minute").and_then(|s| s.parse::<u8>().ok()), args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()),
+                     args.get("purpose"))
+                    {
+                        match insert_booking((booking_id, classroom_id, date, start_hour, start_minute, end_hour, end_minute, teacher_id, purpose.to_string()), 
+                            &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(ServerError::DatabaseError(error)) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                            Err(error) => return error.to_response()
+                        }
+                    }
+                }
+                // Event
+                27 => {
+                    if let (Some(event_id), Some(name), Some(event_type), Some(date), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                    (args.get("event_id").and_then(|s| s.parse::<u16>().ok()), args.get("name"), args.get("event_type").and_then(|s| EventType::parse(s)),
+                     args.get("date").and_then(|s| parse_date(s)), args.get("start_hour").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("start_minute").and_then(|s| s.parse::<u8>().ok()), args.get("end_hour").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_minute").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        let overrides_lessons = args.get("overrides_lessons").is_some_and(|s| s == "true");
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::Event(Some((event_id, name.to_string(), event_type, date, start_hour, start_minute, end_hour, end_minute, overrides_lessons)))), 
+                            &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Class, teacher or classroom taking part in event
+                28 => {
+                    if let (Some(event_id), Some(participant)) = (args.get("event_id").and_then(|s| s.parse::<u16>().ok()), owner_from_args(&args)){
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::EventParticipant(Some((event_id, participant)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // One-off change of lesson
+                29 => {
+                    if let (Some(date), Some(class_id), Some(lesson_hour)) = (args.get("date").and_then(|s| parse_date(s)), 
+                        args.get("class_id").and_then(|s| s.parse::<u16>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok()))
+                    {
+                        let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                        let cancelled = args.get("cancelled").is_some_and(|s| s == "true");
+                        let classroom_id = args.get("classroom_id").and_then(|s| s.parse::<u16>().ok());
+                        let new_lesson_hour = args.get("new_lesson_hour").and_then(|s| s.parse::<u16>().ok());
+                        let teacher_id = args.get("teacher_id").and_then(|s| s.parse::<u16>().ok());
+                        match insert_lesson_override((date, class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(ServerError::DatabaseError(error)) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                            Err(error) => return error.to_response()
+                        }
+                    }
+                }
+                // Bell schedule
+                30 => {
+                    if let (Some(schedule_id), Some(name)) = (args.get("schedule_id").and_then(|s| s.parse::<u16>().ok()), args.get("name")){
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::BellSchedule(Some((schedule_id, name.to_string())))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Time of lesson hour in bell schedule
+                31 => {
+                    if let (Some(schedule_id), Some(lesson_hour), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                    (args.get("schedule_id").and_then(|s| s.parse::<u16>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok()),
+                     args.get("start_hour").and_then(|s| s.parse::<u8>().ok()), args.get("start_minute").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_hour").and_then(|s| s.parse::<u8>().ok()), args.get("end_minute").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::BellScheduleHour(Some((schedule_id, lesson_hour, start_hour, start_minute, end_hour, end_minute)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Time of break in bell schedule
+                32 => {
+                    if let (Some(schedule_id), Some(break_num), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                    (args.get("schedule_id").and_then(|s| s.parse::<u16>().ok()), args.get("break_num").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("start_hour").and_then(|s| s.parse::<u8>().ok()), args.get("start_minute").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_hour").and_then(|s| s.parse::<u8>().ok()), args.get("end_minute").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::BellScheduleBreak(Some((schedule_id, break_num, start_hour, start_minute, end_hour, end_minute)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Bell schedule used on date
+                33 => {
+                    if let (Some(date), Some(schedule_id)) = (args.get("date").and_then(|s| parse_date(s)), args.get("schedule_id").and_then(|s| s.parse::<u16>().ok())){
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::ScheduleDay(Some((date, schedule_id)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Time of lesson hour on weekday
+                34 => {
+                    if let (Some(weekday), Some(lesson_hour), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                    (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok()),
+                     args.get("start_hour").and_then(|s| s.parse::<u8>().ok()), args.get("start_minute").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_hour").and_then(|s| s.parse::<u8>().ok()), args.get("end_minute").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::WeekdayHour(Some((weekday, lesson_hour, start_hour, start_minute, end_hour, end_minute)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Time of break on weekday
+                35 => {
+                    if let (Some(weekday), Some(break_num), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                    (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("break_num").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("start_hour").and_then(|s| s.parse::<u8>().ok()), args.get("start_minute").and_then(|s| s.parse::<u8>().ok()),
+                     args.get("end_hour").and_then(|s| s.parse::<u8>().ok()), args.get("end_minute").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Insert(
+                            backend::POST::WeekdayBreak(Some((weekday, break_num, start_hour, start_minute, end_hour, end_minute)))), &*db.lock().await)
+                        {
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Start next academic year
+                36 => {
+                    if let (Some(academic_year), Some(year_name), Some(final_grade)) = (args.get("academic_year").and_then(|s| s.parse::<u8>().ok()), 
+                        args.get("year_name"), args.get("final_grade").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        let copy_timetable = args.get("copy_timetable").is_some_and(|s| s == "true");
+                        let preview = args.get("preview").is_some_and(|s| s == "true");
+                        match rollover_year(academic_year, year_name, final_grade, copy_timetable, preview, &*db.lock().await){
+                            Ok(report) => {
+                                let mut to_return = match preview{
+                                    true  => lang.english_or("<p>Preview, nothing was saved</p>", "<p>Podgląd, nic nie zostało zapisane</p>"),
+                                    false => String::new()
+                                };
+                                to_return.push_str(&format!("<p>{} {}</p>", lang.english_or("New academic year:", "Nowy rok szkolny:"), report.academic_year));
+                                to_return.push_str(&format!("<p>{} {}</p>", lang.english_or("New semesters:", "Nowe semestry:"), 
+                                    report.semesters.iter().map(|(semester, name)| format!("{} ({})", name, semester)).collect::<Vec<String>>().join(", ")));
+                                to_return.push_str(&format!("<table><tr><th>{}</th><th>{}</th></tr>", 
+                                    lang.english_or("Class", "Klasa"), lang.english_or("Next year", "W nowym roku")));
+                                for (class, new_name) in report.promoted{
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", class, new_name));
+                                }
+                                for class in report.archived{
+                                    to_return.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", class, lang.english_or("archived", "zarchiwizowana")));
+                                }
+                                to_return.push_str("</table>");
+                                if copy_timetable{
+                                    to_return.push_str(&format!("<p>{} {}</p><p>{} {}</p>", 
+                                        lang.english_or("Lessons in draft:", "Lekcje w szkicu:"), report.lessons,
+                                        lang.english_or("Duties in draft:", "Dyżury w szkicu:"), report.duties));
+                                }
+                                return to_return;
+                            }
+                            Err(error) => {
+                                if error == rusqlite::Error::QueryReturnedNoRows{
+                                    return lang.english_or("<p>Academic year doesn't exist</p>", "<p>Rok szkolny nie istnieje</p>");
+                                }
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                // Copy timetable of semester into draft
+                37 => {
+                    if let (Some(semester), Some(academic_year)) = 
+                    (args.get("semester").and_then(|s| s.parse::<u8>().ok()), args.get("academic_year").and_then(|s| s.parse::<u8>().ok()))
+                    {
+                        match copy_to_draft(semester, academic_year, &*db.lock().await){
+                            Ok(v) => return v,
+                            Err(error) => {
+                                visual::error(Some(error), "Database Error");
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        RequestType::Other(val) => {
+            #[allow(warnings)]
+            match (val.as_str(), parsed_request.req_numb){
+                ("PAS", 0) => {
+                    if let (Some(password), Some(set_password)) = (&args.get("password"), get_config().await.and_then(|s| Some(s.password))){
+                        return (**password == set_password).to_string();
+                    }
+                    todo!()
+                }
+                ("DELETE", 0) => {
+                    if let (Some(classid_str), Some(weekd_str), Some(lessonh_str), Some(semester_str), Some(ay_str)) = (args.get("class_id"),
+                    args.get("weekday"), args.get("lesson_hour"), args.get("semester"), args.get("academic_year")){
+                        if let (Ok(class), Ok(weekd), Ok(lessonh), Ok(semester), Ok(academic_year)) = 
+                            (classid_str.parse::<u16>(), weekd_str.parse::<u8>(), lessonh_str.parse::<u16>(), semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            let db = db.lock().await;
+                            let result = has_draft(semester, academic_year, &db)
+                                .map(|open| open || args.get("draft").is_some_and(|s| s == "true"))
+                                .and_then(|draft| manipulate_database(MainpulationType::Delete(match draft{
+                                    true  => backend::Delete::DraftLesson { class, weekd, lessonh, semester, academic_year, week, group_id },
+                                    false => backend::Delete::Lesson { class, weekd, lessonh, semester, academic_year, week, group_id }
+                                }), &db));
+                            match result
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D0: We couldn't delete data from database", "E-D0: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 1) => {
+                    if let (Some(teacherid_str), Some(weekd_str), Some(lessonh_str), Some(semester_str), Some(ay_str)) = (args.get("teacher_id"),
+                    args.get("weekday"), args.get("break_num"), args.get("semester"), args.get("academic_year")){
+                        if let (Ok(teacher_id), Ok(weekday), Ok(break_num), Ok(semester), Ok(academic_year)) = 
+                            (teacherid_str.parse::<u16>(), weekd_str.parse::<u8>(), lessonh_str.parse::<u8>(), semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let db = db.lock().await;
+                            let result = has_draft(semester, academic_year, &db)
+                                .map(|open| open || args.get("draft").is_some_and(|s| s == "true"))
+                                .and_then(|draft| manipulate_database(MainpulationType::Delete(match draft{
+                                    true  => backend::Delete::DraftDuty { teacher_id, weekday, break_num, semester, academic_year, week },
+                                    false => backend::Delete::Duty { teacher_id, weekday, break_num, semester, academic_year, week }
+                                }), &db));
+                            match result
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D1: We couldn't delete data from database", "E-D1: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 2) => {
+                    if let Some(id_str) = args.get("id"){
+                        if let Ok(id) = id_str.parse::<u16>(){
+                            match manipulate_database(MainpulationType::Delete(backend::Delete::Class { class: id }), &*db.lock().await)
                             {
                                 Ok(res) => return res,
                                 Err(err) => {
@@ -975,6 +2249,269 @@ async fn handle_custom_request
                         }
                     }
                 }
+                ("DELETE", 11) => {
+                    if let (Some(classid_str), Some(subjectid_str), Some(semester_str), Some(ay_str)) = 
+                    (args.get("class_id"), args.get("subject_id"), args.get("semester"), args.get("academic_year")){
+                        if let (Ok(class), Ok(subject), Ok(semester), Ok(academic_year)) = 
+                            (classid_str.parse::<u16>(), subjectid_str.parse::<u16>(), semester_str.parse::<u8>(), ay_str.parse::<u8>()){
+                            match manipulate_database(
+                                MainpulationType::Delete(
+                                    backend::Delete::Requirement { class, subject, semester, academic_year }), 
+                                &*db.lock().await)
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D11: We couldn't delete data from database", "E-D11: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 12) => {
+                    if let Some(teacherid_str) = args.get("teacher_id"){
+                        if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let weekday   = args.get("weekday").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let break_num = args.get("break_num").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            match manipulate_database(
+                                MainpulationType::Delete(backend::Delete::DutyExclusion { teacher_id, weekday, break_num }), 
+                                &*db.lock().await)
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D12: We couldn't delete data from database", "E-D12: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 13) => {
+                    if let (Some(teacherid_str), Some(date_str)) = (args.get("teacher_id"), args.get("date")){
+                        if let (Ok(teacher_id), Some(date)) = (teacherid_str.parse::<u16>(), parse_date(date_str)){
+                            match manipulate_database(
+                                MainpulationType::Delete(backend::Delete::Absence { teacher_id, date }), 
+                                &*db.lock().await)
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D13: We couldn't delete data from database", "E-D13: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 14) => {
+                    if let (Some(date_str), Some(classid_str), Some(lessonh_str)) = (args.get("date"), args.get("class_id"), args.get("lesson_hour")){
+                        if let (Some(date), Ok(class_id), Ok(lesson_hour)) = (parse_date(date_str), classid_str.parse::<u16>(), lessonh_str.parse::<u16>()){
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            match manipulate_database(
+                                MainpulationType::Delete(backend::Delete::Substitution { date, class_id, lesson_hour, group_id }), 
+                                &*db.lock().await)
+                            {
+                                Ok(response) => return response,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D14: We couldn't delete data from database", "E-D14: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 15) => {
+                    if let Some(id_str) = args.get("id"){
+                        if let Ok(calendar_id) = id_str.parse::<u16>(){
+                            match manipulate_database(MainpulationType::Delete(backend::Delete::CalendarEntry { calendar_id }), &*db.lock().await)
+                            {
+                                Ok(res) => return res,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D15: We couldn't delete data from database", "E-D15: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 16) => {
+                    if let Some(id_str) = args.get("id"){
+                        if let Ok(group_id) = id_str.parse::<u16>(){
+                            match manipulate_database(MainpulationType::Delete(backend::Delete::Group { group_id }), &*db.lock().await)
+                            {
+                                Ok(res) => return res,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D16: We couldn't delete data from database", "E-D16: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 17) => {
+                    if let (Some(weekday_str), Some(classid_str), Some(lessonhour_str), Some(semester_str), Some(academicyear_str), Some(teacherid_str)) = 
+                    (args.get("weekday"), args.get("class_id"), args.get("lesson_hour"), args.get("semester"), args.get("academic_year"), args.get("teacher_id"))
+                    {
+                        if let (Ok(weekday), Ok(class_id), Ok(lesson_hour), Ok(semester), Ok(academic_year), Ok(teacher_id)) = 
+                        (weekday_str.parse::<u8>(), classid_str.parse::<u16>(), lessonhour_str.parse::<u16>(), semester_str.parse::<u8>(), 
+                         academicyear_str.parse::<u8>(), teacherid_str.parse::<u16>())
+                        {
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            let key = (weekday, class_id, lesson_hour, semester, academic_year, week, group_id);
+                            let db = db.lock().await;
+                            let result = has_draft(semester, academic_year, &db)
+                                .and_then(|draft| manipulate_database(MainpulationType::Delete(match draft{
+                                    true  => backend::Delete::DraftCoTeacher { key, teacher_id },
+                                    false => backend::Delete::CoTeacher { key, teacher_id }
+                                }), &db));
+                            match result
+                            {
+                                Ok(res) => return res,
+                                Err(err) => {
+                                    visual::error(Some(err), "Database Error");
+                                    return lang.english_or("E-D17: We couldn't delete data from database", "E-D17: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                                }
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 18) => {
+                    if let (Some(teacher_id), Some(subject_id)) = 
+                    (args.get("teacher_id").and_then(|s| s.parse::<u16>().ok()), args.get("subject_id").and_then(|s| s.parse::<u16>().ok()))
+                    {
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::Qualification { teacher_id, subject_id }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D18: We couldn't delete data from database", "E-D18: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 19) => {
+                    if let (Some(classroom_id), Some(feature)) = (args.get("classroom_id").and_then(|s| s.parse::<u16>().ok()), args.get("feature")){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::ClassroomFeature { classroom_id, feature: feature.to_string() }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D19: We couldn't delete data from database", "E-D19: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 20) => {
+                    if let (Some(subject_id), Some(feature)) = (args.get("subject_id").and_then(|s| s.parse::<u16>().ok()), args.get("feature")){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::SubjectFeature { subject_id, feature: feature.to_string() }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D20: We couldn't delete data from database", "E-D20: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 21) => {
+                    if let Some(booking_id) = args.get("booking_id").and_then(|s| s.parse::<u16>().ok()){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::Booking { booking_id }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D21: We couldn't delete data from database", "E-D21: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 22) => {
+                    if let Some(event_id) = args.get("event_id").and_then(|s| s.parse::<u16>().ok()){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::Event { event_id }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D22: We couldn't delete data from database", "E-D22: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 23) => {
+                    if let (Some(event_id), Some(participant)) = (args.get("event_id").and_then(|s| s.parse::<u16>().ok()), owner_from_args(&args)){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::EventParticipant { event_id, participant }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D23: We couldn't delete data from database", "E-D23: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 24) => {
+                    if let (Some(date), Some(class_id), Some(lesson_hour)) = (args.get("date").and_then(|s| parse_date(s)), 
+                        args.get("class_id").and_then(|s| s.parse::<u16>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok()))
+                    {
+                        let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::LessonOverride { date, class_id, lesson_hour, group_id }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D24: We couldn't delete data from database", "E-D24: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 25) => {
+                    if let Some(schedule_id) = args.get("schedule_id").and_then(|s| s.parse::<u16>().ok()){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::BellSchedule { schedule_id }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D25: We couldn't delete data from database", "E-D25: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 26) => {
+                    if let Some(date) = args.get("date").and_then(|s| parse_date(s)){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::ScheduleDay { date }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D26: We couldn't delete data from database", "E-D26: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 27) => {
+                    if let (Some(weekday), Some(lesson_hour)) = (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok())){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::WeekdayHour { weekday, lesson_hour }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D27: We couldn't delete data from database", "E-D27: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
+                ("DELETE", 28) => {
+                    if let (Some(weekday), Some(break_num)) = (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("break_num").and_then(|s| s.parse::<u8>().ok())){
+                        match manipulate_database(MainpulationType::Delete(backend::Delete::WeekdayBreak { weekday, break_num }), &*db.lock().await)
+                        {
+                            Ok(res) => return res,
+                            Err(err) => {
+                                visual::error(Some(err), "Database Error");
+                                return lang.english_or("E-D28: We couldn't delete data from database", "E-D28: Nie byliśmy w stanie usunąć danych z bazy danych").to_string();
+                            }
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -997,6 +2534,16 @@ async fn not_found(tcp: &mut TcpStream) {
     }
 }
 
+/// (semester, academic_year) from `semester` and `academic_year` arguments 
+/// or semester of `date` (today if not given). None if date is invalid
+fn semester_from_args(args: &HashMap<String, String>, db: &rusqlite::Connection) -> Option<Result<(u8, u8), rusqlite::Error>>{
+    match (args.get("semester").and_then(|s| s.parse::<u8>().ok()), args.get("academic_year").and_then(|s| s.parse::<u8>().ok())){
+        (Some(semester), Some(academic_year)) => Some(Ok((semester, academic_year))),
+        _ => date_or_today(args.get("date"))
+            .map(|date| get_year_and_semester(date, db).map(|(academic_year, semester)| (semester, academic_year)))
+    }
+}
+
 #[allow(dead_code)]
 fn get_types(line: String) -> Vec<String> {
     let split_line = line.split_whitespace().collect::<Vec<&str>>();
@@ -1011,6 +2558,37 @@ fn get_types(line: String) -> Vec<String> {
     types
 }
 
+pub fn activity_to_string(lang: &Language, activity: &Activity) -> String{
+    let time = |hour: &JoinedHour| match (hour.start_hour, hour.start_minute, hour.end_hour, hour.end_minutes){
+        (Some(sh), Some(sm), Some(eh), Some(em)) => format!("{:02}:{:02}-{:02}:{:02}", sh, sm, eh, em),
+        _ => String::new()
+    };
+    match activity{
+        Activity::Lesson(lesson) => {
+            let subject = match &lesson.group{
+                Some(group) => format!("{} - {}", lesson.subject.clone().unwrap_or_default(), group),
+                None        => lesson.subject.clone().unwrap_or_default()
+            };
+            format!("{} {} ({}, {}, {})", time(&lesson.lessonh), subject, lesson.class.clone().unwrap_or_default(),
+                lesson.classroom.clone().unwrap_or_default(), lesson.teacher.clone().unwrap_or_default())
+        }
+        Activity::Duty(duty) => format!("{} {}: {}", time(&duty.break_num), lang.english_or("Duty", "Dyżur"), duty.place.clone().unwrap_or_default()),
+        Activity::BeforeSchool => lang.english_or("Before school", "Przed lekcjami"),
+        Activity::FreePeriod   => lang.english_or("Free period", "Okienko"),
+        Activity::AfterSchool  => lang.english_or("After school", "Po lekcjach"),
+        Activity::NoSchool     => lang.english_or("No school today", "Dzisiaj nie ma zajęć")
+    }
+}
+
+pub fn event_type_to_string(lang: &Language, event_type: EventType) -> String{
+    match event_type{
+        EventType::Exam          => lang.english_or("Exam", "Egzamin"),
+        EventType::Trip          => lang.english_or("Trip", "Wycieczka"),
+        EventType::ParentMeeting => lang.english_or("Parent meeting", "Wywiadówka"),
+        EventType::Other         => lang.english_or("Other", "Inne")
+    }
+}
+
 pub fn weekd_to_string(lang: &Language, weekd: u8) -> String{
     match weekd{
         1 => lang.english_or("Monday"   ,"Poniedziałek" ),
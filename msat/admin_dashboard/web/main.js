@@ -130,6 +130,24 @@ function delete_component(){
 			<option value='9'>${en_or_pl("Academic Year", "Rok szkolny")}</option>
 			<option value='10'>${en_or_pl("Lesson", "Lekcję")}</option>
 			<option value='11'>${en_or_pl("Duty", "Dyżur")}</option>
+			<option value='12'>${en_or_pl("Curriculum requirement", "Wymaganie programowe")}</option>
+			<option value='13'>${en_or_pl("Duty exclusion", "Wykluczenie z dyżurów")}</option>
+			<option value='14'>${en_or_pl("Teacher absence", "Nieobecność nauczyciela")}</option>
+			<option value='15'>${en_or_pl("Substitution", "Zastępstwo")}</option>
+			<option value='16'>${en_or_pl("Calendar entry", "Wpis w kalendarzu")}</option>
+			<option value='17'>${en_or_pl("Class group", "Grupę klasy")}</option>
+			<option value='18'>${en_or_pl("Co-teacher", "Dodatkowego nauczyciela")}</option>
+			<option value='19'>${en_or_pl("Qualification", "Kwalifikację")}</option>
+			<option value='20'>${en_or_pl("Classroom feature", "Wyposażenie sali")}</option>
+			<option value='21'>${en_or_pl("Feature required by subject", "Wyposażenie wymagane przez przedmiot")}</option>
+			<option value='22'>${en_or_pl("Room booking", "Rezerwację sali")}</option>
+			<option value='23'>${en_or_pl("Event", "Wydarzenie")}</option>
+			<option value='24'>${en_or_pl("Event participant", "Uczestnika wydarzenia")}</option>
+			<option value='25'>${en_or_pl("Lesson change", "Zmianę lekcji")}</option>
+			<option value='26'>${en_or_pl("Bell schedule", "Rodzaj dzwonków")}</option>
+			<option value='27'>${en_or_pl("Bell schedule of date", "Dzwonki w dniu")}</option>
+			<option value='28'>${en_or_pl("Lesson hour of weekday", "Godzinę lekcyjną w dniu tygodnia")}</option>
+			<option value='29'>${en_or_pl("Break of weekday", "Przerwę w dniu tygodnia")}</option>
 		</select>
 		<div id='selection_output'>
 
@@ -275,6 +293,9 @@ function delete_component(){
 				<input id='lh' type='number' min=1 max=255 placeholder=${en_or_pl("Lesson hour", "Godzina Lekcyjna")}>
 				<input id='se' type='number' min=1 max=255 placeholder=${en_or_pl("Semester", "Semestr")}>
 				<input id='ay' type='number' min=1 max=255 placeholder=${en_or_pl("Academic Year", "Rok szkolny")}>
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				<label><input id='dr' type='checkbox'>${en_or_pl("Only in draft", "Tylko w szkicu")}</label>
 				`;
 				$("submit").onclick = function() {
 					let wd = $("wd").value;
@@ -284,7 +305,7 @@ function delete_component(){
 					let ay = $("ay").value;
 					if (wd!=null&&ci!=null&&lh!=null&&se!=null&&ay!=null){
 						fetch(
-						`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+0&weekday=${wd}&class_id=${ci}&semester=${se}&academic_year=${ay}&lesson_hour=${lh}`
+						`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+0&weekday=${wd}&class_id=${ci}&semester=${se}&academic_year=${ay}&lesson_hour=${lh}&week=${$("wk").value}&group_id=${$("gi").value}&draft=${$("dr").checked}`
 						).then(response => response.text())
 						.then(data => {
 							alert(data);
@@ -299,6 +320,8 @@ function delete_component(){
 				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
 				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
 				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<label><input id='dr' type='checkbox'>${en_or_pl("Only in draft", "Tylko w szkicu")}</label>
 				`
 				$("submit").onclick = function() {
 					let wd = $("wd").value;
@@ -308,7 +331,7 @@ function delete_component(){
 					let ay = $("ay").value;
 					if (wd!=null&&bn!=null&&ti!=null&&bp!=null&&se!=null&&ay!=null){
 						fetch(
-`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+1&weekday=${wd}&teacher_id=${ti}&semester=${se}&academic_year=${ay}&break_num=${bn}`
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+1&weekday=${wd}&teacher_id=${ti}&semester=${se}&academic_year=${ay}&break_num=${bn}&week=${$("wk").value}&draft=${$("dr").checked}`
 						).then(response => response.text())
 						.then(data => {
 							alert(data);
@@ -319,6 +342,333 @@ function delete_component(){
 					}
 				}
 				break;
+			case "12":
+				$("selection_output").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa(8)")}">
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				`
+				$("submit").onclick = function() {
+					let ci = $("ci").value;
+					let si = $("si").value;
+					let se = $("se").value;
+					let ay = $("ay").value;
+					if (ci!=null&&si!=null&&se!=null&&ay!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+11&class_id=${ci}&subject_id=${si}&semester=${se}&academic_year=${ay}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "13":
+				$("selection_output").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday (optional)", "Dzień Tygodnia (opcjonalnie)")}">
+				<input id='bn' type='number' min=1 max=255 placeholder="${en_or_pl("Break num (optional)","Przerwa (opcjonalnie)")}">
+				`
+				$("submit").onclick = function() {
+					let ti = $("ti").value;
+					let wd = $("wd").value;
+					let bn = $("bn").value;
+					if (ti!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+12&teacher_id=${ti}&weekday=${wd}&break_num=${bn}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "14":
+				$("selection_output").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function() {
+					let ti = $("ti").value;
+					let dt = $("dt").value;
+					if (ti!=null&&dt!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+13&teacher_id=${ti}&date=${dt}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "15":
+				$("selection_output").innerHTML = `
+				<input id='dt' type='date'>
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina Lekcyjna")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				`
+				$("submit").onclick = function() {
+					let dt = $("dt").value;
+					let ci = $("ci").value;
+					let lh = $("lh").value;
+					if (dt!=null&&ci!=null&&lh!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+14&date=${dt}&class_id=${ci}&lesson_hour=${lh}&group_id=${$("gi").value}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "16":
+				$("selection_output").innerHTML = `
+				<input id='id' type='number' min=1 max=65535 placeholder="${en_or_pl("Entry ID", "Identyfikator wpisu")}">
+				`
+				$("submit").onclick = function() {
+					let id = $("id").value;
+					if (id!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+15&id=${id}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "17":
+				$("selection_output").innerHTML = `
+				<input id='id' type='number' min=1 max=65535 placeholder="${en_or_pl("Group ID", "Identyfikator grupy")}">
+				`
+				$("submit").onclick = function() {
+					let id = $("id").value;
+					if (id!=null){
+						fetch(
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+16&id=${id}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "18":
+				$("selection_output").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina Lekcyjna")}">
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Co-teacher ID", "Dodatkowy nauczyciel")}">
+				`
+				$("submit").onclick = function() {
+					const wd = $("wd").value;
+					const ci = $("ci").value;
+					const lh = $("lh").value;
+					const se = $("se").value;
+					const ay = $("ay").value;
+					const ti = $("ti").value;
+					if (wd!=null&&ci!=null&&lh!=null&&se!=null&&ay!=null&&ti!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+17&weekday=${wd}&class_id=${ci}&lesson_hour=${lh}&semester=${se}&academic_year=${ay}&week=${$("wk").value}&group_id=${$("gi").value}&teacher_id=${ti}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "19":
+				$("selection_output").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				`
+				$("submit").onclick = function() {
+					const ti = $("ti").value;
+					const si = $("si").value;
+					if (ti!=null&&si!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+18&teacher_id=${ti}&subject_id=${si}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "20":
+				$("selection_output").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID", "Sala")}">
+				<input id='ft' type='text' placeholder="${en_or_pl("Feature", "Wyposażenie")}">
+				`
+				$("submit").onclick = function() {
+					const ci = $("ci").value;
+					const ft = $("ft").value;
+					if (ci!=null&&ft!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+19&classroom_id=${ci}&feature=${ft}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "21":
+				$("selection_output").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				<input id='ft' type='text' placeholder="${en_or_pl("Feature", "Wyposażenie")}">
+				`
+				$("submit").onclick = function() {
+					const si = $("si").value;
+					const ft = $("ft").value;
+					if (si!=null&&ft!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+20&subject_id=${si}&feature=${ft}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "22":
+				$("selection_output").innerHTML = `
+				<input id='bi' type='number' min=1 max=65535 placeholder="${en_or_pl("Booking ID", "Rezerwacja")}">
+				`
+				$("submit").onclick = function() {
+					const bi = $("bi").value;
+					if (bi!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+21&booking_id=${bi}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "23":
+				$("selection_output").innerHTML = `
+				<input id='ei' type='number' min=1 max=65535 placeholder="${en_or_pl("Event ID", "Wydarzenie")}">
+				`
+				$("submit").onclick = function() {
+					const ei = $("ei").value;
+					if (ei!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+22&event_id=${ei}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "24":
+				$("selection_output").innerHTML = `
+				<input id='ei' type='number' min=1 max=65535 placeholder="${en_or_pl("Event ID", "Wydarzenie")}">
+				<select id='ow'>
+					<option value='teacher_id'>${en_or_pl("Teacher", "Nauczyciel")}</option>
+					<option value='class_id'>${en_or_pl("Class", "Klasa(8)")}</option>
+					<option value='classroom_id'>${en_or_pl("Classroom", "Klasa")}</option>
+				</select>
+				<input id='id' type='number' min=1 max=65535 placeholder="ID">
+				`
+				$("submit").onclick = function() {
+					const ei = $("ei").value;
+					const id = $("id").value;
+					if (ei!=null&&id!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+23&event_id=${ei}&${$('ow').value}=${id}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "25":
+				$("selection_output").innerHTML = `
+				<input id='dt' type='date'>
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa(8)")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina lekcyjna")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (optional)", "Grupa (opcjonalnie)")}">
+				`
+				$("submit").onclick = function() {
+					const dt = $("dt").value;
+					const ci = $("ci").value;
+					const lh = $("lh").value;
+					if (dt!=null&&ci!=null&&lh!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+24&date=${dt}&class_id=${ci}&lesson_hour=${lh}&group_id=${$('gi').value}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "26":
+				$("selection_output").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Bell schedule ID", "Rodzaj dzwonków")}">
+				`
+				$("submit").onclick = function() {
+					const si = $("si").value;
+					if (si!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+25&schedule_id=${si}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "27":
+				$("selection_output").innerHTML = `
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function() {
+					const dt = $("dt").value;
+					if (dt!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+26&date=${dt}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "28":
+				$("selection_output").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina lekcyjna")}">
+				`
+				$("submit").onclick = function() {
+					const wd = $("wd").value;
+					const lh = $("lh").value;
+					if (wd!=null && lh!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+27&weekday=${wd}&lesson_hour=${lh}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "29":
+				$("selection_output").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='bn' type='number' min=1 max=255 placeholder="${en_or_pl("Break", "Przerwa")}">
+				`
+				$("submit").onclick = function() {
+					const wd = $("wd").value;
+					const bn = $("bn").value;
+					if (wd!=null && bn!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=DELETE+28&weekday=${wd}&break_num=${bn}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
 		}
 	}
 }
@@ -554,6 +904,7 @@ function admin_dashboard_comp(){
 				<option value='get1'>${en_or_pl("Lesson Table for Class", "Plan lekcji dla klasy")}</option>
 				<option value='get3'>${en_or_pl("Lesson Table for Teacher", "Plan lekcji dla nauczyciela")}</option>
 				<option value='get2'>${en_or_pl("Duty List for Teacher", "Lista Dyżuru dla Nauczyciela")}</option>
+				<option value='get13'>${en_or_pl("Conflicts Report", "Raport konfliktów")}</option>
 				<option value='l1'>${en_or_pl("Add lessons", "Dodaj Lekcję")}</option>
 				<option value='d3'>${en_or_pl("Add duty", "Wstaw dyżur")}</option>
 				<option value='y2'>${en_or_pl("Add academic year", "Wstaw rok szkolny")}</option>
@@ -565,6 +916,50 @@ function admin_dashboard_comp(){
 				<option value='c9'>${en_or_pl("Add classroom", "Wstaw klasę (np. informatyczną)")}</option>
 				<option value='s10'>${en_or_pl("Add subject", "Wstaw przedmiot")}</option>
 				<option value='c11'>${en_or_pl("Add break place", "Wstaw miejsce przerwy")}</option>
+				<option value='r12'>${en_or_pl("Add curriculum requirement", "Wstaw wymaganie programowe")}</option>
+				<option value='g13'>${en_or_pl("Generate draft timetable", "Wygeneruj szkic planu lekcji")}</option>
+				<option value='get14'>${en_or_pl("Review draft timetable", "Przejrzyj szkic planu lekcji")}</option>
+				<option value='a14'>${en_or_pl("Apply draft timetable", "Zastosuj szkic planu lekcji")}</option>
+				<option value='d15'>${en_or_pl("Generate duty roster", "Wygeneruj dyżury")}</option>
+				<option value='x16'>${en_or_pl("Exclude teacher from duties", "Wyklucz nauczyciela z dyżurów")}</option>
+				<option value='get15'>${en_or_pl("Lessons to cover", "Lekcje do zastąpienia")}</option>
+				<option value='a17'>${en_or_pl("Add teacher absence", "Wstaw nieobecność nauczyciela")}</option>
+				<option value='s18'>${en_or_pl("Add substitution", "Wstaw zastępstwo")}</option>
+				<option value='get16'>${en_or_pl("School calendar", "Kalendarz szkolny")}</option>
+				<option value='get17'>${en_or_pl("Schedule for day", "Plan lekcji na dzień")}</option>
+				<option value='k19'>${en_or_pl("Add calendar entry", "Wstaw wpis do kalendarza")}</option>
+				<option value='get18'>${en_or_pl("Groups of class", "Grupy klasy")}</option>
+				<option value='g20'>${en_or_pl("Add class group", "Wstaw grupę klasy")}</option>
+				<option value='t21'>${en_or_pl("Add co-teacher to lesson", "Wstaw dodatkowego nauczyciela lekcji")}</option>
+				<option value='get19'>${en_or_pl("Teacher workload", "Obciążenie nauczycieli")}</option>
+				<option value='get20'>${en_or_pl("Curriculum compliance", "Zgodność z programem nauczania")}</option>
+				<option value='q22'>${en_or_pl("Add teacher qualification", "Wstaw kwalifikację nauczyciela")}</option>
+				<option value='get21'>${en_or_pl("Qualifications of teacher", "Kwalifikacje nauczyciela")}</option>
+				<option value='v23'>${en_or_pl("Set teacher availability", "Ustaw dostępność nauczyciela")}</option>
+				<option value='get23'>${en_or_pl("Availability of teacher", "Dostępność nauczyciela")}</option>
+				<option value='get24'>${en_or_pl("Lessons outside of availability", "Lekcje poza dostępnością")}</option>
+				<option value='f24'>${en_or_pl("Add classroom feature", "Wstaw wyposażenie sali")}</option>
+				<option value='f25'>${en_or_pl("Add feature required by subject", "Wstaw wyposażenie wymagane przez przedmiot")}</option>
+				<option value='get25'>${en_or_pl("Features of classroom or subject", "Wyposażenie sali lub przedmiotu")}</option>
+				<option value='get26'>${en_or_pl("Now and next", "Teraz i następnie")}</option>
+				<option value='get27'>${en_or_pl("Free classrooms", "Wolne sale")}</option>
+				<option value='b26'>${en_or_pl("Book classroom", "Zarezerwuj salę")}</option>
+				<option value='get28'>${en_or_pl("Classroom schedule for date", "Plan sali na dzień")}</option>
+				<option value='e27'>${en_or_pl("Add event", "Dodaj wydarzenie")}</option>
+				<option value='p28'>${en_or_pl("Add event participant", "Dodaj uczestnika wydarzenia")}</option>
+				<option value='get29'>${en_or_pl("Lessons and events on date", "Lekcje i wydarzenia w dniu")}</option>
+				<option value='o29'>${en_or_pl("Change lesson for one day", "Zmień lekcję na jeden dzień")}</option>
+				<option value='s30'>${en_or_pl("Add bell schedule", "Dodaj rodzaj dzwonków")}</option>
+				<option value='s31'>${en_or_pl("Lesson hour in bell schedule", "Godzina lekcyjna w rodzaju dzwonków")}</option>
+				<option value='s32'>${en_or_pl("Break in bell schedule", "Przerwa w rodzaju dzwonków")}</option>
+				<option value='s33'>${en_or_pl("Use bell schedule on date", "Użyj rodzaju dzwonków w dniu")}</option>
+				<option value='get30'>${en_or_pl("Bell times on date", "Dzwonki w dniu")}</option>
+				<option value='w34'>${en_or_pl("Lesson hour on weekday", "Godzina lekcyjna w dniu tygodnia")}</option>
+				<option value='w35'>${en_or_pl("Break on weekday", "Przerwa w dniu tygodnia")}</option>
+				<option value='r36'>${en_or_pl("Start next academic year", "Rozpocznij nowy rok szkolny")}</option>
+				<option value='c37'>${en_or_pl("Copy timetable into draft", "Skopiuj plan lekcji do szkicu")}</option>
+				<option value='get31'>${en_or_pl("Changes in draft", "Zmiany w szkicu")}</option>
+				<option value='get32'>${en_or_pl("Lessons between dates", "Lekcje między datami")}</option>
 			</select>
 			<div id='form' style='display:flex;flex-direction:column'>
 
@@ -581,11 +976,13 @@ function admin_dashboard_comp(){
 			case "get1":
 				$("form").innerHTML = `
 				<input id='ci' type='number' min=1 max=65535 placeholder='${en_or_pl("Class Id", "Identyfikator klasy")}'>
+				<input id='dt' type='date'>
 				`;
 				$("submit").onclick = function () {
 					const ci = $('ci').value;
+					const dt = $('dt').value;
 					if (ci!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=GET+1&class_id=${ci}`)
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=GET+1&class_id=${ci}&date=${dt}`)
 						.then(response => response.text())
 						.then(data => {
 							$("msg").innerHTML = data;
@@ -596,11 +993,13 @@ function admin_dashboard_comp(){
 			case "get2":
 				$("form").innerHTML = `
 				<input id='ti' type='number' min=1 max=65535 placeholder=${en_or_pl("Teacher ID", "Identyfikator Nauczyciela")}>
+				<input id='dt' type='date'>
 				`
 				$("submit").onclick = function(){
 					let ti = $('ti').value;
+					let dt = $('dt').value;
 					if (ti!=null){
-						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+2&teacher_id=${ti}`)
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+2&teacher_id=${ti}&date=${dt}`)
 						.then(response => response.text())
 						.then(data => {
 							$('msg').innerHTML = data;
@@ -611,11 +1010,13 @@ function admin_dashboard_comp(){
 			case "get3":
 				$("form").innerHTML = `
 				<input id='ti' type='number' min=1 max=65535 placeholder=${en_or_pl("Teacher ID", "Identyfikator Nauczyciela")}>
+				<input id='dt' type='date'>
 				`
 				$("submit").onclick = function(){
 					let ti = $('ti').value;
+					let dt = $('dt').value;
 					if (ti!=null){
-						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+3&teacher_id=${ti}`)
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+3&teacher_id=${ti}&date=${dt}`)
 						.then(response => response.text())
 						.then(data => {
 							$('msg').innerHTML = data;
@@ -623,59 +1024,589 @@ function admin_dashboard_comp(){
 					}
 				}
 				break;
-			case "l1":
+			case "get13":
 				$("form").innerHTML = `
-				<input id='wd' type='number' min=1 max=7 placeholder=${en_or_pl("Weekday", "Dzień tygodnia")}>
-				<input id='ci' type='number' min=1 max=65535 placeholder=${en_or_pl("Class ID", "Klasa(8)")}>
-				<input id='cl' type='number' min=1 max=65535 placeholder=${en_or_pl("Classroom ID", "Klasa")}>
-				<input id='ti' type='number' min=1 max=65535 placeholder=${en_or_pl("Teacher ID", "Nauczyciel")}>
-				<input id='si' type='number' min=1 max=65535 placeholder=${en_or_pl("Subject ID", "Przedmiot")}>
-				<input id='lh' type='number' min=1 max=255 placeholder=${en_or_pl("Lesson hour", "Godzina Lekcyjna")}>
-				<input id='se' type='number' min=1 max=255 placeholder=${en_or_pl("Semester", "Semestr")}>
-				<input id='ay' type='number' min=1 max=255 placeholder=${en_or_pl("Academic Year", "Rok szkolny")}>
-				`;
-				$("submit").onclick = function() {
-					let wd = $("wd").value;
-					let ci = $("ci").value;
-					let cl = $("cl").value;
-					let ti = $("ti").value;
-					let si = $("si").value;
-					let lh = $("lh").value;
-					let se = $("se").value;
-					let ay = $("ay").value;
-					if (wd!=null&&ci!=null&&cl!=null&&ti!=null&&si!=null&&lh!=null&&se!=null&&ay!=null){
-						fetch(
-						`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+1&weekday=${wd}&class_id=${ci}&classroom_id=${cl}&teacher_id=${ti}&subject_id=${si}&semester=${se}&academic_year=${ay}&lesson_hour=${lh}`
-						).then(response => response.text())
-						.then(data => {
-							alert(data);
-						})
-					}
-					else{
-						alert(en_or_pl("Enter data", "Wstaw dane"));
-					}
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester (optional)", "Semestr (opcjonalnie)")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year (optional)", "Rok szkolny (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					let se = $('se').value;
+					let ay = $('ay').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+13&semester=${se}&academic_year=${ay}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
 				}
 				break;
-			case "y2":
+			case "get19":
 				$("form").innerHTML = `
-				<input id='yn' type='number' min=1 max=7 placeholder="${en_or_pl("Year Number", "Numer Roku")}">
-				<input id='yn1' type='text'  placeholder="${en_or_pl("Year Name","Nazwa roku")}">
-				<input id='st' type='date'   placeholder="${en_or_pl("Start", "Rozpoczęcie")}">
-				<input id='en' type='date'   placeholder="${en_or_pl("End", "Zakończenie")}">
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester (optional)", "Semestr (opcjonalnie)")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year (optional)", "Rok szkolny (opcjonalnie)")}">
+				<select id='fm'>
+					<option value='table'>${en_or_pl("Table", "Tabela")}</option>
+					<option value='csv'>CSV</option>
+				</select>
 				`
-				$("submit").onclick = function() {
-					const date1 = new Date($("st").value).toISOString();
-					const date2 = new Date($("en").value).toISOString();
-					const year_number = $("yn").value;
-					const year_name = $("yn1").value;
-					if (year_name!=null&&year_number!=null&&date1!=null&&date2!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+2&academic_year=${year_number}&year_name=${year_name}&start_date=${date1}&end_date=${date2}`)
-						.then(response => response.text())
-						.then(data => {
-							alert(data);
-						})
-					}
-				}
+				$("submit").onclick = function(){
+					let se = $('se').value;
+					let ay = $('ay').value;
+					let fm = $('fm').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+19&semester=${se}&academic_year=${ay}&format=${fm}`)
+					.then(response => response.text())
+					.then(data => {
+						if (fm == 'csv'){
+							const link = document.createElement('a');
+							link.href = URL.createObjectURL(new Blob([data], {type: 'text/csv'}));
+							link.download = 'workload.csv';
+							link.click();
+						}
+						else{
+							$('msg').innerHTML = data;
+						}
+					})
+				}
+				break;
+			case "get20":
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester (optional)", "Semestr (opcjonalnie)")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year (optional)", "Rok szkolny (opcjonalnie)")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID (optional)", "Klasa (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					let se = $('se').value;
+					let ay = $('ay').value;
+					let ci = $('ci').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+20&semester=${se}&academic_year=${ay}&class_id=${ci}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get21":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				`
+				$("submit").onclick = function(){
+					let ti = $('ti').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+21&teacher_id=${ti}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "v23":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina Lekcyjna")}">
+				<select id='av'>
+					<option value='unavailable'>${en_or_pl("Unavailable", "Niedostępny")}</option>
+					<option value='preferred'>${en_or_pl("Preferred", "Preferowana")}</option>
+					<option value='neutral'>${en_or_pl("Neutral", "Neutralna")}</option>
+				</select>
+				`
+				$("submit").onclick = function(){
+					const ti = $("ti").value;
+					const wd = $("wd").value;
+					const lh = $("lh").value;
+					if (ti!=null&&wd!=null&&lh!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+23&teacher_id=${ti}&weekday=${wd}&lesson_hour=${lh}&availability=${$("av").value}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "get23":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				`
+				$("submit").onclick = function(){
+					let ti = $('ti').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+23&teacher_id=${ti}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get24":
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester (optional)", "Semestr (opcjonalnie)")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year (optional)", "Rok szkolny (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					let se = $('se').value;
+					let ay = $('ay').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+24&semester=${se}&academic_year=${ay}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "f24":
+				$("form").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID", "Sala")}">
+				<input id='ft' type='text' list='features' placeholder="${en_or_pl("Feature", "Wyposażenie")}">
+				<datalist id='features'>
+					<option value='lab'>${en_or_pl("Laboratory", "Laboratorium")}</option>
+					<option value='gym'>${en_or_pl("Gym", "Sala gimnastyczna")}</option>
+					<option value='computers'>${en_or_pl("Computers", "Komputery")}</option>
+					<option value='projector'>${en_or_pl("Projector", "Rzutnik")}</option>
+				</datalist>
+				`
+				$("submit").onclick = function(){
+					const ci = $("ci").value;
+					const ft = $("ft").value;
+					if (ci!=null&&ft!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+24&classroom_id=${ci}&feature=${ft}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "f25":
+				$("form").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				<input id='ft' type='text' list='features' placeholder="${en_or_pl("Feature", "Wyposażenie")}">
+				<datalist id='features'>
+					<option value='lab'>${en_or_pl("Laboratory", "Laboratorium")}</option>
+					<option value='gym'>${en_or_pl("Gym", "Sala gimnastyczna")}</option>
+					<option value='computers'>${en_or_pl("Computers", "Komputery")}</option>
+					<option value='projector'>${en_or_pl("Projector", "Rzutnik")}</option>
+				</datalist>
+				`
+				$("submit").onclick = function(){
+					const si = $("si").value;
+					const ft = $("ft").value;
+					if (si!=null&&ft!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+25&subject_id=${si}&feature=${ft}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "get25":
+				$("form").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID (or subject)", "Sala (lub przedmiot)")}">
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID (or classroom)", "Przedmiot (lub sala)")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+25&classroom_id=${$('ci').value}&subject_id=${$('si').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get26":
+				$("form").innerHTML = `
+				<select id='ow'>
+					<option value='teacher_id'>${en_or_pl("Teacher", "Nauczyciel")}</option>
+					<option value='class_id'>${en_or_pl("Class", "Klasa(8)")}</option>
+					<option value='classroom_id'>${en_or_pl("Classroom", "Klasa")}</option>
+				</select>
+				<input id='id' type='number' min=1 max=65535 placeholder="ID">
+				<input id='dt' type='date'>
+				<input id='tm' type='time'>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+26&${$('ow').value}=${$('id').value}&date=${$('dt').value}&time=${$('tm').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get27":
+				$("form").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday (or date and time)", "Dzień tygodnia (lub data i godzina)")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour (or date and time)", "Godzina lekcyjna (lub data i godzina)")}">
+				<input id='dt' type='date'>
+				<input id='tm' type='time'>
+				<input id='mc' type='number' min=0 max=65535 placeholder="${en_or_pl("Minimal capacity (optional)", "Minimalna pojemność (opcjonalnie)")}">
+				<input id='ft' type='text' placeholder="${en_or_pl("Features separated by comma (optional)", "Wyposażenie oddzielone przecinkami (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+27&weekday=${$('wd').value}&lesson_hour=${$('lh').value}&date=${$('dt').value}&time=${$('tm').value}&min_capacity=${$('mc').value}&features=${$('ft').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "b26":
+				$("form").innerHTML = `
+				<input id='bi' type='number' min=1 max=65535 placeholder="${en_or_pl("Booking ID", "Rezerwacja")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID", "Sala")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='dt' type='date'>
+				<input id='st' type='time'>
+				<input id='et' type='time'>
+				<input id='pp' type='text' placeholder="${en_or_pl("Purpose", "Cel")}">
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("et").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+26&booking_id=${$('bi').value}&classroom_id=${$('ci').value}&teacher_id=${$('ti').value}&date=${$('dt').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}&purpose=${$('pp').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "get28":
+				$("form").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID", "Sala")}">
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+28&classroom_id=${$('ci').value}&date=${$('dt').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "e27":
+				$("form").innerHTML = `
+				<input id='ei' type='number' min=1 max=65535 placeholder="${en_or_pl("Event ID", "Wydarzenie")}">
+				<input id='nm' type='text' placeholder="${en_or_pl("Name", "Nazwa")}">
+				<select id='et'>
+					<option value='exam'>${en_or_pl("Exam", "Egzamin")}</option>
+					<option value='trip'>${en_or_pl("Trip", "Wycieczka")}</option>
+					<option value='parent_meeting'>${en_or_pl("Parent meeting", "Wywiadówka")}</option>
+					<option value='other'>${en_or_pl("Other", "Inne")}</option>
+				</select>
+				<input id='dt' type='date'>
+				<input id='st' type='time'>
+				<input id='nd' type='time'>
+				<label><input id='ol' type='checkbox'>${en_or_pl("Cancels lessons", "Odwołuje lekcje")}</label>
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("nd").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+27&event_id=${$('ei').value}&name=${$('nm').value}&event_type=${$('et').value}&date=${$('dt').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}&overrides_lessons=${$('ol').checked}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "p28":
+				$("form").innerHTML = `
+				<input id='ei' type='number' min=1 max=65535 placeholder="${en_or_pl("Event ID", "Wydarzenie")}">
+				<select id='ow'>
+					<option value='teacher_id'>${en_or_pl("Teacher", "Nauczyciel")}</option>
+					<option value='class_id'>${en_or_pl("Class", "Klasa(8)")}</option>
+					<option value='classroom_id'>${en_or_pl("Classroom", "Klasa")}</option>
+				</select>
+				<input id='id' type='number' min=1 max=65535 placeholder="ID">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+28&event_id=${$('ei').value}&${$('ow').value}=${$('id').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "get29":
+				$("form").innerHTML = `
+				<select id='ow'>
+					<option value='teacher_id'>${en_or_pl("Teacher", "Nauczyciel")}</option>
+					<option value='class_id'>${en_or_pl("Class", "Klasa(8)")}</option>
+					<option value='classroom_id'>${en_or_pl("Classroom", "Klasa")}</option>
+				</select>
+				<input id='id' type='number' min=1 max=65535 placeholder="ID">
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+29&${$('ow').value}=${$('id').value}&date=${$('dt').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "o29":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa(8)")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina lekcyjna")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (optional)", "Grupa (opcjonalnie)")}">
+				<label><input id='cn' type='checkbox'>${en_or_pl("Cancelled", "Odwołana")}</label>
+				<input id='cr' type='number' min=1 max=65535 placeholder="${en_or_pl("New classroom ID (optional)", "Nowa sala (opcjonalnie)")}">
+				<input id='nh' type='number' min=1 max=255 placeholder="${en_or_pl("New lesson hour (optional)", "Nowa godzina lekcyjna (opcjonalnie)")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("New teacher ID (optional)", "Nowy nauczyciel (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+29&date=${$('dt').value}&class_id=${$('ci').value}&lesson_hour=${$('lh').value}&group_id=${$('gi').value}&cancelled=${$('cn').checked}&classroom_id=${$('cr').value}&new_lesson_hour=${$('nh').value}&teacher_id=${$('ti').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "s30":
+				$("form").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Bell schedule ID", "Rodzaj dzwonków")}">
+				<input id='nm' type='text' placeholder="${en_or_pl("Name (e.g. shortened)", "Nazwa (np. skrócone)")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+30&schedule_id=${$('si').value}&name=${$('nm').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "s31":
+				$("form").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Bell schedule ID", "Rodzaj dzwonków")}">
+				<input id='nb' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina lekcyjna")}">
+				<input id='st' type='time'>
+				<input id='et' type='time'>
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("et").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+31&schedule_id=${$('si').value}&lesson_hour=${$('nb').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "s32":
+				$("form").innerHTML = `
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Bell schedule ID", "Rodzaj dzwonków")}">
+				<input id='nb' type='number' min=1 max=255 placeholder="${en_or_pl("Break", "Przerwa")}">
+				<input id='st' type='time'>
+				<input id='et' type='time'>
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("et").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+32&schedule_id=${$('si').value}&break_num=${$('nb').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "s33":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Bell schedule ID", "Rodzaj dzwonków")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+33&date=${$('dt').value}&schedule_id=${$('si').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "get30":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+30&date=${$('dt').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "w34":
+				$("form").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='nb' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina lekcyjna")}">
+				<input id='st' type='time'>
+				<input id='et' type='time'>
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("et").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+34&weekday=${$('wd').value}&lesson_hour=${$('nb').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "w35":
+				$("form").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='nb' type='number' min=1 max=255 placeholder="${en_or_pl("Break", "Przerwa")}">
+				<input id='st' type='time'>
+				<input id='et' type='time'>
+				`
+				$("submit").onclick = function(){
+					const [sh, sm] = $("st").value.split(":");
+					const [eh, em] = $("et").value.split(":");
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+35&weekday=${$('wd').value}&break_num=${$('nb').value}&start_hour=${sh}&start_minute=${sm}&end_hour=${eh}&end_minute=${em}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "r36":
+				$("form").innerHTML = `
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Current academic year", "Obecny rok szkolny")}">
+				<input id='yn' type='text' placeholder="${en_or_pl("Name of new year", "Nazwa nowego roku")}">
+				<input id='fg' type='number' min=1 max=255 placeholder="${en_or_pl("Final grade", "Ostatnia klasa")}">
+				<label><input id='ct' type='checkbox'>${en_or_pl("Copy timetable into draft", "Skopiuj plan lekcji do szkicu")}</label>
+				<label><input id='pv' type='checkbox' checked>${en_or_pl("Preview", "Podgląd")}</label>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+36&academic_year=${$('ay').value}&year_name=${$('yn').value}&final_grade=${$('fg').value}&copy_timetable=${$('ct').checked}&preview=${$('pv').checked}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "c37":
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=POST+37&semester=${$('se').value}&academic_year=${$('ay').value}`)
+					.then(response => response.text())
+					.then(data => {
+						alert(data);
+					})
+				}
+				break;
+			case "get31":
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+31&semester=${$('se').value}&academic_year=${$('ay').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get32":
+				$("form").innerHTML = `
+				<select id='ow'>
+					<option value='teacher_id'>${en_or_pl("Teacher", "Nauczyciel")}</option>
+					<option value='class_id'>${en_or_pl("Class", "Klasa(8)")}</option>
+					<option value='classroom_id'>${en_or_pl("Classroom", "Klasa")}</option>
+				</select>
+				<input id='id' type='number' min=1 max=65535 placeholder="ID">
+				<input id='fr' type='date'>
+				<input id='to' type='date'>
+				`
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+32&${$('ow').value}=${$('id').value}&from=${$('fr').value}&to=${$('to').value}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "q22":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				`
+				$("submit").onclick = function(){
+					const ti = $("ti").value;
+					const si = $("si").value;
+					if (ti!=null&&si!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+22&teacher_id=${ti}&subject_id=${si}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "l1":
+				$("form").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder=${en_or_pl("Weekday", "Dzień tygodnia")}>
+				<input id='ci' type='number' min=1 max=65535 placeholder=${en_or_pl("Class ID", "Klasa(8)")}>
+				<input id='cl' type='number' min=1 max=65535 placeholder=${en_or_pl("Classroom ID", "Klasa")}>
+				<input id='ti' type='number' min=1 max=65535 list='qt' placeholder=${en_or_pl("Teacher ID", "Nauczyciel")}>
+				<datalist id='qt'></datalist>
+				<input id='si' type='number' min=1 max=65535 placeholder=${en_or_pl("Subject ID", "Przedmiot")}>
+				<input id='lh' type='number' min=1 max=255 placeholder=${en_or_pl("Lesson hour", "Godzina Lekcyjna")}>
+				<input id='se' type='number' min=1 max=255 placeholder=${en_or_pl("Semester", "Semestr")}>
+				<input id='ay' type='number' min=1 max=255 placeholder=${en_or_pl("Academic Year", "Rok szkolny")}>
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				<input id='ln' type='number' min=1 max=255 placeholder="${en_or_pl("Number of lesson hours (2 - double period)", "Liczba godzin lekcyjnych (2 - blok)")}">
+				<label><input id='dr' type='checkbox'>${en_or_pl("Only in draft", "Tylko w szkicu")}</label>
+				`;
+				// suggest only teachers qualified to teach chosen subject
+				$("si").onchange = function() {
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=GET+22&subject_id=${$("si").value}`)
+					.then(response => response.text())
+					.then(data => {
+						$("qt").innerHTML = data;
+					})
+				}
+				$("submit").onclick = function() {
+					let wd = $("wd").value;
+					let ci = $("ci").value;
+					let cl = $("cl").value;
+					let ti = $("ti").value;
+					let si = $("si").value;
+					let lh = $("lh").value;
+					let se = $("se").value;
+					let ay = $("ay").value;
+					if (wd!=null&&ci!=null&&cl!=null&&ti!=null&&si!=null&&lh!=null&&se!=null&&ay!=null){
+						fetch(
+						`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+1&weekday=${wd}&class_id=${ci}&classroom_id=${cl}&teacher_id=${ti}&subject_id=${si}&semester=${se}&academic_year=${ay}&lesson_hour=${lh}&week=${$("wk").value}&group_id=${$("gi").value}&length=${$("ln").value}&draft=${$("dr").checked}`
+						).then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+					else{
+						alert(en_or_pl("Enter data", "Wstaw dane"));
+					}
+				}
+				break;
+			case "y2":
+				$("form").innerHTML = `
+				<input id='yn' type='number' min=1 max=7 placeholder="${en_or_pl("Year Number", "Numer Roku")}">
+				<input id='yn1' type='text'  placeholder="${en_or_pl("Year Name","Nazwa roku")}">
+				<input id='st' type='date'   placeholder="${en_or_pl("Start", "Rozpoczęcie")}">
+				<input id='en' type='date'   placeholder="${en_or_pl("End", "Zakończenie")}">
+				`
+				$("submit").onclick = function() {
+					const date1 = new Date($("st").value).toISOString();
+					const date2 = new Date($("en").value).toISOString();
+					const year_number = $("yn").value;
+					const year_name = $("yn1").value;
+					if (year_name!=null&&year_number!=null&&date1!=null&&date2!=null){
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+2&academic_year=${year_number}&year_name=${year_name}&start_date=${date1}&end_date=${date2}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
 				break;
 			case "d3":
 				$("form").innerHTML = `
@@ -685,6 +1616,8 @@ function admin_dashboard_comp(){
 				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
 				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
 				<input id='bp' type='number' min=1 max=255 placeholder="${en_or_pl("Break_Place", "MiejscePrzerwy")}">
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<label><input id='dr' type='checkbox'>${en_or_pl("Only in draft", "Tylko w szkicu")}</label>
 				`
 				$("submit").onclick = function() {
 					let wd = $("wd").value;
@@ -695,7 +1628,7 @@ function admin_dashboard_comp(){
 					let ay = $("ay").value;
 					if (wd!=null&&bn!=null&&ti!=null&&bp!=null&&se!=null&&ay!=null){
 						fetch(
-`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+3&weekday=${wd}&teacher_id=${ti}&semester=${se}&academic_year=${ay}&break_num=${bn}&place_id=${bp}`
+`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+3&weekday=${wd}&teacher_id=${ti}&semester=${se}&academic_year=${ay}&break_num=${bn}&place_id=${bp}&week=${$("wk").value}&draft=${$("dr").checked}`
 						).then(response => response.text())
 						.then(data => {
 							alert(data);
@@ -736,6 +1669,7 @@ function admin_dashboard_comp(){
 				<input id='yn1' type='text'   placeholder="${en_or_pl("Semester Name","Nazwa semestru")}">
 				<input id='st' type='date' placeholder="${en_or_pl("Start", "Rozpoczęcie")}">
 				<input id='en' type='date' placeholder="${en_or_pl("End", "Zakończenie")}">
+				<input id='wc' type='number' min=1 max=255 placeholder="${en_or_pl("Weeks in cycle (A/B = 2)", "Tygodnie w cyklu (A/B = 2)")}">
 				`;
 				$("submit").onclick = function() {
 					const date1 = new Date($("st").value).toISOString();
@@ -743,7 +1677,7 @@ function admin_dashboard_comp(){
 					const sem_number = $("yn").value;
 					const sem_name = $("yn1").value;
 					if (sem_name!=null&&sem_number!=null&&date1!=null&&date2!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+5&semester=${sem_number}&semester_name=${sem_name}&start_date=${date1}&end_date=${date2}`)
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+5&semester=${sem_number}&semester_name=${sem_name}&start_date=${date1}&end_date=${date2}&week_cycle=${$("wc").value}`)
 						.then(response => response.text())
 						.then(data => {
 							alert(data);
@@ -779,13 +1713,14 @@ function admin_dashboard_comp(){
 				$("form").innerHTML = `
 				<input id=iid type=number min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Identyfikator Nauczyciela")}">
 				<input id=iname type=text placeholder="${en_or_pl("Teacher Name", "Imię Nauczyciela")}">
+				<input id=ipn type=number min=0 max=255 placeholder="${en_or_pl("Lessons per week in contract (optional)", "Pensum (opcjonalnie)")}">
 				`
 				$("submit").onclick = function(){
 					const id = $("iid").value;
 					const name = $("iname").value;
 
 					if (id!=null&&name!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+7&teacher_id=${id}&teacher_name=${name}`)
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+7&teacher_id=${id}&teacher_name=${name}&pensum=${$("ipn").value}`)
 						.then(response => response.text())
 						.then(data => {
 							alert (data);
@@ -797,13 +1732,14 @@ function admin_dashboard_comp(){
 				$("form").innerHTML = `
 				<input id=iid type=number min=1 max=65535 placeholder="${en_or_pl("Class ID", "Identyfikator Klasy")}">
 				<input id=iname type=text placeholder="${en_or_pl("Class Name", "Nazwa Klasy")}">
+				<input id=ist type=number min=0 max=65535 placeholder="${en_or_pl("Number of students (optional)", "Liczba uczniów (opcjonalnie)")}">
 				`
 				$("submit").onclick = function(){
 					const id = $("iid").value;
 					const name = $("iname").value;
 
 					if (id!=null&&name!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+8&class_id=${id}&class_name=${name}`)
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+8&class_id=${id}&class_name=${name}&students=${$("ist").value}`)
 						.then(response => response.text())
 						.then(data => {
 							alert (data);
@@ -815,13 +1751,16 @@ function admin_dashboard_comp(){
 				$("form").innerHTML = `
 				<input id=iid type=number min=1 max=65535 placeholder="${en_or_pl("Classroom ID", "Identyfikator Klasy")}">
 				<input id=iname type=text placeholder="${en_or_pl("Classroom Name", "Nazwa Klasy")}">
+				<input id=ipl type=number min=1 max=65535 placeholder="${en_or_pl("Place ID (optional)", "Identyfikator Miejsca (opcjonalnie)")}">
+				<input id=icp type=number min=0 max=65535 placeholder="${en_or_pl("Capacity (optional)", "Pojemność (opcjonalnie)")}">
 				`
 				$("submit").onclick = function(){
 					const id = $("iid").value;
 					const name = $("iname").value;
+					const place = $("ipl").value;
 
 					if (id!=null&&name!=null){
-						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+9&classroom_id=${id}&classroom_name=${name}`)
+						fetch (`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+9&classroom_id=${id}&classroom_name=${name}&place_id=${place}&capacity=${$("icp").value}`)
 						.then(response => response.text())
 						.then(data => {
 							alert (data);
@@ -864,6 +1803,252 @@ function admin_dashboard_comp(){
 					}
 				}
 				break;
+			case "r12":
+				$("form").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa(8)")}">
+				<input id='si' type='number' min=1 max=65535 placeholder="${en_or_pl("Subject ID", "Przedmiot")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='cl' type='number' min=1 max=65535 placeholder="${en_or_pl("Classroom ID (optional)", "Klasa (opcjonalnie)")}">
+				<input id='hs' type='number' min=1 max=255 placeholder="${en_or_pl("Hours per week", "Godziny tygodniowo")}">
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				`
+				$("submit").onclick = function(){
+					const ci = $("ci").value;
+					const si = $("si").value;
+					const ti = $("ti").value;
+					const cl = $("cl").value;
+					const hs = $("hs").value;
+					const se = $("se").value;
+					const ay = $("ay").value;
+					if (ci!=null&&si!=null&&ti!=null&&hs!=null&&se!=null&&ay!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+12&class_id=${ci}&subject_id=${si}&teacher_id=${ti}&classroom_id=${cl}&hours=${hs}&semester=${se}&academic_year=${ay}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "g13":
+			case "a14":
+			case "d15":
+				const method = {"g13": "POST+13", "a14": "POST+14", "d15": "POST+15"}[$("select").value];
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				`
+				$("submit").onclick = function(){
+					const se = $("se").value;
+					const ay = $("ay").value;
+					if (se!=null&&ay!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=${method}&semester=${se}&academic_year=${ay}`)
+						.then(response => response.text())
+						.then(data => {
+							$('msg').innerHTML = data;
+						})
+					}
+				}
+				break;
+			case "get15":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					let dt = $('dt').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+15&date=${dt}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get16":
+				$("form").innerHTML = ``
+				$("submit").onclick = function(){
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+16`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get17":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					let dt = $('dt').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+17&date=${dt}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "get18":
+				$("form").innerHTML = `
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				`
+				$("submit").onclick = function(){
+					let ci = $('ci').value;
+					fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie('password')}&method=GET+18&class_id=${ci}`)
+					.then(response => response.text())
+					.then(data => {
+						$('msg').innerHTML = data;
+					})
+				}
+				break;
+			case "g20":
+				$("form").innerHTML = `
+				<input id='gi' type='number' min=1 max=65535 placeholder="${en_or_pl("Group ID", "Identyfikator grupy")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				<input id='gn' type='text' placeholder="${en_or_pl("Group name", "Nazwa grupy")}">
+				`
+				$("submit").onclick = function(){
+					const gi = $("gi").value;
+					const ci = $("ci").value;
+					const gn = $("gn").value;
+					if (gi!=null&&ci!=null&&gn!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+20&group_id=${gi}&class_id=${ci}&group_name=${gn}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "t21":
+				$("form").innerHTML = `
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday", "Dzień Tygodnia")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina Lekcyjna")}">
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				<input id='wk' type='number' min=0 max=255 placeholder="${en_or_pl("Week of cycle (0 - every week)", "Tydzień cyklu (0 - co tydzień)")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Co-teacher ID", "Dodatkowy nauczyciel")}">
+				`
+				$("submit").onclick = function(){
+					const wd = $("wd").value;
+					const ci = $("ci").value;
+					const lh = $("lh").value;
+					const se = $("se").value;
+					const ay = $("ay").value;
+					const ti = $("ti").value;
+					if (wd!=null&&ci!=null&&lh!=null&&se!=null&&ay!=null&&ti!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+21&weekday=${wd}&class_id=${ci}&lesson_hour=${lh}&semester=${se}&academic_year=${ay}&week=${$("wk").value}&group_id=${$("gi").value}&teacher_id=${ti}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "k19":
+				$("form").innerHTML = `
+				<input id='id' type='number' min=1 max=65535 placeholder="${en_or_pl("Entry ID", "Identyfikator wpisu")}">
+				<input id='sd' type='date'>
+				<input id='ed' type='date'>
+				<select id='dy'>
+					<option value='holiday'>${en_or_pl("Holiday", "Święto")}</option>
+					<option value='exam'>${en_or_pl("Exams", "Egzaminy")}</option>
+					<option value='day_off'>${en_or_pl("Day off", "Dzień wolny")}</option>
+				</select>
+				<input id='nm' type='text' placeholder="${en_or_pl("Name", "Nazwa")}">
+				`
+				$("submit").onclick = function(){
+					const id = $("id").value;
+					const sd = $("sd").value;
+					const ed = $("ed").value;
+					const dy = $("dy").value;
+					const nm = $("nm").value;
+					if (id!=null&&sd!=null&&ed!=null&&nm!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+19&calendar_id=${id}&start_date=${sd}&end_date=${ed}&day_type=${dy}&name=${nm}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "a17":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='dt' type='date'>
+				`
+				$("submit").onclick = function(){
+					const ti = $("ti").value;
+					const dt = $("dt").value;
+					if (ti!=null&&dt!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+17&teacher_id=${ti}&date=${dt}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "s18":
+				$("form").innerHTML = `
+				<input id='dt' type='date'>
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID", "Klasa")}">
+				<input id='lh' type='number' min=1 max=255 placeholder="${en_or_pl("Lesson hour", "Godzina Lekcyjna")}">
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Substitute teacher ID", "Nauczyciel zastępujący")}">
+				<input id='gi' type='number' min=0 max=65535 placeholder="${en_or_pl("Group ID (0 - whole class)", "Grupa (0 - cała klasa)")}">
+				`
+				$("submit").onclick = function(){
+					const dt = $("dt").value;
+					const ci = $("ci").value;
+					const lh = $("lh").value;
+					const ti = $("ti").value;
+					if (dt!=null&&ci!=null&&lh!=null&&ti!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+18&date=${dt}&class_id=${ci}&lesson_hour=${lh}&teacher_id=${ti}&group_id=${$("gi").value}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "x16":
+				$("form").innerHTML = `
+				<input id='ti' type='number' min=1 max=65535 placeholder="${en_or_pl("Teacher ID", "Nauczyciel")}">
+				<input id='wd' type='number' min=1 max=7 placeholder="${en_or_pl("Weekday (optional)", "Dzień Tygodnia (opcjonalnie)")}">
+				<input id='bn' type='number' min=1 max=255 placeholder="${en_or_pl("Break num (optional)","Przerwa (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					const ti = $("ti").value;
+					const wd = $("wd").value;
+					const bn = $("bn").value;
+					if (ti!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=POST+16&teacher_id=${ti}&weekday=${wd}&break_num=${bn}`)
+						.then(response => response.text())
+						.then(data => {
+							alert(data);
+						})
+					}
+				}
+				break;
+			case "get14":
+				$("form").innerHTML = `
+				<input id='se' type='number' min=1 max=255 placeholder="${en_or_pl("Semester", "Semestr")}">
+				<input id='ay' type='number' min=1 max=255 placeholder="${en_or_pl("Academic Year", "Rok szkolny")}">
+				<input id='ci' type='number' min=1 max=65535 placeholder="${en_or_pl("Class ID (optional)", "Klasa (opcjonalnie)")}">
+				`
+				$("submit").onclick = function(){
+					const se = $("se").value;
+					const ay = $("ay").value;
+					const ci = $("ci").value;
+					if (se!=null&&ay!=null){
+						fetch(`/?msat/${MSAT_VERSION}&password=${get_cookie("password")}&method=GET+14&semester=${se}&academic_year=${ay}&class_id=${ci}`)
+						.then(response => response.text())
+						.then(data => {
+							$('msg').innerHTML = data;
+						})
+					}
+				}
+				break;
 		}
 	})
 }
@@ -11,6 +11,7 @@
 
 // Global Imports
 use std::sync::Arc;
+use std::collections::HashMap;
 use tokio::{
     sync::{
         Mutex, 
@@ -37,8 +38,34 @@ use colored::Colorize;
 use shared_components::{
     backend::{
         self, 
+        get_collisions,
         get_config, 
         get_lessons_by_teacher_id, 
+        get_year_and_semester,
+        date_or_today,
+        get_calendar_entry,
+        is_school_day,
+        get_cycle_week,
+        get_teacher_workload,
+        get_now_and_next,
+        get_free_classrooms,
+        get_free_classrooms_at,
+        get_room_schedule,
+        get_day_schedule,
+        get_timetable_between,
+        get_bell_schedule_for_date,
+        get_lesson_hours_for_date,
+        get_breaks_for_date,
+        insert_booking,
+        parse_date,
+        classroom_filter_from_args,
+        owner_from_args,
+        moment_or_now,
+        insert_lesson_block,
+        insert_duty,
+        insert_draft_lesson,
+        insert_draft_duty,
+        has_draft,
         manipulate_database, 
         MainpulationType, 
         ParsedRequest,
@@ -248,19 +275,33 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                 3 => {
                     if let Some(teacherid_str) = args.get("teacher_id"){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
-                            match get_lessons_by_teacher_id(teacher_id, &*db.lock().await){
+                            let date = match date_or_today(args.get("date")){
+                                Some(date) => date,
+                                None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+                            };
+                            match get_lessons_by_teacher_id(teacher_id, date, &*db.lock().await){
                                 Ok(vec) => {
                                     let mut to_return = String::new();
                                     to_return.push_str("msat/200-OK");
                                     let mut amount = 0;
-                                    for lesson in vec{
+                                    // lessons are numbered by lesson hour, so only lessons from one day are sent
+                                    for lesson in vec.into_iter().filter(|l| l.date == Some(date.to_string())){
                                         if let Some(lessonh) = lesson.lessonh.lesson_hour{
+                                            match lesson.status{
+                                                LessonStatus::Substitution => to_return.push_str(&format!("&substitution{}=true", lessonh)),
+                                                LessonStatus::Cancelled    => to_return.push_str(&format!("&cancelled{}=true", lessonh)),
+                                                LessonStatus::Changed      => to_return.push_str(&format!("&changed{}=true", lessonh)),
+                                                LessonStatus::Regular      => {}
+                                            }
                                             if amount < lessonh{
                                                 amount = lessonh;
                                             }
                                             if let Some(class_id) = lesson.class{
                                                 to_return.push_str(&format!("&class{}={}", lessonh, class_id.to_single('_')));
                                             }
+                                            if let Some(group) = lesson.group{
+                                                to_return.push_str(&format!("&group{}={}", lessonh, group.to_single('_')));
+                                            }
                                             if let Some(classroom_id) = lesson.classroom{
                                                 to_return.push_str(&format!("&classroom{}={}", lessonh, classroom_id.to_single('_')));
                                             }
@@ -295,6 +336,257 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                         return Err(ServerError::ArgsMissing { expected: ["teacher_id"].iter().map(|s| s.to_string()).collect() });
                     }
                 }
+                // Conflicts report
+                4 => {
+                    let db = db.lock().await;
+                    let (semester, academic_year) = match semester_from_args(args, &db)?{
+                        Some(semester) => semester,
+                        None => return Ok("msat/204-No-Content".to_string())
+                    };
+                    match get_collisions(semester, academic_year, &db){
+                        Ok(collisions) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", collisions.len());
+                            for (i, collision) in collisions.iter().enumerate(){
+                                to_return.push_str(&format!("&type{i}={}&weekday{i}={}&lesson_hour{i}={}&resource_id{i}={}&first_class{i}={}&second_class{i}={}",
+                                    collision.kind.as_str(), collision.weekday, collision.lesson_hour, collision.resource.0, 
+                                    collision.classes[0].0, collision.classes[1].0));
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Is there school on date
+                5 => {
+                    let date = match date_or_today(args.get("date")){
+                        Some(date) => date,
+                        None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+                    };
+                    let db = db.lock().await;
+                    match (is_school_day(date, &db), get_calendar_entry(date, &db), get_cycle_week(date, &db), get_bell_schedule_for_date(date, &db)){
+                        (Ok(school_day), Ok(entry), Ok(week), Ok(bell_schedule)) => {
+                            let mut to_return = format!("msat/200-OK&school_day={}&week={}", school_day, week);
+                            if let Some(entry) = entry{
+                                to_return.push_str(&format!("&day_type={}&name={}", entry.day_type.as_str(), entry.name.to_single('_')));
+                            }
+                            if let Some((_, bell_schedule)) = bell_schedule{
+                                to_return.push_str(&format!("&bell_schedule={}", bell_schedule.to_single('_')));
+                            }
+                            return Ok(to_return);
+                        }
+                        (Err(error), _, _, _) | (_, Err(error), _, _) | (_, _, Err(error), _) | (_, _, _, Err(error)) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Teacher workload
+                6 => {
+                    let db = db.lock().await;
+                    let (semester, academic_year) = match semester_from_args(args, &db)?{
+                        Some(semester) => semester,
+                        None => return Ok("msat/204-No-Content".to_string())
+                    };
+                    match get_teacher_workload(semester, academic_year, &db){
+                        Ok(workload) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", workload.len());
+                            for (i, teacher) in workload.iter().enumerate(){
+                                to_return.push_str(&format!("&teacher_id{i}={}&teacher{i}={}&lessons{i}={}&duties{i}={}&status{i}={}",
+                                    teacher.teacher_id, teacher.teacher_name.to_single('_'), teacher.lessons, teacher.duties, teacher.status().as_str()));
+                                if let Some(pensum) = teacher.pensum{
+                                    to_return.push_str(&format!("&pensum{i}={}", pensum));
+                                }
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Now and next
+                7 => {
+                    let owner = match owner_from_args(args){
+                        Some(owner) => owner,
+                        None => return Err(ServerError::ArgsMissing { expected: ["teacher_id", "class_id", "classroom_id"].iter().map(|s| s.to_string()).collect() })
+                    };
+                    let moment = match moment_or_now(args.get("date"), args.get("time")){
+                        Some(moment) => moment,
+                        None => return Err(ServerError::ParseArgError { 
+                            args: [args.get("date"), args.get("time")].iter().map(|s| s.cloned().unwrap_or_default()).collect() })
+                    };
+                    match get_now_and_next(owner, moment, &*db.lock().await){
+                        Ok(now_and_next) => {
+                            return Ok(format!("msat/200-OK{}{}", activity_to_args("current", &now_and_next.current), 
+                                activity_to_args("next", &now_and_next.next)));
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Free classrooms in lesson hour of weekday (or at date and time)
+                8 => {
+                    let filter = classroom_filter_from_args(args);
+                    let db = db.lock().await;
+                    let classrooms = match (args.get("weekday").and_then(|s| s.parse::<u8>().ok()), args.get("lesson_hour").and_then(|s| s.parse::<u16>().ok())){
+                        (Some(weekday), Some(lesson_hour)) => {
+                            let (semester, academic_year) = match semester_from_args(args, &db)?{
+                                Some(semester) => semester,
+                                None => return Ok("msat/204-No-Content".to_string())
+                            };
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            get_free_classrooms(weekday, lesson_hour, semester, academic_year, week, &filter, &db)
+                        }
+                        _ => match moment_or_now(args.get("date"), args.get("time")){
+                            Some(moment) => get_free_classrooms_at(moment, &filter, &db),
+                            None => return Err(ServerError::ParseArgError { 
+                                args: [args.get("date"), args.get("time")].iter().map(|s| s.cloned().unwrap_or_default()).collect() })
+                        }
+                    };
+                    match classrooms{
+                        Ok(classrooms) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", classrooms.len());
+                            for (i, (classroom_id, classroom_name, capacity)) in classrooms.iter().enumerate(){
+                                to_return.push_str(&format!("&classroom_id{i}={}&classroom{i}={}", classroom_id, classroom_name.to_single('_')));
+                                if let Some(capacity) = capacity{
+                                    to_return.push_str(&format!("&capacity{i}={}", capacity));
+                                }
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            if error == rusqlite::Error::QueryReturnedNoRows{
+                                return Ok("msat/204-No-Content".to_string());
+                            }
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Lessons and bookings of classroom on date
+                9 => {
+                    let classroom_id = match args.get("classroom_id"){
+                        Some(classroom_id) => match classroom_id.parse::<u16>(){
+                            Ok(classroom_id) => classroom_id,
+                            Err(_) => return Err(ServerError::ParseIntError { arg: classroom_id.to_string() })
+                        },
+                        None => return Err(ServerError::ArgsMissing { expected: vec!["classroom_id".to_string()] })
+                    };
+                    let date = match date_or_today(args.get("date")){
+                        Some(date) => date,
+                        None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+                    };
+                    match get_room_schedule(classroom_id, date, &*db.lock().await){
+                        Ok(schedule) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", schedule.len());
+                            for (i, entry) in schedule.iter().enumerate(){
+                                to_return.push_str(&entry.to_args(i));
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Lessons and events of teacher, class or classroom on date
+                10 => {
+                    let owner = match owner_from_args(args){
+                        Some(owner) => owner,
+                        None => return Err(ServerError::ArgsMissing { expected: ["teacher_id", "class_id", "classroom_id"].iter().map(|s| s.to_string()).collect() })
+                    };
+                    let date = match date_or_today(args.get("date")){
+                        Some(date) => date,
+                        None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+                    };
+                    match get_day_schedule(owner, date, &*db.lock().await){
+                        Ok(schedule) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", schedule.lessons.len());
+                            for (i, lesson) in schedule.lessons.into_iter().enumerate(){
+                                let status = match lesson.status{
+                                    LessonStatus::Regular      => "regular",
+                                    LessonStatus::Substitution => "substitution",
+                                    LessonStatus::Cancelled    => "cancelled",
+                                    LessonStatus::Changed      => "changed"
+                                };
+                                to_return.push_str(&format!("&status{i}={}{}", status, activity_to_args(&format!("lesson{i}"), &Activity::Lesson(lesson))));
+                            }
+                            to_return.push_str(&format!("&EVENTS={}", schedule.events.len()));
+                            for (i, event) in schedule.events.iter().enumerate(){
+                                to_return.push_str(&format!("&event_id{i}={}&event{i}={}&event_type{i}={}&event_start{i}={:02}:{:02}&event_end{i}={:02}:{:02}&overrides_lessons{i}={}",
+                                    event.event_id, event.name.to_single('_'), event.event_type.as_str(), event.start_hour, event.start_minute, 
+                                    event.end_hour, event.end_minute, event.overrides_lessons));
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Times of lesson hours and breaks on date
+                11 => {
+                    let date = match date_or_today(args.get("date")){
+                        Some(date) => date,
+                        None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+                    };
+                    let db = db.lock().await;
+                    match (get_lesson_hours_for_date(date, &db), get_breaks_for_date(date, &db)){
+                        (Ok(lesson_hours), Ok(breaks)) => {
+                            let times = |prefix: &str, hours: &[JoinedHour]| {
+                                let mut to_return = String::new();
+                                for (i, hour) in hours.iter().enumerate(){
+                                    if let (Some(num), Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+                                    (hour.lesson_hour, hour.start_hour, hour.start_minute, hour.end_hour, hour.end_minutes)
+                                    {
+                                        to_return.push_str(&format!("&{prefix}num{i}={}&{prefix}start{i}={:02}:{:02}&{prefix}end{i}={:02}:{:02}", 
+                                            num, start_hour, start_minute, end_hour, end_minute));
+                                    }
+                                }
+                                to_return
+                            };
+                            return Ok(format!("msat/200-OK&AMOUNT={}{}&BREAKS={}{}", 
+                                lesson_hours.len(), times("", &lesson_hours), breaks.len(), times("break_", &breaks)));
+                        }
+                        (Err(error), _) | (_, Err(error)) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
+                // Lessons of teacher, class or classroom between two dates
+                12 => {
+                    let owner = match owner_from_args(args){
+                        Some(owner) => owner,
+                        None => return Err(ServerError::ArgsMissing { expected: ["teacher_id", "class_id", "classroom_id"].iter().map(|s| s.to_string()).collect() })
+                    };
+                    let (from, to) = match (args.get("from"), args.get("to")){
+                        (Some(from_str), Some(to_str)) => match (parse_date(from_str), parse_date(to_str)){
+                            (Some(from), Some(to)) => (from, to),
+                            _ => return Err(ServerError::ParseArgError { args: vec![from_str.to_string(), to_str.to_string()] })
+                        },
+                        _ => return Err(ServerError::ArgsMissing { expected: ["from", "to"].iter().map(|s| s.to_string()).collect() })
+                    };
+                    match get_timetable_between(owner, from, to, &*db.lock().await){
+                        Ok(lessons) => {
+                            let mut to_return = format!("msat/200-OK&AMOUNT={}", lessons.len());
+                            for (i, lesson) in lessons.into_iter().enumerate(){
+                                let status = match lesson.status{
+                                    LessonStatus::Regular      => "regular",
+                                    LessonStatus::Substitution => "substitution",
+                                    LessonStatus::Cancelled    => "cancelled",
+                                    LessonStatus::Changed      => "changed"
+                                };
+                                to_return.push_str(&format!("&date{i}={}&status{i}={}{}", lesson.date.clone().unwrap_or_default(), 
+                                    status, activity_to_args(&format!("lesson{i}"), &Activity::Lesson(lesson))));
+                            }
+                            return Ok(to_return);
+                        }
+                        Err(error) => {
+                            return Err(ServerError::DatabaseError(error));
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -313,16 +605,18 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                         (weekday_str.parse::<u8>(),classid_str.parse::<u16>(),classroomid_str.parse::<u16>(),teacherid_str.parse::<u16>(),
                         subjectid_str.parse::<u16>(), semester_str.parse::<u8>(), academicyear_str.parse::<u8>(), lessonhour_str.parse::<u16>())
                         {
-                            match manipulate_database(
-                            MainpulationType::Insert
-                            (backend::POST::Lesson
-                            (Some((weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year)))), &*db.lock().await)
-                            {
-                                Ok(v) => return Ok(v),
-                                Err(error) => {
-                                    return Err(ServerError::DatabaseError(error));
-                                }
-                            }
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let group_id = args.get("group_id").and_then(|s| s.parse::<u16>().ok()).unwrap_or(0);
+                            // number of consecutive lesson hours covered by lesson (e.g. 2 for double period)
+                            let length = args.get("length").and_then(|s| s.parse::<u16>().ok()).unwrap_or(1);
+                            let lesson = (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id);
+                            let db = db.lock().await;
+                            // while semester has draft, changes go into draft (see `apply_draft`)
+                            return match has_draft(semester, academic_year, &db).map(|open| open || args.get("draft").is_some_and(|s| s == "true")){
+                                Ok(true)   => insert_draft_lesson(lesson, length, &db),
+                                Ok(false)  => insert_lesson_block(lesson, length, &db),
+                                Err(error) => Err(ServerError::DatabaseError(error))
+                            };
                         }
                     }
                     else{
@@ -372,15 +666,14 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                         (weekday_str.parse::<u8>(),breaknum_str.parse::<u8>(),teacherid_str.parse::<u16>(),
                         semester_str.parse::<u8>(), academicyear_str.parse::<u8>(),placeid_str.parse::<u16>())
                         {
-                            match manipulate_database(MainpulationType::Insert
-                                (backend::POST::Duty(Some((weekday, break_num, teacher_id, place_id, semester, academic_year)))), 
-                                &*db.lock().await)
-                            {
-                                Ok(v) => return Ok(v),
-                                Err(error) => {
-                                    return Err(ServerError::DatabaseError(error));
-                                }
-                            }
+                            let week = args.get("week").and_then(|s| s.parse::<u8>().ok()).unwrap_or(0);
+                            let duty = (weekday, break_num, teacher_id, place_id, semester, academic_year, week);
+                            let db = db.lock().await;
+                            return match has_draft(semester, academic_year, &db).map(|open| open || args.get("draft").is_some_and(|s| s == "true")){
+                                Ok(true)   => insert_draft_duty(duty, &db),
+                                Ok(false)  => insert_duty(duty, &db),
+                                Err(error) => Err(ServerError::DatabaseError(error))
+                            };
                         }
                         else{
                             return Err(ServerError::ParseArgError { 
@@ -428,9 +721,10 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                     (args.get("semester"),args.get("semester_name"),args.get("start_date"),args.get("end_date"))
                     {
                         if let Ok(semester) = semester_str.parse::<u8>(){
+                            let week_cycle = args.get("week_cycle").and_then(|s| s.parse::<u8>().ok()).unwrap_or(1);
                             match manipulate_database(MainpulationType::Insert(
                                     backend::POST::Semester(
-                                        Some((semester, semester_name.to_string(), start_date.to_string(), end_date.to_string())))), &*db.lock().await)
+                                        Some((semester, semester_name.to_string(), start_date.to_string(), end_date.to_string(), week_cycle)))), &*db.lock().await)
                             {
                                 Ok(v) => return Ok(v),
                                 Err(err) => {
@@ -479,8 +773,9 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                 7 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("teacher_id"), args.get("teacher_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let pensum = args.get("pensum").and_then(|s| s.parse::<u8>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Teacher(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Teacher(Some ((teacher_id, teacher_name.to_string(), pensum)) )), &*db.lock().await)
                             {
                                 Ok(v) => return Ok(v),
                                 Err(error) => {
@@ -500,8 +795,9 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                 8 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("class_id"), args.get("class_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let students = args.get("students").and_then(|s| s.parse::<u16>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Class(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Class(Some ((teacher_id, teacher_name.to_string(), students)) )), &*db.lock().await)
                             {
                                 Ok(v) => return Ok(v),
                                 Err(error) => {
@@ -518,8 +814,10 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                 9 => {
                     if let (Some(teacherid_str), Some(teacher_name)) = (args.get("classroom_id"), args.get("classroom_name")){
                         if let Ok(teacher_id) = teacherid_str.parse::<u16>(){
+                            let corridor = args.get("place_id").and_then(|s| s.parse::<u16>().ok());
+                            let capacity = args.get("capacity").and_then(|s| s.parse::<u16>().ok());
                             match manipulate_database(
-                                MainpulationType::Insert(backend::POST::Classroom(Some ((teacher_id, teacher_name.to_string())) )), &*db.lock().await)
+                                MainpulationType::Insert(backend::POST::Classroom(Some ((teacher_id, teacher_name.to_string(), corridor, capacity)) )), &*db.lock().await)
                             {
                                 Ok(v) => return Ok(v),
                                 Err(error) => {
@@ -577,6 +875,32 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
                         return Err(ServerError::ArgsMissing { expected: ["place_id", "place_name"].iter().map(|s| s.to_string()).collect() });
                     }
                 }
+                // Booking
+                12 => {
+                    if let (Some(bookingid_str), Some(classroomid_str), Some(date_str), Some(starthour_str), Some(startminute_str), 
+                        Some(endhour_str), Some(endminute_str), Some(teacherid_str), Some(purpose)) = 
+                    (args.get("booking_id"), args.get("classroom_id"), args.get("date"), args.get("start_hour"), args.get("start_minute"),
+                     args.get("end_hour"), args.get("end_minute"), args.get("teacher_id"), args.get("purpose"))
+                    {
+                        if let (Ok(booking_id), Ok(classroom_id), Some(date), Ok(start_hour), Ok(start_minute), Ok(end_hour), Ok(end_minute), Ok(teacher_id)) = 
+                        (bookingid_str.parse::<u16>(), classroomid_str.parse::<u16>(), parse_date(date_str), starthour_str.parse::<u8>(), 
+                         startminute_str.parse::<u8>(), endhour_str.parse::<u8>(), endminute_str.parse::<u8>(), teacherid_str.parse::<u16>())
+                        {
+                            return insert_booking((booking_id, classroom_id, date, start_hour, start_minute, end_hour, end_minute, teacher_id, purpose.to_string()), 
+                                &*db.lock().await);
+                        }
+                        else{
+                            return Err(ServerError::ParseArgError { 
+                                args: [bookingid_str, classroomid_str, date_str, starthour_str, startminute_str, endhour_str, endminute_str, teacherid_str]
+                                    .iter().map(|s| s.to_string()).collect() 
+                            });
+                        }
+                    }
+                    else{
+                        return Err(ServerError::ArgsMissing { expected: ["booking_id", "classroom_id", "date", "start_hour", "start_minute", 
+                            "end_hour", "end_minute", "teacher_id", "purpose"].iter().map(|s| s.to_string()).collect() });
+                    }
+                }
                 _ => {}
             }
         }
@@ -584,3 +908,46 @@ async fn get_response(parsed_request: ParsedRequest, db: Arc<Mutex<SQLite>>) ->
     }
     Err(ServerError::UnknownRequest)
 }
+
+/// Semester and academic year from `semester` and `academic_year` arguments or ones 
+/// that `date` (today by default) belongs to. None if date isn't in any semester
+fn semester_from_args(args: &HashMap<String, String>, db: &SQLite) -> Result<Option<(u8, u8)>, ServerError>{
+    if let (Some(semester_str), Some(year_str)) = (args.get("semester"), args.get("academic_year")){
+        return match (semester_str.parse::<u8>(), year_str.parse::<u8>()){
+            (Ok(semester), Ok(academic_year)) => Ok(Some((semester, academic_year))),
+            _ => Err(ServerError::ParseArgError { args: [semester_str, year_str].iter().map(|s| s.to_string()).collect() })
+        };
+    }
+    let date = match date_or_today(args.get("date")){
+        Some(date) => date,
+        None => return Err(ServerError::ParseArgError { args: vec![args.get("date").cloned().unwrap_or_default()] })
+    };
+    match get_year_and_semester(date, db){
+        Ok((academic_year, semester)) => Ok(Some((semester, academic_year))),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(error) => Err(ServerError::DatabaseError(error))
+    }
+}
+
+/// Arguments describing lesson or duty (e.g. `&current=lesson&current_subject=...`)
+fn activity_to_args(prefix: &str, activity: &Activity) -> String{
+    let mut to_return = format!("&{}={}", prefix, activity.as_str());
+    let (hour, fields) = match activity{
+        Activity::Lesson(lesson) => (&lesson.lessonh, vec![("subject", &lesson.subject), ("class", &lesson.class), ("group", &lesson.group),
+            ("classroom", &lesson.classroom), ("teacher", &lesson.teacher)]),
+        Activity::Duty(duty) => (&duty.break_num, vec![("place", &duty.place)]),
+        _ => return to_return
+    };
+    for (name, value) in fields{
+        if let Some(value) = value{
+            to_return.push_str(&format!("&{}_{}={}", prefix, name, value.to_single('_')));
+        }
+    }
+    if let (Some(start_hour), Some(start_minute)) = (hour.start_hour, hour.start_minute){
+        to_return.push_str(&format!("&{}_start={:02}:{:02}", prefix, start_hour, start_minute));
+    }
+    if let (Some(end_hour), Some(end_minute)) = (hour.end_hour, hour.end_minutes){
+        to_return.push_str(&format!("&{}_end={:02}:{:02}", prefix, end_hour, end_minute));
+    }
+    to_return
+}
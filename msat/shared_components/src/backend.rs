@@ -12,17 +12,31 @@ use rusqlite::{
 };
 use tokio::fs;
 use toml;
-use std::collections::HashMap;
+use std::collections::{
+    HashMap,
+    BTreeMap
+};
 use chrono::{
-    DateTime, Datelike
+    DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Timelike
 };
 // Local Imports 
 use crate::{
     types::*,
     consts::{
         VERSION,
-        SUPPORTED_VERSIONS
+        SUPPORTED_VERSIONS,
+        SCHOOL_DAYS
     }, 
+    generator::{
+        self,
+        Requirement,
+        DraftReport,
+        FixedLesson,
+        TimetableProblem,
+        Duty,
+        DutyProblem,
+        DutyRoster
+    },
     visual
 };
 // static/const declaration
@@ -160,31 +174,71 @@ pub fn init_db() -> Result<Database, SQLiteError>{
     db.execute_batch("PRAGMA journal_mode = WAL;")?;
     db.execute_batch("PRAGMA foreign_key = 1;")?;
     db.busy_timeout(std::time::Duration::from_secs(4))?;
-    db.execute(
+    create_tables(&db)?;
+    Ok(db)
+}
+
+fn create_tables(db: &Database) -> Result<(), SQLiteError>{
+    let classes_table = 
         "CREATE TABLE IF NOT EXISTS Classes(
             class_id   INTEGER PRIMARY KEY,
-            class_name TEXT NOT NULL UNIQUE
+            class_name TEXT NOT NULL,
+            students   INTEGER CHECK(students >= 0),
+            archived   INTEGER NOT NULL DEFAULT 0
         );
-        "
-        ,[])?;
+        ";
+    db.execute(classes_table, [])?;
+    add_column(db, "Classes", "students", "INTEGER CHECK(students >= 0)")?;
+    // archived is 1 for classes of past academic years (see `rollover_year`), 
+    // so only names of classes that aren't archived have to be unique
+    add_column(db, "Classes", "archived", "INTEGER NOT NULL DEFAULT 0")?;
+    unique_class_names(db, classes_table)?;
+    db.execute("CREATE UNIQUE INDEX IF NOT EXISTS ClassNames ON Classes (class_name) WHERE archived = 0", [])?;
+    // corridor is place where classroom is located and capacity is number
+    // of students that fit into classroom (both NULL if unknown)
     db.execute(
         "CREATE TABLE IF NOT EXISTS Classrooms(
             classroom_id INTEGER PRIMARY KEY,
-            classroom_name   TEXT NOT NULL UNIQUE
+            classroom_name   TEXT NOT NULL UNIQUE,
+            corridor     INTEGER REFERENCES Corridors (corridor),
+            capacity     INTEGER CHECK(capacity >= 0)
         );"
         ,[])?;
+    add_column(db, "Classrooms", "corridor", "INTEGER REFERENCES Corridors (corridor)")?;
+    add_column(db, "Classrooms", "capacity", "INTEGER CHECK(capacity >= 0)")?;
+    // pensum is contractual number of lessons per week (NULL if unknown)
     db.execute(
         "CREATE TABLE IF NOT EXISTS Teachers(
             teacher_id   INTEGER PRIMARY KEY,
-            teacher_name TEXT NOT NULL UNIQUE
+            teacher_name TEXT NOT NULL UNIQUE,
+            pensum       INTEGER CHECK(pensum >= 0)
         );"
         ,[])?;
+    add_column(db, "Teachers", "pensum", "INTEGER CHECK(pensum >= 0)")?;
     db.execute(
         "CREATE TABLE IF NOT EXISTS Subjects(
             subject_id   INTEGER PRIMARY KEY,
             subject_name TEXT NOT NULL UNIQUE
         );"
         ,[])?;
+    // equipment of classroom (e.g. lab, gym, computers or projector)
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS ClassroomFeatures(
+            classroom_id INTEGER NOT NULL,
+            feature      TEXT NOT NULL,
+            PRIMARY KEY (classroom_id, feature),
+            FOREIGN KEY (classroom_id) REFERENCES Classrooms (classroom_id)
+        );"
+        ,[])?;
+    // equipment that classroom needs to have for lessons of subject
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS SubjectFeatures(
+            subject_id INTEGER NOT NULL,
+            feature    TEXT NOT NULL,
+            PRIMARY KEY (subject_id, feature),
+            FOREIGN KEY (subject_id) REFERENCES Subjects (subject_id)
+        );"
+        ,[])?;
     db.execute(
         "CREATE TABLE IF NOT EXISTS LessonHours(
             lesson_hour   INTEGER PRIMARY KEY,
@@ -194,15 +248,18 @@ pub fn init_db() -> Result<Database, SQLiteError>{
             end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60)
         );"
         ,[])?;
-    // semester_start and semester_end should be formatted in ISO8601 format
+    // semester_start and semester_end should be formatted in ISO8601 format,
+    // week_cycle is number of weeks after which timetable repeats (counted from week of start_date)
     db.execute(
         "CREATE TABLE IF NOT EXISTS Semesters(
             semester      INTEGER PRIMARY KEY,
             semester_name TEXT NOT NULL UNIQUE,
             start_date    TEXT NOT NULL,
-            end_date      TEXT NOT NULL
+            end_date      TEXT NOT NULL,
+            week_cycle    INTEGER NOT NULL DEFAULT 1 CHECK(week_cycle > 0)
         );"
         ,[])?;
+    add_column(db, "Semesters", "week_cycle", "INTEGER NOT NULL DEFAULT 1 CHECK(week_cycle > 0)")?;
     // start_date and end_date should be formatted as ISO8601 compatible date
     db.execute(
         "CREATE TABLE IF NOT EXISTS Years(
@@ -212,7 +269,20 @@ pub fn init_db() -> Result<Database, SQLiteError>{
             end_date       TEXT NOT NULL
         );"
         ,[])?;
+    // group of class that has its own lessons (e.g. for languages or PE)
     db.execute(
+        "CREATE TABLE IF NOT EXISTS ClassGroups(
+            group_id   INTEGER PRIMARY KEY CHECK(group_id > 0),
+            class_id   INTEGER NOT NULL,
+            group_name TEXT NOT NULL,
+            UNIQUE (class_id, group_name),
+            FOREIGN KEY (class_id) REFERENCES Classes (class_id)
+        );
+        "
+        ,[])?;
+    // week is week of semester's week_cycle (starting from 1) or 0 if lesson takes place every week,
+    // group_id is 0 if lesson is for the whole class
+    let lessons_table = 
         "CREATE TABLE IF NOT EXISTS Lessons (
             weekday       INTEGER NOT NULL,
             class_id      INTEGER NOT NULL,
@@ -222,7 +292,9 @@ pub fn init_db() -> Result<Database, SQLiteError>{
             lesson_hour   INTEGER NOT NULL,
             semester      INTEGER NOT NULL,
             academic_year INTEGER NOT NULL,
-            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year),
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id),
             FOREIGN KEY (class_id)      REFERENCES Classes    (class_id),
             FOREIGN KEY (classroom_id)  REFERENCES Classrooms (classroom_id),
             FOREIGN KEY (teacher_id)    REFERENCES Teachers   (teacher_id),
@@ -231,6 +303,41 @@ pub fn init_db() -> Result<Database, SQLiteError>{
             FOREIGN KEY (semester)      REFERENCES Semesters  (semester),
             FOREIGN KEY (academic_year) REFERENCES Years      (academic_year)
         );
+        ";
+    db.execute(lessons_table, [])?;
+    rebuild_table(db, "Lessons", "week", lessons_table)?;
+    rebuild_table(db, "Lessons", "group_id", lessons_table)?;
+    // lessons covering more than one lesson hour (e.g. labs and workshops), every hour from
+    // lesson_hour to last_hour has its own row in `Lessons` with the same key
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS LessonBlocks(
+            weekday       INTEGER NOT NULL,
+            class_id      INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            last_hour     INTEGER NOT NULL CHECK(last_hour > lesson_hour),
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id)
+        );
+        "
+        ,[])?;
+    // additional teachers of lesson (e.g. special education support or lab assistant),
+    // every column except teacher_id is part of lesson's primary key
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS LessonTeachers(
+            weekday       INTEGER NOT NULL,
+            class_id      INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            teacher_id    INTEGER NOT NULL,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id, teacher_id),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id)
+        );
         "
         ,[])?;
     db.execute(
@@ -251,7 +358,8 @@ pub fn init_db() -> Result<Database, SQLiteError>{
         );
         "
         ,[])?;
-    db.execute(
+    // week has the same meaning as in Lessons
+    let duties_table = 
         "CREATE TABLE IF NOT EXISTS Duties(
             weekday       INTEGER NOT NULL,
             break_num     INTEGER NOT NULL,
@@ -259,26 +367,408 @@ pub fn init_db() -> Result<Database, SQLiteError>{
             place_id      INTEGER NOT NULL,
             semester      INTEGER NOT NULL,
             academic_year INTEGER NOT NULL,
-            PRIMARY KEY (weekday, break_num, teacher_id, semester, academic_year),
+            week          INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (weekday, break_num, teacher_id, semester, academic_year, week),
             FOREIGN KEY (break_num)     REFERENCES Breaks    (break_num),
             FOREIGN KEY (teacher_id)    REFERENCES Teachers  (teacher_id),
             FOREIGN KEY (place_id)      REFERENCES Corridors (corridor),
             FOREIGN KEY (academic_year) REFERENCES Years     (academic_year),
             FOREIGN KEY (semester)      REFERENCES Semesters (semester)
         );
+        ";
+    db.execute(duties_table, [])?;
+    rebuild_table(db, "Duties", "week", duties_table)?;
+    // weekday and break_num equal to 0 exclude teacher from every weekday/break
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS DutyExclusions(
+            teacher_id INTEGER NOT NULL,
+            weekday    INTEGER NOT NULL DEFAULT 0,
+            break_num  INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (teacher_id, weekday, break_num),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id)
+        );
         "
         ,[])?;
-    Ok(db)
+    // subjects that teacher is allowed to teach, teacher without 
+    // any qualifications can teach every subject
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Qualifications(
+            teacher_id INTEGER NOT NULL,
+            subject_id INTEGER NOT NULL,
+            PRIMARY KEY (teacher_id, subject_id),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id),
+            FOREIGN KEY (subject_id) REFERENCES Subjects (subject_id)
+        );
+        "
+        ,[])?;
+    // unavailable or preferred lesson hours of teacher, 
+    // lesson hours without row are neutral
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Availability(
+            teacher_id   INTEGER NOT NULL,
+            weekday      INTEGER NOT NULL CHECK(weekday >= 1 AND weekday <= 7),
+            lesson_hour  INTEGER NOT NULL,
+            availability TEXT NOT NULL CHECK(availability IN ('unavailable', 'preferred')),
+            PRIMARY KEY (teacher_id, weekday, lesson_hour),
+            FOREIGN KEY (teacher_id)  REFERENCES Teachers    (teacher_id),
+            FOREIGN KEY (lesson_hour) REFERENCES LessonHours (lesson_hour)
+        );
+        "
+        ,[])?;
+    // classroom_id is NULL when any classroom can be used
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Requirements(
+            class_id      INTEGER NOT NULL,
+            subject_id    INTEGER NOT NULL,
+            teacher_id    INTEGER NOT NULL,
+            classroom_id  INTEGER,
+            hours         INTEGER NOT NULL CHECK(hours > 0),
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            PRIMARY KEY (class_id, subject_id, semester, academic_year),
+            FOREIGN KEY (class_id)      REFERENCES Classes    (class_id),
+            FOREIGN KEY (subject_id)    REFERENCES Subjects   (subject_id),
+            FOREIGN KEY (teacher_id)    REFERENCES Teachers   (teacher_id),
+            FOREIGN KEY (classroom_id)  REFERENCES Classrooms (classroom_id),
+            FOREIGN KEY (semester)      REFERENCES Semesters  (semester),
+            FOREIGN KEY (academic_year) REFERENCES Years      (academic_year)
+        );
+        "
+        ,[])?;
+    // date is formatted as YYYY-MM-DD
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Absences(
+            teacher_id INTEGER NOT NULL,
+            date       TEXT    NOT NULL,
+            PRIMARY KEY (teacher_id, date),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id)
+        );
+        "
+        ,[])?;
+    // substitute_teacher_id teaches class_id (or its group) at lesson_hour on date (YYYY-MM-DD)
+    let substitutions_table = 
+        "CREATE TABLE IF NOT EXISTS Substitutions(
+            date                  TEXT    NOT NULL,
+            class_id              INTEGER NOT NULL,
+            lesson_hour           INTEGER NOT NULL,
+            substitute_teacher_id INTEGER NOT NULL,
+            group_id              INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (date, class_id, lesson_hour, group_id),
+            FOREIGN KEY (class_id)              REFERENCES Classes    (class_id),
+            FOREIGN KEY (lesson_hour)           REFERENCES LessonHours(lesson_hour),
+            FOREIGN KEY (substitute_teacher_id) REFERENCES Teachers   (teacher_id)
+        );
+        ";
+    db.execute(substitutions_table, [])?;
+    rebuild_table(db, "Substitutions", "group_id", substitutions_table)?;
+    // start_date and end_date are formatted as YYYY-MM-DD, day_type is one of: holiday, exam, day_off
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Calendar(
+            calendar_id INTEGER PRIMARY KEY,
+            start_date  TEXT NOT NULL,
+            end_date    TEXT NOT NULL,
+            day_type    TEXT NOT NULL CHECK(day_type IN ('holiday', 'exam', 'day_off')),
+            name        TEXT NOT NULL,
+            CHECK(start_date <= end_date)
+        );
+        "
+        ,[])?;
+    // single reservation of classroom (e.g. meeting, exam or workshop) made by teacher,
+    // date is formatted as YYYY-MM-DD
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Bookings(
+            booking_id    INTEGER PRIMARY KEY CHECK(booking_id > 0),
+            classroom_id  INTEGER NOT NULL,
+            date          TEXT NOT NULL,
+            start_hour    INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour      INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            teacher_id    INTEGER NOT NULL,
+            purpose       TEXT NOT NULL,
+            CHECK(start_hour * 60 + start_minutes < end_hour * 60 + end_minutes),
+            FOREIGN KEY (classroom_id) REFERENCES Classrooms (classroom_id),
+            FOREIGN KEY (teacher_id)   REFERENCES Teachers   (teacher_id)
+        );
+        "
+        ,[])?;
+    // exams, trips, parent meetings and other events, date is formatted as YYYY-MM-DD.
+    // Events that override lessons cancel lessons of their classes and teachers
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS Events(
+            event_id          INTEGER PRIMARY KEY CHECK(event_id > 0),
+            name              TEXT NOT NULL,
+            event_type        TEXT NOT NULL CHECK(event_type IN ('exam', 'trip', 'parent_meeting', 'other')),
+            date              TEXT NOT NULL,
+            start_hour        INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes     INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour          INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes       INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            overrides_lessons INTEGER NOT NULL DEFAULT 0,
+            CHECK(start_hour * 60 + start_minutes < end_hour * 60 + end_minutes)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS EventClasses(
+            event_id INTEGER NOT NULL,
+            class_id INTEGER NOT NULL,
+            PRIMARY KEY (event_id, class_id),
+            FOREIGN KEY (event_id) REFERENCES Events  (event_id),
+            FOREIGN KEY (class_id) REFERENCES Classes (class_id)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS EventTeachers(
+            event_id   INTEGER NOT NULL,
+            teacher_id INTEGER NOT NULL,
+            PRIMARY KEY (event_id, teacher_id),
+            FOREIGN KEY (event_id)   REFERENCES Events   (event_id),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS EventClassrooms(
+            event_id     INTEGER NOT NULL,
+            classroom_id INTEGER NOT NULL,
+            PRIMARY KEY (event_id, classroom_id),
+            FOREIGN KEY (event_id)     REFERENCES Events     (event_id),
+            FOREIGN KEY (classroom_id) REFERENCES Classrooms (classroom_id)
+        );
+        "
+        ,[])?;
+    // one-off changes of lesson on single date (YYYY-MM-DD), columns that are NULL
+    // keep values from weekly timetable. new_lesson_hour moves lesson to other lesson hour
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS LessonOverrides(
+            date            TEXT    NOT NULL,
+            class_id        INTEGER NOT NULL,
+            lesson_hour     INTEGER NOT NULL,
+            group_id        INTEGER NOT NULL DEFAULT 0,
+            cancelled       INTEGER NOT NULL DEFAULT 0,
+            classroom_id    INTEGER,
+            new_lesson_hour INTEGER,
+            teacher_id      INTEGER,
+            PRIMARY KEY (date, class_id, lesson_hour, group_id),
+            FOREIGN KEY (class_id)        REFERENCES Classes    (class_id),
+            FOREIGN KEY (lesson_hour)     REFERENCES LessonHours(lesson_hour),
+            FOREIGN KEY (classroom_id)    REFERENCES Classrooms (classroom_id),
+            FOREIGN KEY (new_lesson_hour) REFERENCES LessonHours(lesson_hour),
+            FOREIGN KEY (teacher_id)      REFERENCES Teachers   (teacher_id)
+        );
+        "
+        ,[])?;
+    // times of lesson hours and breaks that are different on some weekday
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS WeekdayHours(
+            weekday       INTEGER NOT NULL CHECK(weekday >= 1 AND weekday <= 7),
+            lesson_hour   INTEGER NOT NULL,
+            start_hour    INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour      INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            PRIMARY KEY (weekday, lesson_hour),
+            FOREIGN KEY (lesson_hour) REFERENCES LessonHours(lesson_hour)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS WeekdayBreaks(
+            weekday       INTEGER NOT NULL CHECK(weekday >= 1 AND weekday <= 7),
+            break_num     INTEGER NOT NULL,
+            start_hour    INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour      INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            PRIMARY KEY (weekday, break_num),
+            FOREIGN KEY (break_num) REFERENCES Breaks(break_num)
+        );
+        "
+        ,[])?;
+    // named bell schedules (e.g. shortened lessons) that replace times of some lesson hours 
+    // and breaks on dates from ScheduleDays. Times missing from bell schedule stay the same
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS BellSchedules(
+            schedule_id INTEGER PRIMARY KEY CHECK(schedule_id > 0),
+            name        TEXT NOT NULL
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS BellScheduleHours(
+            schedule_id   INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            start_hour    INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour      INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            PRIMARY KEY (schedule_id, lesson_hour),
+            FOREIGN KEY (schedule_id) REFERENCES BellSchedules(schedule_id),
+            FOREIGN KEY (lesson_hour) REFERENCES LessonHours  (lesson_hour)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS BellScheduleBreaks(
+            schedule_id   INTEGER NOT NULL,
+            break_num     INTEGER NOT NULL,
+            start_hour    INTEGER NOT NULL CHECK(start_hour >= 0 AND start_hour < 24),
+            start_minutes INTEGER NOT NULL CHECK(start_minutes >= 0 AND start_minutes < 60),
+            end_hour      INTEGER NOT NULL CHECK(end_hour >= 0 AND end_hour < 24),
+            end_minutes   INTEGER NOT NULL CHECK(end_minutes >= 0 AND end_minutes < 60),
+            PRIMARY KEY (schedule_id, break_num),
+            FOREIGN KEY (schedule_id) REFERENCES BellSchedules(schedule_id),
+            FOREIGN KEY (break_num)   REFERENCES Breaks       (break_num)
+        );
+        "
+        ,[])?;
+    // date is formatted as YYYY-MM-DD
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS ScheduleDays(
+            date        TEXT PRIMARY KEY,
+            schedule_id INTEGER NOT NULL,
+            FOREIGN KEY (schedule_id) REFERENCES BellSchedules(schedule_id)
+        );
+        "
+        ,[])?;
+    // Generated or copied lessons waiting for admin to apply them
+    let draft_lessons_table = 
+        "CREATE TABLE IF NOT EXISTS DraftLessons (
+            weekday       INTEGER NOT NULL,
+            class_id      INTEGER NOT NULL,
+            classroom_id  INTEGER NOT NULL,
+            teacher_id    INTEGER NOT NULL,
+            subject_id    INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id),
+            FOREIGN KEY (class_id)      REFERENCES Classes    (class_id),
+            FOREIGN KEY (classroom_id)  REFERENCES Classrooms (classroom_id),
+            FOREIGN KEY (teacher_id)    REFERENCES Teachers   (teacher_id),
+            FOREIGN KEY (subject_id)    REFERENCES Subjects   (subject_id),
+            FOREIGN KEY (lesson_hour)   REFERENCES LessonHours(lesson_hour),
+            FOREIGN KEY (semester)      REFERENCES Semesters  (semester),
+            FOREIGN KEY (academic_year) REFERENCES Years      (academic_year)
+        );
+        ";
+    db.execute(draft_lessons_table, [])?;
+    rebuild_table(db, "DraftLessons", "week", draft_lessons_table)?;
+    rebuild_table(db, "DraftLessons", "group_id", draft_lessons_table)?;
+    // Copied duties waiting for admin to apply them together with `DraftLessons`
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS DraftDuties(
+            weekday       INTEGER NOT NULL,
+            break_num     INTEGER NOT NULL,
+            teacher_id    INTEGER NOT NULL,
+            place_id      INTEGER NOT NULL,
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (weekday, break_num, teacher_id, semester, academic_year, week),
+            FOREIGN KEY (break_num)     REFERENCES Breaks    (break_num),
+            FOREIGN KEY (teacher_id)    REFERENCES Teachers  (teacher_id),
+            FOREIGN KEY (place_id)      REFERENCES Corridors (corridor),
+            FOREIGN KEY (academic_year) REFERENCES Years     (academic_year),
+            FOREIGN KEY (semester)      REFERENCES Semesters (semester)
+        );
+        "
+        ,[])?;
+    // co-teachers and multi-slot lessons of `DraftLessons` (see `LessonTeachers` and `LessonBlocks`)
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS DraftLessonTeachers(
+            weekday       INTEGER NOT NULL,
+            class_id      INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            teacher_id    INTEGER NOT NULL,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id, teacher_id),
+            FOREIGN KEY (teacher_id) REFERENCES Teachers (teacher_id)
+        );
+        "
+        ,[])?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS DraftLessonBlocks(
+            weekday       INTEGER NOT NULL,
+            class_id      INTEGER NOT NULL,
+            lesson_hour   INTEGER NOT NULL,
+            last_hour     INTEGER NOT NULL CHECK(last_hour > lesson_hour),
+            semester      INTEGER NOT NULL,
+            academic_year INTEGER NOT NULL,
+            week          INTEGER NOT NULL DEFAULT 0,
+            group_id      INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year, week, group_id)
+        );
+        "
+        ,[])?;
+    Ok(())
+}
+
+/// Adds column to table that was created by older version of msat
+fn add_column(db: &Database, table: &str, column: &str, definition: &str) -> Result<(), SQLiteError>{
+    let mut stmt = db.prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1", table))?;
+    if !stmt.exists([column])?{
+        db.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+/// Recreates table that was created by older version of msat without `column`
+/// that is part of primary key (so it can't be added with ALTER TABLE). 
+/// Rows are copied and `column` gets its default value
+fn rebuild_table(db: &Database, table: &str, column: &str, create_table: &str) -> Result<(), SQLiteError>{
+    let mut stmt = db.prepare(&format!("SELECT name FROM pragma_table_info('{}')", table))?;
+    let columns = stmt.query_map([], |row| row.get::<usize, String>(0))?.collect::<Result<Vec<String>, SQLiteError>>()?;
+    if columns.iter().any(|name| name == column){
+        return Ok(());
+    }
+    let columns = columns.join(", ");
+    let tx = db.unchecked_transaction()?;
+    tx.execute(&format!("ALTER TABLE {table} RENAME TO {table}Old"), [])?;
+    tx.execute(create_table, [])?;
+    tx.execute(&format!("INSERT INTO {table} ({columns}) SELECT {columns} FROM {table}Old"), [])?;
+    tx.execute(&format!("DROP TABLE {table}Old"), [])?;
+    tx.commit()
+}
+
+/// Recreates `Classes` created by older version of msat, where names of archived classes 
+/// had to be unique too. Foreign keys are turned off while tables are swapped, 
+/// so tables that reference `Classes` are left as they are
+fn unique_class_names(db: &Database, create_table: &str) -> Result<(), SQLiteError>{
+    if !db.prepare("SELECT 1 FROM pragma_index_list('Classes') WHERE origin = 'u'")?.exists([])?{
+        return Ok(());
+    }
+    let foreign_keys : bool = db.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    db.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let tx = db.unchecked_transaction()?;
+    tx.execute(&create_table.replace("Classes(", "ClassesNew("), [])?;
+    tx.execute("INSERT INTO ClassesNew (class_id, class_name, students, archived) 
+        SELECT class_id, class_name, students, archived FROM Classes", [])?;
+    tx.execute("DROP TABLE Classes", [])?;
+    tx.execute("ALTER TABLE ClassesNew RENAME TO Classes", [])?;
+    tx.commit()?;
+    if foreign_keys{
+        db.execute_batch("PRAGMA foreign_keys = ON;")?;
+    }
+    Ok(())
 }
 
-pub fn get_year_and_semester(db: &rusqlite::Connection) -> Result<(u8, u8), rusqlite::Error>{
+/// Academic year and semester that `date` belongs to
+pub fn get_year_and_semester(date: NaiveDate, db: &rusqlite::Connection) -> Result<(u8, u8), rusqlite::Error>{
     let mut stmt1 = db.prepare("
     SELECT academic_year 
     FROM Years 
     WHERE start_date < ?1
     AND end_date     > ?1")?;
-    let now = chrono::Local::now();
-    let year = stmt1.query_row([now.to_rfc3339()], |row|{
+    let moment = date_to_rfc3339(date);
+    let year = stmt1.query_row([&moment], |row|{
         Ok(row.get::<usize, u8>(0).unwrap_or_default())
     })?;
     let mut stmt2 = db.prepare(
@@ -287,108 +777,229 @@ pub fn get_year_and_semester(db: &rusqlite::Connection) -> Result<(u8, u8), rusq
     WHERE start_date < ?1
     AND end_date     > ?1
     ")?;
-    let semester = stmt2.query_row([now.to_rfc3339()], |row|{
+    let semester = stmt2.query_row([&moment], |row|{
         Ok(row.get::<usize, u8>(0).unwrap_or_default())
     })?;
     Ok((year, semester))
 }
-/// DYNAMIC
-pub fn get_lessons_by_teacher_id(teacher_id: u16, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
-    let now         = chrono::Local::now();
-    let now_iso8601 = now.to_rfc3339();
-    let query = "
-SELECT 
-  Lessons.weekday, 
-  Classes.class_name,
-  Classrooms.classroom_name, 
-  Subjects.subject_name, 
-  LessonHours.start_hour, 
-  LessonHours.start_minutes, 
-  LessonHours.end_hour, 
-  LessonHours.end_minutes
-FROM 
-  Lessons
-  JOIN Classrooms  ON Lessons.classroom_id  = Classrooms.classroom_id
-  JOIN Teachers    ON Lessons.teacher_id    = Teachers.teacher_id
-  JOIN Classes     ON Lessons.class_id      = Classes.class_id
-  JOIN Subjects    ON Lessons.subject_id    = Subjects.subject_id
-  JOIN LessonHours ON Lessons.lesson_hour   = LessonHours.lesson_hour
-  JOIN Years       ON Lessons.academic_year = Years.academic_year
-  JOIN Semesters   ON Lessons.semester      = Semesters.semester
-WHERE 
-  Lessons.class_id < ?1
-  AND Semesters.start_date < ?2 AND Semesters.end_date > ?2
-  AND Years.start_date     < ?2 AND Years.end_date     > ?2
-    ";
-    let mut stmt = db.prepare(query)?;
-
-    let iter = stmt.query_map([teacher_id.to_string(), now_iso8601], |row|{
-        Ok(
-            JoinedLesson{
-                weekday   : row.get(0).ok(),
-                teacher   : None,
-                class     : row.get(1).ok(),
-                classroom : row.get(2).ok(),
-                subject   : row.get(3).ok(),
-                lessonh   : JoinedHour{
-                    lesson_hour  : row.get(8).ok(),
-                    start_hour   : row.get(4).ok(),
-                    start_minute : row.get(5).ok(),
-                    end_hour     : row.get(6).ok(),
-                    end_minutes  : row.get(7).ok()
-                },
-                academic_year : None,
-                semester      : None
-            }
-        )
-    })?;
-    let mut to_return = vec![];
-    for joined_lesson in iter.flatten(){
-        to_return.push(joined_lesson);
+/// Parses date formatted as YYYY-MM-DD
+pub fn parse_date(date: &str) -> Option<NaiveDate>{
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+/// Date from request argument or today if argument is missing or empty
+pub fn date_or_today(arg: Option<&String>) -> Option<NaiveDate>{
+    match arg{
+        Some(date) if !date.is_empty() => parse_date(date),
+        _ => Some(chrono::Local::now().date_naive())
     }
-    Ok(to_return)
 }
-/// DYNAMIC
-pub fn get_lessons_by_class_id(class_id: u16, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
-    let now         = chrono::Local::now();
-    let now_iso8601 = now.to_rfc3339();
-    let query = 
+/// Date and time (HH:MM) from request arguments, missing or empty ones are taken from current moment
+pub fn moment_or_now(date: Option<&String>, time: Option<&String>) -> Option<NaiveDateTime>{
+    let time = match time{
+        Some(time) if !time.is_empty() => NaiveTime::parse_from_str(time, "%H:%M").ok()?,
+        _ => chrono::Local::now().time()
+    };
+    Some(date_or_today(date)?.and_time(time))
+}
+/// Teacher, class or classroom from `teacher_id`, `class_id` or `classroom_id` argument (checked in that order)
+pub fn owner_from_args(args: &HashMap<String, String>) -> Option<TimetableOwner>{
+    let id = |name: &str| args.get(name).and_then(|s| s.parse::<u16>().ok());
+    id("teacher_id").map(TimetableOwner::Teacher)
+        .or_else(|| id("class_id").map(TimetableOwner::Class))
+        .or_else(|| id("classroom_id").map(TimetableOwner::Classroom))
+}
+/// Classroom filter from `min_capacity` and `features` (separated by comma) arguments
+pub fn classroom_filter_from_args(args: &HashMap<String, String>) -> ClassroomFilter{
+    ClassroomFilter{
+        min_capacity : args.get("min_capacity").and_then(|s| s.parse::<u16>().ok()),
+        features     : args.get("features")
+            .map(|s| s.split(',').filter(|f| !f.is_empty()).map(|f| f.replace("%20", " ")).collect())
+            .unwrap_or_default()
+    }
+}
+/// Noon of `date` in the same format as dates sent by dashboard,
+/// so it can be compared with `start_date` and `end_date` columns
+fn date_to_rfc3339(date: NaiveDate) -> String{
+    date.and_hms_opt(12, 0, 0)
+        .and_then(|time| time.and_local_timezone(chrono::Local).earliest())
+        .map(|time| time.with_timezone(&chrono::Utc).to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+/// Week of semester's week cycle (starting from 1) that `date` belongs to,
+/// weeks are counted from the week in which semester starts
+pub fn get_cycle_week(date: NaiveDate, db: &rusqlite::Connection) -> Result<u8, SQLiteError>{
+    let semester = db.query_row("SELECT start_date, week_cycle FROM Semesters WHERE start_date < ?1 AND end_date > ?1", 
+        [date_to_rfc3339(date)], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, i64>(1)?)));
+    let (start_date, week_cycle) = match semester{
+        Ok(semester) => semester,
+        Err(SQLiteError::QueryReturnedNoRows) => return Ok(1),
+        Err(error) => return Err(error)
+    };
+    let start = match DateTime::parse_from_rfc3339(&start_date){
+        Ok(start) => start.with_timezone(&chrono::Local).date_naive(),
+        Err(_) => return Ok(1)
+    };
+    let weeks = (week_start(date) - week_start(start)).num_weeks();
+    Ok((weeks.rem_euclid(week_cycle.max(1)) + 1) as u8)
+}
+/// Monday of the week that contains `date`
+fn week_start(date: NaiveDate) -> NaiveDate{
+    date - chrono::Duration::days(date.weekday().num_days_from_monday().into())
+}
+fn lesson_status(substituted: bool, absent: bool, cancelled: bool, changed: bool) -> LessonStatus{
+    match (substituted, absent, cancelled, changed){
+        (_, _, true, _)             => LessonStatus::Cancelled,
+        (true, _, false, _)         => LessonStatus::Substitution,
+        (false, true, false, _)     => LessonStatus::Cancelled,
+        (false, false, false, true) => LessonStatus::Changed,
+        _                           => LessonStatus::Regular
+    }
+}
+// Lessons with `lesson_date` column holding date of lesson in week starting at ?2
+const WEEK_LESSONS : &str = "(SELECT *, date(?2, '+' || (weekday - 1) || ' days') AS lesson_date FROM Lessons) AS Lessons";
+// Lesson hours and breaks with `date` and `weekday` columns: both NULL for regular times, weekday for
+// times used on that weekday and date for times from bell schedule used on that date
+const DATED_LESSON_HOURS : &str = "(SELECT NULL AS date, NULL AS weekday, lesson_hour, start_hour, start_minutes, end_hour, end_minutes FROM LessonHours
+    UNION ALL
+    SELECT NULL, weekday, lesson_hour, start_hour, start_minutes, end_hour, end_minutes FROM WeekdayHours
+    UNION ALL
+    SELECT ScheduleDays.date, NULL, lesson_hour, start_hour, start_minutes, end_hour, end_minutes
+    FROM ScheduleDays JOIN BellScheduleHours USING (schedule_id)) AS LessonHours";
+const DATED_BREAKS : &str = "(SELECT NULL AS date, NULL AS weekday, break_num, start_hour, start_minutes, end_hour, end_minutes FROM Breaks
+    UNION ALL
+    SELECT NULL, weekday, break_num, start_hour, start_minutes, end_hour, end_minutes FROM WeekdayBreaks
+    UNION ALL
+    SELECT ScheduleDays.date, NULL, break_num, start_hour, start_minutes, end_hour, end_minutes
+    FROM ScheduleDays JOIN BellScheduleBreaks USING (schedule_id)) AS Breaks";
+/// Condition matching row of `DATED_LESSON_HOURS` (if `breaks` is false) or `DATED_BREAKS` with `key`
+/// that has times from bell schedule used on `date`, times of weekday of `date` if bell schedule 
+/// doesn't change them or regular times if neither of them does
+fn bell_times_on(breaks: bool, key: &str, date: &str) -> String{
+    let (table, column, variant, weekday_table) = match breaks{
+        false => ("LessonHours", "lesson_hour", "BellScheduleHours", "WeekdayHours"),
+        true  => ("Breaks", "break_num", "BellScheduleBreaks", "WeekdayBreaks")
+    };
+    let bell_schedule_date = format!("(SELECT ScheduleDays.date FROM ScheduleDays 
+        JOIN {variant} USING (schedule_id) 
+        WHERE ScheduleDays.date = {date} AND {variant}.{column} = {key})");
+    // strftime returns 0 for sunday, weekdays in msat start with 1 for monday
+    let weekday = format!("(SELECT weekday FROM {weekday_table} 
+        WHERE weekday = (CAST(strftime('%w', {date}) AS INTEGER) + 6) % 7 + 1 AND {column} = {key})");
+    format!("{table}.{column} = {key} AND {table}.date IS {bell_schedule_date} 
+        AND {table}.weekday IS (CASE WHEN {bell_schedule_date} IS NULL THEN {weekday} END)")
+}
+// Condition true if lesson from `Lessons` (joined with `LessonHours`) is overridden by event of its class or teacher
+const OVERRIDDEN_BY_EVENT : &str = "EXISTS (SELECT 1 FROM Events 
+    WHERE Events.date = Lessons.lesson_date AND Events.overrides_lessons
+    AND Events.start_hour * 60 + Events.start_minutes < LessonHours.end_hour * 60 + LessonHours.end_minutes
+    AND LessonHours.start_hour * 60 + LessonHours.start_minutes < Events.end_hour * 60 + Events.end_minutes
+    AND (EXISTS (SELECT 1 FROM EventClasses  WHERE EventClasses.event_id  = Events.event_id AND EventClasses.class_id    = Lessons.class_id)
+      OR EXISTS (SELECT 1 FROM EventTeachers WHERE EventTeachers.event_id = Events.event_id AND EventTeachers.teacher_id = Teachers.teacher_id)))";
+// Condition matching rows from `LessonTeachers` with lesson from `Lessons`
+const CO_TEACHERS_OF_LESSON : &str = "LessonTeachers.class_id = Lessons.class_id AND LessonTeachers.weekday = Lessons.weekday
+    AND LessonTeachers.lesson_hour   = Lessons.lesson_hour   AND LessonTeachers.semester = Lessons.semester
+    AND LessonTeachers.academic_year = Lessons.academic_year AND LessonTeachers.week     = Lessons.week
+    AND LessonTeachers.group_id      = Lessons.group_id";
+// Condition matching row from `LessonBlocks` containing lesson from `Lessons`
+const BLOCK_OF_LESSON : &str = "LessonBlocks.class_id = Lessons.class_id AND LessonBlocks.weekday = Lessons.weekday
+    AND Lessons.lesson_hour BETWEEN LessonBlocks.lesson_hour AND LessonBlocks.last_hour
+    AND LessonBlocks.semester = Lessons.semester AND LessonBlocks.academic_year = Lessons.academic_year
+    AND LessonBlocks.week     = Lessons.week     AND LessonBlocks.group_id      = Lessons.group_id";
+// Lessons with one row for every teacher of lesson, `co_teacher` is 1 for rows from `LessonTeachers`
+const TEACHING : &str = "(SELECT weekday, class_id, classroom_id, teacher_id, lesson_hour, semester, academic_year, week, group_id, 0 AS co_teacher 
+    FROM Lessons
+    UNION ALL
+    SELECT weekday, class_id, Lessons.classroom_id, LessonTeachers.teacher_id, lesson_hour, semester, academic_year, week, group_id, 1
+    FROM LessonTeachers 
+    JOIN Lessons USING (weekday, class_id, lesson_hour, semester, academic_year, week, group_id))";
+
+// Like `TEACHING`, but lessons of classes that are in draft come from draft tables
+fn draft_teaching() -> String{
+    format!("(SELECT weekday, class_id, classroom_id, teacher_id, lesson_hour, semester, academic_year, week, group_id, 0 AS co_teacher 
+        FROM DraftLessons
+        UNION ALL
+        SELECT weekday, class_id, DraftLessons.classroom_id, DraftLessonTeachers.teacher_id, lesson_hour, semester, academic_year, week, group_id, 1
+        FROM DraftLessonTeachers 
+        JOIN DraftLessons USING (weekday, class_id, lesson_hour, semester, academic_year, week, group_id)
+        UNION ALL
+        SELECT * FROM {TEACHING} AS Published 
+        WHERE NOT EXISTS (SELECT 1 FROM DraftLessons WHERE DraftLessons.class_id = Published.class_id 
+            AND DraftLessons.semester = Published.semester AND DraftLessons.academic_year = Published.academic_year))")
+}
+
+/// Lessons from week containing `date` that match `filter` (SQL condition where ?1 is `id`), 
+/// with teachers replaced by substitutes, one-off changes from `LessonOverrides` applied,
+/// times from bell schedule of each day and without lessons on days from school calendar.
+/// Only lessons from current week of week cycle are returned
+fn get_effective_lessons(filter: &str, id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
+    let query = format!(
     "
     SELECT 
-        Lessons.weekday, Teachers.teacher_name, Classrooms.classroom_name, Subjects.subject_name,
-        LessonHours.start_hour, LessonHours.start_minutes, LessonHours.end_hour, LessonHours.end_minutes,
-        Years.start_date, Years.end_date 
+        Lessons.weekday, COALESCE(Substitutes.teacher_name, Teachers.teacher_name), Classes.class_name, Classrooms.classroom_name, 
+        Subjects.subject_name, LessonHours.start_hour, LessonHours.start_minutes, LessonHours.end_hour, LessonHours.end_minutes,
+        LessonHours.lesson_hour, Lessons.lesson_date, 
+        Substitutions.class_id IS NOT NULL OR LessonOverrides.teacher_id IS NOT NULL, Absences.teacher_id IS NOT NULL,
+        ClassGroups.group_name,
+        (SELECT GROUP_CONCAT(CoTeachers.teacher_name, ', ') 
+            FROM LessonTeachers 
+            JOIN Teachers AS CoTeachers ON LessonTeachers.teacher_id = CoTeachers.teacher_id 
+            WHERE {}),
+        COALESCE(LessonOverrides.cancelled, 0) OR {},
+        LessonOverrides.classroom_id IS NOT NULL OR LessonOverrides.new_lesson_hour IS NOT NULL,
+        LessonBlocks.lesson_hour, LessonBlocks.last_hour
     FROM 
-        Lessons 
-    JOIN Classrooms  ON Lessons.classroom_id  = Classrooms.classroom_id 
-    JOIN Teachers    ON Lessons.teacher_id    = Teachers.teacher_id
+        {}
+    LEFT JOIN LessonBlocks ON {}
+    LEFT JOIN LessonOverrides ON LessonOverrides.date = Lessons.lesson_date
+        AND LessonOverrides.class_id    = Lessons.class_id
+        AND LessonOverrides.lesson_hour = Lessons.lesson_hour
+        AND LessonOverrides.group_id    = Lessons.group_id
+    JOIN Classrooms  ON COALESCE(LessonOverrides.classroom_id, Lessons.classroom_id)   = Classrooms.classroom_id 
+    JOIN Teachers    ON COALESCE(LessonOverrides.teacher_id, Lessons.teacher_id)       = Teachers.teacher_id
+    JOIN Classes     ON Lessons.class_id      = Classes.class_id
     JOIN Subjects    ON Lessons.subject_id    = Subjects.subject_id
-    JOIN LessonHours ON Lessons.lesson_hour   = LessonHours.lesson_hour
+    JOIN {}    ON {}
     JOIN Years       ON Lessons.academic_year = Years.academic_year
     JOIN Semesters   ON Lessons.semester      = Semesters.semester
-    WHERE Lessons.class_id          < ?1
-    AND   Semesters.start_date      < ?2 AND Semesters.end_date      > ?2
-    AND   Years.start_date          < ?2 AND Years.end_date          > ?2
-    ";
-    let mut stmt = db.prepare(query)?;
+    LEFT JOIN ClassGroups ON Lessons.group_id = ClassGroups.group_id
+    LEFT JOIN Substitutions ON Substitutions.class_id = Lessons.class_id 
+        AND Substitutions.group_id    = Lessons.group_id
+        AND Substitutions.lesson_hour = Lessons.lesson_hour 
+        AND Substitutions.date        = Lessons.lesson_date
+    LEFT JOIN Teachers AS Substitutes ON Substitutions.substitute_teacher_id = Substitutes.teacher_id
+    LEFT JOIN Absences ON Absences.teacher_id = Teachers.teacher_id AND Absences.date = Lessons.lesson_date
+    WHERE ({})
+    AND   (Lessons.week = 0 OR Lessons.week = ?4)
+    AND   Semesters.start_date      < ?3 AND Semesters.end_date      > ?3
+    AND   Years.start_date          < ?3 AND Years.end_date          > ?3
+    AND   NOT EXISTS (SELECT 1 FROM Calendar WHERE Lessons.lesson_date BETWEEN Calendar.start_date AND Calendar.end_date)
+    ORDER BY Lessons.weekday, LessonHours.lesson_hour, Lessons.group_id
+    ", CO_TEACHERS_OF_LESSON, OVERRIDDEN_BY_EVENT, WEEK_LESSONS, BLOCK_OF_LESSON, DATED_LESSON_HOURS,
+    bell_times_on(false, "COALESCE(LessonOverrides.new_lesson_hour, Lessons.lesson_hour)", "Lessons.lesson_date"), filter);
+    let mut stmt = db.prepare(&query)?;
 
-    let iter = stmt.query_map([class_id.to_string(), now_iso8601], |row|{
+    let iter = stmt.query_map(rusqlite::params![id, week_start(date).to_string(), date_to_rfc3339(date), get_cycle_week(date, db)?], |row|{
         Ok(
             JoinedLesson{
                 weekday   : row.get(0).ok(),
                 teacher   : row.get(1).ok(),
-                class     : None,
-                classroom : row.get(2).ok(),
-                subject   : row.get(3).ok(),
+                class     : row.get(2).ok(),
+                classroom : row.get(3).ok(),
+                subject   : row.get(4).ok(),
                 lessonh   : JoinedHour{
-                    lesson_hour: None,
-                    start_hour   : row.get(4).ok(),
-                    start_minute : row.get(5).ok(),
-                    end_hour     : row.get(6).ok(),
-                    end_minutes  : row.get(7).ok()
+                    lesson_hour  : row.get(9).ok(),
+                    start_hour   : row.get(5).ok(),
+                    start_minute : row.get(6).ok(),
+                    end_hour     : row.get(7).ok(),
+                    end_minutes  : row.get(8).ok()
                 },
                 academic_year : None,
-                semester      : None
+                semester      : None,
+                date          : row.get(10).ok(),
+                status        : lesson_status(row.get(11)?, row.get(12)?, row.get(15)?, row.get(16)?),
+                group         : row.get(13)?,
+                co_teachers   : row.get(14)?,
+                block         : row.get::<usize, Option<u16>>(17)?.zip(row.get(18)?)
             }
         )
     })?;
@@ -398,36 +1009,419 @@ pub fn get_lessons_by_class_id(class_id: u16, db: &rusqlite::Connection) -> Resu
     }
     Ok(to_return)
 }
+/// DYNAMIC
+/// Lessons of teacher in week containing `date`: lessons covered by 
+/// substitutes or falling on days of absence are skipped and lessons
+/// where teacher is substitute or co-teacher are added
+pub fn get_lessons_by_teacher_id(teacher_id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
+    get_effective_lessons(&format!(
+        "(Teachers.teacher_id = ?1 AND Substitutions.class_id IS NULL AND Absences.teacher_id IS NULL) 
+        OR Substitutions.substitute_teacher_id = ?1
+        OR (EXISTS (SELECT 1 FROM LessonTeachers WHERE LessonTeachers.teacher_id = ?1 AND {}) 
+            AND NOT EXISTS (SELECT 1 FROM Absences AS CoAbsences WHERE CoAbsences.teacher_id = ?1 AND CoAbsences.date = Lessons.lesson_date))",
+        CO_TEACHERS_OF_LESSON), teacher_id, date, db)
+}
+/// DYNAMIC
+/// Lessons of class in week containing `date` with
+/// teachers replaced by substitutes
+pub fn get_lessons_by_class_id(class_id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
+    get_effective_lessons("Lessons.class_id = ?1", class_id, date, db)
+}
 
-pub fn get_duties_for_teacher(teacher_id: u16, db: &rusqlite::Connection) -> Result<Vec<JoinedDuty>, rusqlite::Error>{
-    let now         = chrono::Local::now();
-    let now_iso8601 = now.to_rfc3339();
-    let query = "
-    SELECT 
-        Corridors.corridor_name, Breaks.start_hour, Breaks.start_minutes, Breaks.end_hour, Breaks.end_minutes, Breaks.break_num, Duties.weekday
-    FROM Duties
-    JOIN Teachers  ON Duties.teacher_id    = Teachers.teacher_id
-    JOIN Breaks    ON Duties.break_num     = Breaks.break_num
-    JOIN Corridors ON Duties.place_id      = Corridors.corridor 
-    JOIN Years     ON Duties.academic_year = Years.academic_year
-    JOIN Semesters ON Duties.semester      = Semesters.semester
-    WHERE Duties.teacher_id  = ?1
-    AND Semesters.start_date < ?2 AND Semesters.end_date > ?2
-    AND Years.start_date     < ?2 AND Years.end_date     > ?2
-    ";
-    let mut stmt = db.prepare(query)?;
-    let iter = stmt.query_map([teacher_id.to_string(), now_iso8601], |row| {
-        Ok(
-            JoinedDuty{
-                weekday       : row.get(6).ok(),
-                place         : row.get(0).ok(),
-                teacher       : None,
-                semester      : None,
-                academic_year : None,
-                break_num: JoinedHour{
-                    lesson_hour  : row.get(5).ok(),
-                    start_hour   : row.get(1).ok(),
-                    start_minute : row.get(2).ok(),
+/// Lessons in classroom in week that `date` belongs to
+pub fn get_lessons_by_classroom_id(classroom_id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, rusqlite::Error> {
+    get_effective_lessons("Classrooms.classroom_id = ?1", classroom_id, date, db)
+}
+
+/// Lesson or duty that takes place at `moment` and the one that follows it on the same day.
+/// Cancelled lessons are skipped and duties are included only for teachers
+pub fn get_now_and_next(owner: TimetableOwner, moment: NaiveDateTime, db: &rusqlite::Connection) -> Result<NowAndNext, SQLiteError>{
+    let date = moment.date();
+    if !is_school_day(date, db)?{
+        return Ok(NowAndNext{current: Activity::NoSchool, next: Activity::NoSchool});
+    }
+    let lessons = match owner{
+        TimetableOwner::Teacher(teacher_id)     => get_lessons_by_teacher_id(teacher_id, date, db)?,
+        TimetableOwner::Class(class_id)         => get_lessons_by_class_id(class_id, date, db)?,
+        TimetableOwner::Classroom(classroom_id) => get_lessons_by_classroom_id(classroom_id, date, db)?
+    };
+    // minutes since midnight
+    let span = |hour: &JoinedHour| match (hour.start_hour, hour.start_minute, hour.end_hour, hour.end_minutes){
+        (Some(sh), Some(sm), Some(eh), Some(em)) => Some((sh as u32 * 60 + sm as u32, eh as u32 * 60 + em as u32)),
+        _ => None
+    };
+    let mut activities : Vec<(u32, u32, Activity)> = vec![];
+    for lesson in lessons{
+        if lesson.date == Some(date.to_string()) && lesson.status != LessonStatus::Cancelled{
+            if let Some((start, end)) = span(&lesson.lessonh){
+                activities.push((start, end, Activity::Lesson(lesson)));
+            }
+        }
+    }
+    if let TimetableOwner::Teacher(teacher_id) = owner{
+        for duty in get_duties_for_teacher(teacher_id, date, db)?{
+            if duty.weekday == Some(date.weekday().number_from_monday() as u8){
+                if let Some((start, end)) = span(&duty.break_num){
+                    activities.push((start, end, Activity::Duty(duty)));
+                }
+            }
+        }
+    }
+    activities.sort_by_key(|(start, _, _)| *start);
+
+    let now = moment.hour() * 60 + moment.minute();
+    let current = activities.iter().position(|(start, end, _)| *start <= now && now < *end);
+    let next = activities.iter().position(|(start, _, _)| *start > now);
+    let mut now_and_next = NowAndNext{
+        current : match next{
+            Some(0) => Activity::BeforeSchool,
+            Some(_) => Activity::FreePeriod,
+            None    => Activity::AfterSchool
+        },
+        next    : Activity::AfterSchool
+    };
+    for (i, (_, _, activity)) in activities.into_iter().enumerate(){
+        if Some(i) == current{
+            now_and_next.current = activity;
+        }
+        else if Some(i) == next{
+            now_and_next.next = activity;
+        }
+    }
+    Ok(now_and_next)
+}
+
+/// Lessons returned by `query` for every week between `from` and `to` (inclusive), e.g.
+/// `get_lessons_between(from, to, |date| get_lessons_by_class_id(class_id, date, db))`
+pub fn get_lessons_between<F>(from: NaiveDate, to: NaiveDate, query: F) -> Result<Vec<JoinedLesson>, SQLiteError>
+where F: Fn(NaiveDate) -> Result<Vec<JoinedLesson>, SQLiteError>
+{
+    let (first, last) = (from.to_string(), to.to_string());
+    let mut to_return = vec![];
+    let mut week = week_start(from);
+    while week <= to{
+        for lesson in query(week)?{
+            if lesson.date.as_ref().is_some_and(|date| &first <= date && date <= &last){
+                to_return.push(lesson);
+            }
+        }
+        week += chrono::Duration::weeks(1);
+    }
+    Ok(to_return)
+}
+
+/// Lessons of `owner` between `from` and `to` (inclusive), see `get_lessons_between`
+pub fn get_timetable_between(owner: TimetableOwner, from: NaiveDate, to: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, SQLiteError>{
+    get_lessons_between(from, to, |date| match owner{
+        TimetableOwner::Teacher(teacher_id)     => get_lessons_by_teacher_id(teacher_id, date, db),
+        TimetableOwner::Class(class_id)         => get_lessons_by_class_id(class_id, date, db),
+        TimetableOwner::Classroom(classroom_id) => get_lessons_by_classroom_id(classroom_id, date, db)
+    })
+}
+
+/// Lesson without group (`group_id` = 0) or with group of its class
+pub fn is_group_of_class(group_id: u16, class_id: u16, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    if group_id == 0{
+        return Ok(true);
+    }
+    db.query_row("SELECT EXISTS (SELECT 1 FROM ClassGroups WHERE group_id = ?1 AND class_id = ?2)", [group_id, class_id], |row| row.get(0))
+}
+
+/// Teacher can teach subject if it's one of teacher's `Qualifications`
+/// or if teacher has no qualifications at all
+pub fn is_qualified(teacher_id: u16, subject_id: u16, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    db.query_row("SELECT NOT EXISTS (SELECT 1 FROM Qualifications WHERE teacher_id = ?1)
+        OR EXISTS (SELECT 1 FROM Qualifications WHERE teacher_id = ?1 AND subject_id = ?2)", [teacher_id, subject_id], |row| row.get(0))
+}
+
+/// Subjects that teacher is qualified to teach as (subject_id, subject_name)
+pub fn get_qualifications(teacher_id: u16, db: &rusqlite::Connection) -> Result<Vec<(u16, String)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT Subjects.subject_id, Subjects.subject_name 
+        FROM Qualifications
+        JOIN Subjects ON Qualifications.subject_id = Subjects.subject_id
+        WHERE Qualifications.teacher_id = ?1
+        ORDER BY Subjects.subject_id")?;
+    let iter = stmt.query_map([teacher_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    iter.collect()
+}
+
+/// Teachers allowed to teach subject (see `is_qualified`) as (teacher_id, teacher_name)
+pub fn get_qualified_teachers(subject_id: u16, db: &rusqlite::Connection) -> Result<Vec<(u16, String)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT teacher_id, teacher_name 
+        FROM Teachers
+        WHERE NOT EXISTS (SELECT 1 FROM Qualifications WHERE Qualifications.teacher_id = Teachers.teacher_id)
+        OR EXISTS (SELECT 1 FROM Qualifications WHERE Qualifications.teacher_id = Teachers.teacher_id AND subject_id = ?1)
+        ORDER BY teacher_id")?;
+    let iter = stmt.query_map([subject_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    iter.collect()
+}
+
+/// Teacher is available unless lesson hour is marked as unavailable in `Availability`
+pub fn is_available(teacher_id: u16, weekday: u8, lesson_hour: u16, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    db.query_row("SELECT NOT EXISTS (SELECT 1 FROM Availability 
+        WHERE teacher_id = ?1 AND weekday = ?2 AND lesson_hour = ?3 AND availability = 'unavailable')", 
+        rusqlite::params![teacher_id, weekday, lesson_hour], |row| row.get(0))
+}
+
+/// Teacher can have duty in break unless lesson hour right before or after it is unavailable
+pub fn is_available_for_break(teacher_id: u16, weekday: u8, break_num: u8, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    let Some((_, before, after)) = lesson_hours_around_breaks(db)?.into_iter().find(|(b, _, _)| *b == break_num) else {
+        return Ok(true);
+    };
+    for lesson_hour in [before, after].into_iter().flatten(){
+        if !is_available(teacher_id, weekday, lesson_hour, db)?{
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Availability of teacher as (weekday, lesson_hour, availability), neutral lesson hours are skipped
+pub fn get_availability(teacher_id: u16, db: &rusqlite::Connection) -> Result<Vec<(u8, u16, Availability)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT weekday, lesson_hour, availability FROM Availability 
+        WHERE teacher_id = ?1 
+        ORDER BY weekday, lesson_hour")?;
+    let iter = stmt.query_map([teacher_id], |row| {
+        let availability : String = row.get(2)?;
+        Ok((row.get(0)?, row.get(1)?, Availability::parse(&availability).unwrap_or(Availability::Neutral)))
+    })?;
+    iter.collect()
+}
+
+/// Lessons (also co-taught ones) of semester that take place when their teacher is unavailable
+pub fn get_availability_violations(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<Vec<AvailabilityViolation>, SQLiteError>{
+    let mut stmt = db.prepare(&format!("
+    SELECT Lessons.teacher_id, Teachers.teacher_name, Lessons.weekday, Lessons.lesson_hour, 
+        Lessons.class_id, Classes.class_name, Lessons.week, Lessons.group_id
+    FROM {} AS Lessons
+    JOIN Availability ON Availability.teacher_id  = Lessons.teacher_id 
+                     AND Availability.weekday     = Lessons.weekday 
+                     AND Availability.lesson_hour = Lessons.lesson_hour
+    JOIN Teachers ON Lessons.teacher_id = Teachers.teacher_id
+    JOIN Classes  ON Lessons.class_id   = Classes.class_id
+    WHERE Lessons.semester = ?1 AND Lessons.academic_year = ?2 AND Availability.availability = 'unavailable'
+    ORDER BY Lessons.teacher_id, Lessons.weekday, Lessons.lesson_hour
+    ", TEACHING))?;
+    let iter = stmt.query_map([semester, academic_year], |row| {
+        Ok(
+            AvailabilityViolation{
+                teacher_id   : row.get(0)?,
+                teacher_name : row.get(1)?,
+                weekday      : row.get(2)?,
+                lesson_hour  : row.get(3)?,
+                class_id     : row.get(4)?,
+                class        : row.get(5)?,
+                week         : row.get(6)?,
+                group_id     : row.get(7)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+
+/// Groups of class as (group_id, group_name)
+pub fn get_groups(class_id: u16, db: &rusqlite::Connection) -> Result<Vec<(u16, String)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT group_id, group_name FROM ClassGroups WHERE class_id = ?1 ORDER BY group_name")?;
+    let iter = stmt.query_map([class_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    iter.collect()
+}
+
+/// Entry from school calendar that covers `date`
+pub fn get_calendar_entry(date: NaiveDate, db: &rusqlite::Connection) -> Result<Option<CalendarEntry>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT calendar_id, start_date, end_date, day_type, name 
+        FROM Calendar 
+        WHERE ?1 BETWEEN start_date AND end_date
+        ORDER BY start_date DESC")?;
+    let mut iter = stmt.query_map([date.to_string()], calendar_entry_from_row)?;
+    iter.next().transpose()
+}
+/// Every entry from school calendar
+pub fn get_calendar(db: &rusqlite::Connection) -> Result<Vec<CalendarEntry>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT calendar_id, start_date, end_date, day_type, name 
+        FROM Calendar 
+        ORDER BY start_date")?;
+    let iter = stmt.query_map([], calendar_entry_from_row)?;
+    iter.collect()
+}
+fn calendar_entry_from_row(row: &rusqlite::Row) -> Result<CalendarEntry, SQLiteError>{
+    let day_type : String = row.get(3)?;
+    Ok(
+        CalendarEntry{
+            calendar_id : row.get(0)?,
+            start_date  : row.get(1)?,
+            end_date    : row.get(2)?,
+            day_type    : DayType::parse(&day_type).unwrap_or(DayType::DayOff),
+            name        : row.get(4)?
+        }
+    )
+}
+/// School is open on school days of semester that
+/// aren't holidays or days off in school calendar
+pub fn is_school_day(date: NaiveDate, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    if !SCHOOL_DAYS.contains(&(date.weekday().number_from_monday() as u8)){
+        return Ok(false);
+    }
+    let in_semester = db.prepare("SELECT 1 FROM Semesters WHERE start_date < ?1 AND end_date > ?1")?
+        .exists([date_to_rfc3339(date)])?;
+    Ok(in_semester && get_calendar_entry(date, db)?.is_none_or(|entry| entry.day_type == DayType::Exam))
+}
+/// Lessons of every class that take place on `date`
+pub fn get_schedule_for_date(date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, SQLiteError>{
+    if !is_school_day(date, db)?{
+        return Ok(vec![]);
+    }
+    let mut lessons = get_effective_lessons("Lessons.weekday = ?1", date.weekday().number_from_monday() as u16, date, db)?;
+    lessons.sort_by(|a, b| (&a.class, a.lessonh.lesson_hour, &a.group).cmp(&(&b.class, b.lessonh.lesson_hour, &b.group)));
+    Ok(lessons)
+}
+
+/// Lessons on `date` taught by teachers that are absent on that day. Lessons cancelled
+/// or given to other teacher with `LessonOverrides` don't need cover
+pub fn get_cover_lessons(date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<CoverLesson>, SQLiteError>{
+    let mut stmt = db.prepare("
+    SELECT Lessons.class_id, Lessons.lesson_hour, Classes.class_name, Subjects.subject_name, Teachers.teacher_name, Substitutes.teacher_name,
+        Lessons.group_id, ClassGroups.group_name
+    FROM Lessons
+    JOIN Absences  ON Absences.teacher_id  = Lessons.teacher_id AND Absences.date = ?1
+    JOIN Classes   ON Lessons.class_id     = Classes.class_id
+    JOIN Subjects  ON Lessons.subject_id   = Subjects.subject_id
+    JOIN Teachers  ON Lessons.teacher_id   = Teachers.teacher_id
+    JOIN Years     ON Lessons.academic_year = Years.academic_year
+    JOIN Semesters ON Lessons.semester      = Semesters.semester
+    LEFT JOIN ClassGroups ON Lessons.group_id = ClassGroups.group_id
+    LEFT JOIN Substitutions ON Substitutions.class_id = Lessons.class_id 
+        AND Substitutions.group_id    = Lessons.group_id
+        AND Substitutions.lesson_hour = Lessons.lesson_hour 
+        AND Substitutions.date        = ?1
+    LEFT JOIN Teachers AS Substitutes ON Substitutions.substitute_teacher_id = Substitutes.teacher_id
+    WHERE Lessons.weekday = ?2
+    AND (Lessons.week = 0 OR Lessons.week = ?4)
+    AND NOT EXISTS (SELECT 1 FROM LessonOverrides 
+        WHERE LessonOverrides.date = ?1 AND LessonOverrides.class_id = Lessons.class_id 
+        AND LessonOverrides.lesson_hour = Lessons.lesson_hour AND LessonOverrides.group_id = Lessons.group_id
+        AND (LessonOverrides.cancelled OR LessonOverrides.teacher_id IS NOT NULL))
+    AND Semesters.start_date < ?3 AND Semesters.end_date > ?3
+    AND Years.start_date     < ?3 AND Years.end_date     > ?3
+    ORDER BY Lessons.lesson_hour, Classes.class_name, Lessons.group_id
+    ")?;
+    let weekday = date.weekday().number_from_monday();
+    let iter = stmt.query_map(rusqlite::params![date.to_string(), weekday, date_to_rfc3339(date), get_cycle_week(date, db)?], |row| {
+        Ok(
+            CoverLesson{
+                class_id    : row.get(0)?,
+                lesson_hour : row.get(1)?,
+                class       : row.get(2)?,
+                subject     : row.get(3)?,
+                teacher     : row.get(4)?,
+                substitute  : row.get(5)?,
+                group_id    : row.get(6)?,
+                group       : row.get(7)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+
+/// Teachers without lesson, absence or other substitution during lesson
+/// of `class_id` (or its group) at `lesson_hour` on `date`, who aren't unavailable, given other
+/// lesson with `LessonOverrides` or taking part in event at that time.
+/// Teachers qualified to teach subject of lesson (see `Qualifications`) are listed first,
+/// then teachers who prefer that lesson hour (see `Availability`)
+pub fn suggest_substitutes(date: NaiveDate, class_id: u16, group_id: u16, lesson_hour: u16, db: &rusqlite::Connection) -> Result<Vec<SubstituteCandidate>, SQLiteError>{
+    let weekday = date.weekday().number_from_monday();
+    let week    = get_cycle_week(date, db)?;
+    let (teacher_id, subject_id, semester, academic_year) : (u16, u16, u8, u8) = db.query_row("
+    SELECT Lessons.teacher_id, Lessons.subject_id, Lessons.semester, Lessons.academic_year
+    FROM Lessons
+    JOIN Years     ON Lessons.academic_year = Years.academic_year
+    JOIN Semesters ON Lessons.semester      = Semesters.semester
+    WHERE Lessons.class_id = ?1 AND Lessons.lesson_hour = ?2 AND Lessons.weekday = ?3
+    AND (Lessons.week = 0 OR Lessons.week = ?5) AND Lessons.group_id = ?6
+    AND Semesters.start_date < ?4 AND Semesters.end_date > ?4
+    AND Years.start_date     < ?4 AND Years.end_date     > ?4
+    ", rusqlite::params![class_id, lesson_hour, weekday, date_to_rfc3339(date), week, group_id], |row| {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+    })?;
+    let mut stmt = db.prepare(&format!("
+    SELECT Teachers.teacher_id, Teachers.teacher_name, 
+        EXISTS (SELECT 1 FROM Qualifications 
+            WHERE Qualifications.teacher_id = Teachers.teacher_id AND subject_id = ?2) AS qualified,
+        EXISTS (SELECT 1 FROM Availability 
+            WHERE Availability.teacher_id = Teachers.teacher_id AND weekday = ?3 AND lesson_hour = ?4 AND availability = 'preferred') AS preferred
+    FROM Teachers
+    WHERE Teachers.teacher_id != ?1
+    AND (qualified OR NOT EXISTS (SELECT 1 FROM Qualifications WHERE Qualifications.teacher_id = Teachers.teacher_id))
+    AND NOT EXISTS (SELECT 1 FROM {} AS Lessons 
+        WHERE Lessons.teacher_id = Teachers.teacher_id AND weekday = ?3 AND lesson_hour = ?4 
+        AND semester = ?5 AND academic_year = ?6 AND (week = 0 OR week = ?9))
+    AND NOT EXISTS (SELECT 1 FROM Absences 
+        WHERE Absences.teacher_id = Teachers.teacher_id AND date = ?7)
+    AND NOT EXISTS (SELECT 1 FROM Substitutions 
+        WHERE substitute_teacher_id = Teachers.teacher_id AND date = ?7 AND lesson_hour = ?4 AND (class_id != ?8 OR group_id != ?10))
+    AND NOT EXISTS (SELECT 1 FROM Availability 
+        WHERE Availability.teacher_id = Teachers.teacher_id AND weekday = ?3 AND lesson_hour = ?4 AND availability = 'unavailable')
+    AND NOT EXISTS (SELECT 1 FROM LessonOverrides 
+        JOIN Lessons USING (class_id, lesson_hour, group_id)
+        WHERE LessonOverrides.date = ?7 AND NOT LessonOverrides.cancelled
+        AND Lessons.weekday = ?3 AND Lessons.semester = ?5 AND Lessons.academic_year = ?6 AND (Lessons.week = 0 OR Lessons.week = ?9)
+        AND COALESCE(LessonOverrides.teacher_id, Lessons.teacher_id) = Teachers.teacher_id
+        AND COALESCE(LessonOverrides.new_lesson_hour, LessonOverrides.lesson_hour) = ?4
+        AND (LessonOverrides.class_id != ?8 OR LessonOverrides.group_id != ?10))
+    AND NOT EXISTS (SELECT 1 FROM Events 
+        JOIN EventTeachers ON EventTeachers.event_id = Events.event_id
+        WHERE EventTeachers.teacher_id = Teachers.teacher_id AND Events.date = ?7
+        AND Events.start_hour * 60 + Events.start_minutes < ?12 AND ?11 < Events.end_hour * 60 + Events.end_minutes)
+    ORDER BY qualified DESC, preferred DESC, Teachers.teacher_id
+    ", TEACHING))?;
+    // minutes since midnight when lesson starts and ends on that day
+    let (start, end) = get_lesson_hours_for_date(date, db)?.into_iter()
+        .find(|hour| hour.lesson_hour == Some(lesson_hour))
+        .map(|hour| (hour.start_hour.zip(hour.start_minute).map(|(h, m)| h as u16 * 60 + m as u16), 
+                     hour.end_hour.zip(hour.end_minutes).map(|(h, m)| h as u16 * 60 + m as u16)))
+        .unwrap_or_default();
+    let iter = stmt.query_map(
+        rusqlite::params![teacher_id, subject_id, weekday, lesson_hour, semester, academic_year, date.to_string(), class_id, week, group_id, start, end], |row| {
+        Ok(
+            SubstituteCandidate{
+                teacher_id   : row.get(0)?,
+                teacher_name : row.get(1)?,
+                qualified    : row.get(2)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+
+/// Duties of teacher in semester, academic year and week of week cycle that `date` belongs to.
+/// Times of breaks come from bell schedule used on day of duty
+pub fn get_duties_for_teacher(teacher_id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedDuty>, rusqlite::Error>{
+    let query = format!("
+    SELECT 
+        Corridors.corridor_name, Breaks.start_hour, Breaks.start_minutes, Breaks.end_hour, Breaks.end_minutes, Breaks.break_num, Duties.weekday
+    FROM (SELECT *, date(?4, '+' || (weekday - 1) || ' days') AS duty_date FROM Duties) AS Duties
+    JOIN Teachers  ON Duties.teacher_id    = Teachers.teacher_id
+    JOIN {}    ON {}
+    JOIN Corridors ON Duties.place_id      = Corridors.corridor 
+    JOIN Years     ON Duties.academic_year = Years.academic_year
+    JOIN Semesters ON Duties.semester      = Semesters.semester
+    WHERE Duties.teacher_id  = ?1
+    AND (Duties.week = 0 OR Duties.week = ?3)
+    AND Semesters.start_date < ?2 AND Semesters.end_date > ?2
+    AND Years.start_date     < ?2 AND Years.end_date     > ?2
+    ", DATED_BREAKS, bell_times_on(true, "Duties.break_num", "Duties.duty_date"));
+    let mut stmt = db.prepare(&query)?;
+    let iter = stmt.query_map([teacher_id.to_string(), date_to_rfc3339(date), get_cycle_week(date, db)?.to_string(), week_start(date).to_string()], |row| {
+        Ok(
+            JoinedDuty{
+                weekday       : row.get(6).ok(),
+                place         : row.get(0).ok(),
+                teacher       : None,
+                semester      : None,
+                academic_year : None,
+                break_num: JoinedHour{
+                    lesson_hour  : row.get(5).ok(),
+                    start_hour   : row.get(1).ok(),
+                    start_minute : row.get(2).ok(),
                     end_hour     : row.get(3).ok(),
                     end_minutes  : row.get(4).ok()
                 }
@@ -460,7 +1454,7 @@ pub enum GET{
     Duty      {weekd: u8, break_num: u8, teacher_id: u16, semester: u8, academic_year: u8}
 }
 pub enum Delete{
-    Lesson     {class: u16, weekd: u8, lessonh: u16, semester: u8, academic_year: u8},
+    Lesson     {class: u16, weekd: u8, lessonh: u16, semester: u8, academic_year: u8, week: u8, group_id: u16},
     Year       {academic_year: u8},
     Semester   {semester: u8},
     Subject    {subject: u16},
@@ -470,23 +1464,1549 @@ pub enum Delete{
     LessonHour {lessonh: u16},
     Corridor   {corridor: u16},
     Break      {break_num: u16},
-    Duty       {weekday: u8, break_num: u8, teacher_id: u16, semester: u8, academic_year: u8}
+    Duty       {weekday: u8, break_num: u8, teacher_id: u16, semester: u8, academic_year: u8, week: u8},
+    Requirement{class: u16, subject: u16, semester: u8, academic_year: u8},
+    DutyExclusion{teacher_id: u16, weekday: u8, break_num: u8},
+    Absence     {teacher_id: u16, date: NaiveDate},
+    Substitution{date: NaiveDate, class_id: u16, lesson_hour: u16, group_id: u16},
+    CalendarEntry{calendar_id: u16},
+    /// also removes lessons and substitutions of group
+    Group       {group_id: u16},
+    CoTeacher   {key: LessonKey, teacher_id: u16},
+    Qualification{teacher_id: u16, subject_id: u16},
+    ClassroomFeature{classroom_id: u16, feature: String},
+    SubjectFeature{subject_id: u16, feature: String},
+    Booking     {booking_id: u16},
+    /// also removes classes, teachers and classrooms of event
+    Event       {event_id: u16},
+    EventParticipant{event_id: u16, participant: TimetableOwner},
+    LessonOverride{date: NaiveDate, class_id: u16, lesson_hour: u16, group_id: u16},
+    /// also removes times and days of bell schedule
+    BellSchedule{schedule_id: u16},
+    ScheduleDay {date: NaiveDate},
+    WeekdayHour {weekday: u8, lesson_hour: u16},
+    WeekdayBreak{weekday: u8, break_num: u8},
+    DraftLesson {class: u16, weekd: u8, lessonh: u16, semester: u8, academic_year: u8, week: u8, group_id: u16},
+    DraftDuty   {weekday: u8, break_num: u8, teacher_id: u16, semester: u8, academic_year: u8, week: u8},
+    DraftCoTeacher{key: LessonKey, teacher_id: u16}
 }
 
-type Lesson = (u8, u16, u16, u16, u16, u16, u8, u8);
+/// (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id)
+pub type Lesson = (u8, u16, u16, u16, u16, u16, u8, u8, u8, u16);
+/// Primary key of lesson: (weekday, class_id, lesson_hour, semester, academic_year, week, group_id)
+pub type LessonKey = (u8, u16, u16, u8, u8, u8, u16);
+/// (booking_id, classroom_id, date, start_hour, start_minute, end_hour, end_minute, teacher_id, purpose)
+pub type Booking = (u16, u16, NaiveDate, u8, u8, u8, u8, u16, String);
+/// (event_id, name, event_type, date, start_hour, start_minute, end_hour, end_minute, overrides_lessons)
+pub type Event = (u16, String, EventType, NaiveDate, u8, u8, u8, u8, bool);
+/// (date, class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id), 
+/// None keeps value from weekly timetable
+pub type LessonOverride = (NaiveDate, u16, u16, u16, bool, Option<u16>, Option<u16>, Option<u16>);
+/// (class_id, subject_id, teacher_id, classroom_id, hours, semester, academic_year)
+pub type LessonRequirement = (u16, u16, u16, Option<u16>, u8, u8, u8);
 
 pub enum POST{
     Lesson      (Option<Lesson>),
-    Class       (Option<(u16, String)>),
-    Classroom   (Option<(u16, String)>),
-    Teacher     (Option<(u16, String)>),
+    /// (class_id, class_name, students)
+    Class       (Option<(u16, String, Option<u16>)>),
+    /// (classroom_id, classroom_name, corridor, capacity)
+    Classroom   (Option<(u16, String, Option<u16>, Option<u16>)>),
+    /// (teacher_id, teacher_name, pensum)
+    Teacher     (Option<(u16, String, Option<u8>)>),
     Subject     (Option<(u16, String)>),
     LessonHours (Option<(u16, u8, u8, u8, u8)>),
-    Semester    (Option<(u8, String, String, String)>),
+    /// (semester, semester_name, start_date, end_date, week_cycle)
+    Semester    (Option<(u8, String, String, String, u8)>),
     Year        (Option<(u8, String, String, String)>),
     Corridors   (Option<(u16, String)>),
     Break       (Option<(u8, u8, u8, u8, u8)>),
-    Duty        (Option<(u8, u8, u16, u16, u8, u8)>)
+    /// (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+    Duty        (Option<(u8, u8, u16, u16, u8, u8, u8)>),
+    Requirement (Option<LessonRequirement>),
+    /// (teacher_id, weekday, break_num)
+    DutyExclusion(Option<(u16, u8, u8)>),
+    /// (teacher_id, date)
+    Absence     (Option<(u16, NaiveDate)>),
+    /// (date, class_id, lesson_hour, substitute_teacher_id, group_id)
+    Substitution(Option<(NaiveDate, u16, u16, u16, u16)>),
+    /// (calendar_id, start_date, end_date, day_type, name)
+    CalendarEntry(Option<(u16, NaiveDate, NaiveDate, DayType, String)>),
+    /// (group_id, class_id, group_name)
+    Group       (Option<(u16, u16, String)>),
+    /// (lesson, teacher_id)
+    CoTeacher   (Option<(LessonKey, u16)>),
+    /// (teacher_id, subject_id)
+    Qualification(Option<(u16, u16)>),
+    /// (teacher_id, weekday, lesson_hour, availability), `Neutral` removes entry
+    Availability(Option<(u16, u8, u16, Availability)>),
+    /// (classroom_id, feature)
+    ClassroomFeature(Option<(u16, String)>),
+    /// (subject_id, feature)
+    SubjectFeature(Option<(u16, String)>),
+    Booking     (Option<Booking>),
+    Event       (Option<Event>),
+    /// (event_id, class, teacher or classroom taking part in event)
+    EventParticipant(Option<(u16, TimetableOwner)>),
+    LessonOverride(Option<LessonOverride>),
+    /// (schedule_id, name)
+    BellSchedule(Option<(u16, String)>),
+    /// (schedule_id, lesson_hour, start_hour, start_minute, end_hour, end_minute)
+    BellScheduleHour(Option<(u16, u16, u8, u8, u8, u8)>),
+    /// (schedule_id, break_num, start_hour, start_minute, end_hour, end_minute)
+    BellScheduleBreak(Option<(u16, u8, u8, u8, u8, u8)>),
+    /// (date, schedule_id)
+    ScheduleDay (Option<(NaiveDate, u16)>),
+    /// (weekday, lesson_hour, start_hour, start_minute, end_hour, end_minute)
+    WeekdayHour (Option<(u8, u16, u8, u8, u8, u8)>),
+    /// (weekday, break_num, start_hour, start_minute, end_hour, end_minute)
+    WeekdayBreak(Option<(u8, u8, u8, u8, u8, u8)>),
+    DraftLesson (Option<Lesson>),
+    /// (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+    DraftDuty   (Option<(u8, u8, u16, u16, u8, u8, u8)>),
+    DraftCoTeacher(Option<(LessonKey, u16)>)
+}
+
+/// Returns lessons that would clash with `lesson` (same teacher, classroom
+/// or students) if it was inserted. Lesson that would be replaced by `lesson` is skipped.
+/// Co-teachers of both `lesson` and other lessons are treated as booked
+pub fn get_lesson_conflicts(lesson: &Lesson, db: &rusqlite::Connection) -> Result<Vec<LessonConflict>, SQLiteError>{
+    let (weekd, class_id, _, teacher_id, _, lessonh, semester, academic_year, week, group_id) = *lesson;
+    let mut teachers = get_co_teacher_ids((weekd, class_id, lessonh, semester, academic_year, week, group_id), db)?;
+    teachers.push(teacher_id);
+    get_slot_conflicts(lesson, &teachers, db)
+}
+/// Teachers from `LessonTeachers` of lesson with `key`
+fn get_co_teacher_ids(key: LessonKey, db: &rusqlite::Connection) -> Result<Vec<u16>, SQLiteError>{
+    let (weekd, class_id, lessonh, semester, academic_year, week, group_id) = key;
+    let mut stmt = db.prepare("SELECT teacher_id FROM LessonTeachers 
+        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND semester = ?4 AND academic_year = ?5 AND week = ?6 AND group_id = ?7")?;
+    let iter = stmt.query_map([weekd.into(), class_id, lessonh, semester.into(), academic_year.into(), week.into(), group_id], 
+        |row| row.get::<usize, u16>(0))?;
+    iter.collect()
+}
+/// Lessons taking place at the same time as `lesson` that use one of
+/// `teachers`, classroom of `lesson` or have the same students
+fn get_slot_conflicts(lesson: &Lesson, teachers: &[u16], db: &rusqlite::Connection) -> Result<Vec<LessonConflict>, SQLiteError>{
+    let (weekd, class_id, classroom_id, _, _, lessonh, semester, academic_year, week, group_id) = *lesson;
+    // lessons clash only if they can take place in the same week,
+    // lessons of different groups of one class can take place at the same time
+    let mut stmt = db.prepare(&format!(
+        "SELECT class_id, classroom_id, teacher_id, group_id, co_teacher 
+        FROM {}
+        WHERE NOT (class_id = ?5 AND week = ?6 AND group_id = ?7)
+        AND weekday       = ?1
+        AND lesson_hour   = ?2
+        AND semester      = ?3
+        AND academic_year = ?4
+        AND (week = 0 OR ?6 = 0 OR week = ?6)", TEACHING)
+    )?;
+    let iter = stmt.query_map([weekd.into(), lessonh, semester.into(), academic_year.into(), class_id, week.into(), group_id], |row| {
+        Ok((row.get::<usize, u16>(0)?, row.get::<usize, u16>(1)?, row.get::<usize, u16>(2)?, row.get::<usize, u16>(3)?, row.get::<usize, bool>(4)?))
+    })?;
+    let mut to_return = vec![];
+    for row in iter{
+        let (other_class, other_classroom, other_teacher, other_group, co_teacher) = row?;
+        for kind in conflict_kinds((class_id, group_id, classroom_id), teachers, (other_class, other_group, other_classroom, other_teacher, co_teacher)){
+            to_return.push(LessonConflict{
+                kind,
+                weekday      : weekd,
+                lesson_hour  : lessonh,
+                class_id     : other_class,
+                classroom_id : other_classroom,
+                teacher_id   : other_teacher
+            });
+        }
+    }
+    Ok(to_return)
+}
+/// Resources that lesson of (class_id, group_id, classroom_id) taught by `teachers` shares with 
+/// other lesson (class_id, group_id, classroom_id, teacher_id, co_teacher) at the same time
+fn conflict_kinds(lesson: (u16, u16, u16), teachers: &[u16], other: (u16, u16, u16, u16, bool)) -> Vec<ConflictKind>{
+    let ((class_id, group_id, classroom_id), (other_class, other_group, other_classroom, other_teacher, co_teacher)) = (lesson, other);
+    let mut kinds = vec![];
+    if !co_teacher && other_class == class_id && (other_group == 0 || group_id == 0 || other_group == group_id){
+        kinds.push(ConflictKind::Class);
+    }
+    else{
+        if teachers.contains(&other_teacher){
+            kinds.push(ConflictKind::Teacher);
+        }
+        if !co_teacher && other_classroom == classroom_id{
+            kinds.push(ConflictKind::Classroom);
+        }
+    }
+    kinds
+}
+
+/// Lessons clashing with lesson of `class_id` (or its group) at `lesson_hour` on `date` once
+/// `lesson_override` is applied: other lessons of that day (with their own one-off changes
+/// and substitutions) that use its teacher or classroom or have the same students
+pub fn get_override_conflicts(lesson_override: &LessonOverride, db: &rusqlite::Connection) -> Result<Vec<LessonConflict>, SQLiteError>{
+    let (date, class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id) = *lesson_override;
+    if cancelled{
+        return Ok(vec![]);
+    }
+    let weekd = date.weekday().number_from_monday() as u8;
+    let mut stmt = db.prepare(&format!("
+    SELECT Lessons.class_id, Lessons.group_id, Lessons.lesson_hour, Lessons.co_teacher, COALESCE(LessonOverrides.cancelled, 0),
+        COALESCE(LessonOverrides.new_lesson_hour, Lessons.lesson_hour), COALESCE(LessonOverrides.classroom_id, Lessons.classroom_id),
+        CASE WHEN Lessons.co_teacher THEN Lessons.teacher_id 
+            ELSE COALESCE(Substitutions.substitute_teacher_id, LessonOverrides.teacher_id, Lessons.teacher_id) END,
+        Lessons.classroom_id, Lessons.teacher_id, Substitutions.substitute_teacher_id
+    FROM {} AS Lessons
+    JOIN Years     ON Lessons.academic_year = Years.academic_year
+    JOIN Semesters ON Lessons.semester      = Semesters.semester
+    LEFT JOIN LessonOverrides ON LessonOverrides.date = ?1 AND LessonOverrides.class_id = Lessons.class_id
+        AND LessonOverrides.lesson_hour = Lessons.lesson_hour AND LessonOverrides.group_id = Lessons.group_id
+    LEFT JOIN Substitutions ON Substitutions.date = ?1 AND Substitutions.class_id = Lessons.class_id
+        AND Substitutions.lesson_hour = Lessons.lesson_hour AND Substitutions.group_id = Lessons.group_id
+    WHERE Lessons.weekday = ?2 AND (Lessons.week = 0 OR Lessons.week = ?3)
+    AND Semesters.start_date < ?4 AND Semesters.end_date > ?4
+    AND Years.start_date     < ?4 AND Years.end_date     > ?4
+    ", TEACHING))?;
+    type DayLesson = (u16, u16, u16, bool, bool, u16, u16, u16, u16, u16, Option<u16>);
+    let lessons = stmt.query_map(rusqlite::params![date.to_string(), weekd, get_cycle_week(date, db)?, date_to_rfc3339(date)], |row| {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?, row.get(9)?, row.get(10)?))
+    })?.collect::<Result<Vec<DayLesson>, SQLiteError>>()?;
+    let (own, others) : (Vec<DayLesson>, Vec<DayLesson>) = lessons.into_iter()
+        .partition(|lesson| (lesson.0, lesson.1, lesson.2) == (class_id, group_id, lesson_hour));
+    // nothing to check if there is no lesson to change
+    let Some(&(.., lesson_classroom, lesson_teacher, substitute)) = own.iter().find(|lesson| !lesson.3) else {
+        return Ok(vec![]);
+    };
+    let hour      = new_lesson_hour.unwrap_or(lesson_hour);
+    let classroom = classroom_id.unwrap_or(lesson_classroom);
+    let mut teachers = own.iter().filter(|lesson| lesson.3).map(|lesson| lesson.7).collect::<Vec<u16>>();
+    teachers.push(substitute.or(teacher_id).unwrap_or(lesson_teacher));
+    let mut to_return = vec![];
+    for (other_class, other_group, _, co_teacher, other_cancelled, other_hour, other_classroom, other_teacher, ..) in others{
+        if other_cancelled || other_hour != hour{
+            continue;
+        }
+        for kind in conflict_kinds((class_id, group_id, classroom), &teachers, (other_class, other_group, other_classroom, other_teacher, co_teacher)){
+            to_return.push(LessonConflict{
+                kind,
+                weekday      : weekd,
+                lesson_hour  : hour,
+                class_id     : other_class,
+                classroom_id : other_classroom,
+                teacher_id   : other_teacher
+            });
+        }
+    }
+    Ok(to_return)
+}
+/// Saves one-off change of lesson only if changed lesson doesn't clash with
+/// other lessons of that day (see `get_override_conflicts`)
+pub fn insert_lesson_override(lesson_override: LessonOverride, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let conflicts = get_override_conflicts(&lesson_override, db).map_err(ServerError::DatabaseError)?;
+    if !conflicts.is_empty(){
+        return Err(ServerError::LessonConflict(conflicts));
+    }
+    manipulate_database(MainpulationType::Insert(POST::LessonOverride(Some(lesson_override))), db).map_err(ServerError::DatabaseError)
+}
+
+/// Lists every teacher and classroom collision that is
+/// already stored in `Lessons` for given semester and year
+pub fn get_collisions(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<Vec<Collision>, SQLiteError>{
+    let mut to_return = vec![];
+    // co-teachers are taken into account only in teacher collisions
+    for (kind, column, table, name_column, lessons) in [
+        (ConflictKind::Teacher  , "teacher_id"  , "Teachers"  , "teacher_name"  , TEACHING ),
+        (ConflictKind::Classroom, "classroom_id", "Classrooms", "classroom_name", "Lessons")
+    ]{
+        let mut stmt = db.prepare(&format!("
+        SELECT 
+            a.weekday, a.lesson_hour, a.{column}, r.{name_column},
+            a.class_id, ca.class_name, b.class_id, cb.class_name
+        FROM {lessons} a
+        JOIN {lessons} b  ON  a.weekday       = b.weekday
+                          AND a.lesson_hour   = b.lesson_hour
+                          AND a.semester      = b.semester
+                          AND a.academic_year = b.academic_year
+                          AND a.{column}      = b.{column}
+                          AND (a.class_id < b.class_id OR (a.class_id = b.class_id AND a.group_id < b.group_id))
+                          AND (a.week = 0 OR b.week = 0 OR a.week = b.week)
+        JOIN {table} r    ON  a.{column}      = r.{column}
+        JOIN Classes ca   ON  a.class_id      = ca.class_id
+        JOIN Classes cb   ON  b.class_id      = cb.class_id
+        WHERE a.semester = ?1 AND a.academic_year = ?2
+        "))?;
+        let iter = stmt.query_map([semester, academic_year], |row| {
+            Ok(
+                Collision{
+                    kind,
+                    weekday     : row.get(0)?,
+                    lesson_hour : row.get(1)?,
+                    resource    : (row.get(2)?, row.get(3)?),
+                    classes     : [(row.get(4)?, row.get(5)?), (row.get(6)?, row.get(7)?)]
+                }
+            )
+        })?;
+        for collision in iter{
+            to_return.push(collision?);
+        }
+    }
+    to_return.sort_by_key(|c| (c.weekday, c.lesson_hour));
+    Ok(to_return)
+}
+
+/// Inserts lesson only if teacher is allowed to teach its subject (see `is_qualified`), is available 
+/// at that time (see `is_available`) and it doesn't clash with any other lesson (see `get_lesson_conflicts`).
+/// Lesson in unsuitable classroom is inserted, but warnings (see `get_room_warnings`) are added to response
+pub fn insert_lesson(lesson: Lesson, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    insert_lesson_block(lesson, 1, db)
+}
+/// Inserts lesson covering `length` consecutive lesson hours starting at lesson hour of `lesson` 
+/// (e.g. double period), every hour is checked like in `insert_lesson` and nothing is inserted if any of them fails.
+/// Multi-slot lessons of the same class group that overlap new lesson are deleted with all their hours. Co-teachers 
+/// of replaced lessons are kept (and must be available), except for new main teacher
+pub fn insert_lesson_block(lesson: Lesson, length: u16, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekday, class_id, classroom_id, teacher_id, subject_id, first_hour, semester, academic_year, week, group_id) = lesson;
+    let last_hour = *block_hours(first_hour, length, db)?.end();
+    if !is_group_of_class(group_id, class_id, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::GroupNotInClass{group_id, class_id});
+    }
+    if !is_qualified(teacher_id, subject_id, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::NotQualified{teacher_id, subject_id});
+    }
+    let mut conflicts = vec![];
+    for lesson_hour in first_hour..=last_hour{
+        // co-teachers of replaced lesson stay with new one
+        let co_teachers = get_co_teacher_ids((weekday, class_id, lesson_hour, semester, academic_year, week, group_id), db)
+            .map_err(ServerError::DatabaseError)?;
+        for teacher_id in co_teachers.into_iter().filter(|t| *t != teacher_id).chain([teacher_id]){
+            if !is_available(teacher_id, weekday, lesson_hour, db).map_err(ServerError::DatabaseError)?{
+                return Err(ServerError::TeacherUnavailable{teacher_id, weekday, lesson_hour});
+            }
+        }
+        let lesson = (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id);
+        conflicts.extend(get_lesson_conflicts(&lesson, db).map_err(ServerError::DatabaseError)?);
+    }
+    if !conflicts.is_empty(){
+        return Err(ServerError::LessonConflict(conflicts));
+    }
+    let warnings = get_room_warnings(&lesson, db).map_err(ServerError::DatabaseError)?;
+    let key = (weekday, class_id, first_hour, semester, academic_year, week, group_id);
+    let tx = db.unchecked_transaction().map_err(ServerError::DatabaseError)?;
+    let (start, end) = get_block_range(key, last_hour, false, &tx).map_err(ServerError::DatabaseError)?;
+    if (start, end) != (first_hour, last_hour){
+        delete_lesson_hours((weekday, class_id, start, semester, academic_year, week, group_id), end, false, &tx).map_err(ServerError::DatabaseError)?;
+    }
+    // main teacher who was co-teacher of replaced lesson would be counted twice
+    tx.execute("DELETE FROM LessonTeachers 
+        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour BETWEEN ?3 AND ?4 AND semester = ?5 AND academic_year = ?6 
+        AND week = ?7 AND group_id = ?8 AND teacher_id = ?9",
+        rusqlite::params![weekday, class_id, first_hour, last_hour, semester, academic_year, week, group_id, teacher_id])
+        .map_err(ServerError::DatabaseError)?;
+    let mut response = String::new();
+    for lesson_hour in first_hour..=last_hour{
+        let lesson = (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id);
+        response = manipulate_database(MainpulationType::Insert(POST::Lesson(Some(lesson))), &tx).map_err(ServerError::DatabaseError)?;
+    }
+    if last_hour > first_hour{
+        tx.execute("INSERT INTO LessonBlocks (weekday, class_id, lesson_hour, last_hour, semester, academic_year, week, group_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (class_id, weekday, lesson_hour, semester, academic_year, week, group_id)
+            DO UPDATE SET last_hour = excluded.last_hour", 
+            rusqlite::params![weekday, class_id, first_hour, last_hour, semester, academic_year, week, group_id])
+            .map_err(ServerError::DatabaseError)?;
+    }
+    tx.commit().map_err(ServerError::DatabaseError)?;
+    if !warnings.is_empty(){
+        response.push_str(&format!("&AMOUNT={}", warnings.len()));
+        for (i, warning) in warnings.iter().enumerate(){
+            response.push_str(&warning.to_args(i));
+        }
+    }
+    Ok(response)
+}
+/// Inserts lesson covering `length` lesson hours into `DraftLessons` (and `DraftLessonBlocks`) if teacher is qualified 
+/// and available (like in `insert_lesson_block`). Clashes with other lessons are checked when draft is applied (see `apply_draft`)
+pub fn insert_draft_lesson(lesson: Lesson, length: u16, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekday, class_id, classroom_id, teacher_id, subject_id, first_hour, semester, academic_year, week, group_id) = lesson;
+    if !is_group_of_class(group_id, class_id, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::GroupNotInClass{group_id, class_id});
+    }
+    if !is_qualified(teacher_id, subject_id, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::NotQualified{teacher_id, subject_id});
+    }
+    let hours = block_hours(first_hour, length, db)?;
+    for lesson_hour in hours.clone(){
+        if !is_available(teacher_id, weekday, lesson_hour, db).map_err(ServerError::DatabaseError)?{
+            return Err(ServerError::TeacherUnavailable{teacher_id, weekday, lesson_hour});
+        }
+    }
+    let last_hour = *hours.end();
+    let tx = db.unchecked_transaction().map_err(ServerError::DatabaseError)?;
+    copy_class_to_draft(semester, academic_year, class_id, &tx).map_err(ServerError::DatabaseError)?;
+    let (start, end) = get_block_range((weekday, class_id, first_hour, semester, academic_year, week, group_id), last_hour, true, &tx)
+        .map_err(ServerError::DatabaseError)?;
+    if (start, end) != (first_hour, last_hour){
+        delete_lesson_hours((weekday, class_id, start, semester, academic_year, week, group_id), end, true, &tx).map_err(ServerError::DatabaseError)?;
+    }
+    tx.execute("DELETE FROM DraftLessonTeachers 
+        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour BETWEEN ?3 AND ?4 AND semester = ?5 AND academic_year = ?6 
+        AND week = ?7 AND group_id = ?8 AND teacher_id = ?9",
+        rusqlite::params![weekday, class_id, first_hour, last_hour, semester, academic_year, week, group_id, teacher_id])
+        .map_err(ServerError::DatabaseError)?;
+    for lesson_hour in hours{
+        let lesson = (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id);
+        manipulate_database(MainpulationType::Insert(POST::DraftLesson(Some(lesson))), &tx).map_err(ServerError::DatabaseError)?;
+    }
+    if last_hour > first_hour{
+        tx.execute("INSERT INTO DraftLessonBlocks (weekday, class_id, lesson_hour, last_hour, semester, academic_year, week, group_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (class_id, weekday, lesson_hour, semester, academic_year, week, group_id)
+            DO UPDATE SET last_hour = excluded.last_hour", 
+            rusqlite::params![weekday, class_id, first_hour, last_hour, semester, academic_year, week, group_id])
+            .map_err(ServerError::DatabaseError)?;
+    }
+    tx.commit().map_err(ServerError::DatabaseError)?;
+    Ok("msat/201-Created".to_string())
+}
+/// Semester has draft of timetable that isn't applied yet (see `apply_draft`), so changes
+/// of its lessons and duties should be saved into draft, otherwise they would be lost when draft is applied
+pub fn has_draft(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<bool, SQLiteError>{
+    db.query_row("SELECT EXISTS (SELECT 1 FROM DraftLessons WHERE semester = ?1 AND academic_year = ?2)
+        OR EXISTS (SELECT 1 FROM DraftDuties WHERE semester = ?1 AND academic_year = ?2)", [semester, academic_year], |row| row.get(0))
+}
+/// Lesson hours covered by lesson of `length` hours starting at `first_hour`,
+/// only if every one of them is in `LessonHours`
+fn block_hours(first_hour: u16, length: u16, db: &rusqlite::Connection) -> Result<std::ops::RangeInclusive<u16>, ServerError>{
+    let last_hour = first_hour.checked_add(length.max(1) - 1)
+        .ok_or_else(|| ServerError::InvalidRequest(format!("lesson_hour={}&length={}", first_hour, length)))?;
+    for lesson_hour in first_hour..=last_hour{
+        let exists : bool = db.query_row("SELECT EXISTS (SELECT 1 FROM LessonHours WHERE lesson_hour = ?1)", [lesson_hour], |row| row.get(0))
+            .map_err(ServerError::DatabaseError)?;
+        if !exists{
+            return Err(ServerError::NoLessonHour{lesson_hour});
+        }
+    }
+    Ok(first_hour..=last_hour)
+}
+/// Range of lesson hours from lesson hour of `key` to `last_hour` extended with every multi-slot 
+/// lesson of the same class group that overlaps it (in `DraftLessonBlocks` if `draft` is true)
+fn get_block_range(key: LessonKey, last_hour: u16, draft: bool, db: &rusqlite::Connection) -> Result<(u16, u16), SQLiteError>{
+    let (weekday, class_id, first_hour, semester, academic_year, week, group_id) = key;
+    let table = match draft{
+        false => "LessonBlocks",
+        true  => "DraftLessonBlocks"
+    };
+    let (start, end) : (Option<u16>, Option<u16>) = db.query_row(&format!("SELECT MIN(lesson_hour), MAX(last_hour) FROM {table}
+        WHERE class_id = ?1 AND weekday = ?2 AND semester = ?3 AND academic_year = ?4 AND week = ?5 AND group_id = ?6
+        AND lesson_hour <= ?8 AND last_hour >= ?7"), 
+        rusqlite::params![class_id, weekday, semester, academic_year, week, group_id, first_hour, last_hour], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok((start.map_or(first_hour, |start| start.min(first_hour)), end.map_or(last_hour, |end| end.max(last_hour))))
+}
+/// Deletes lessons (with co-teachers and multi-slot lessons) of class group 
+/// from lesson hour of `key` to `last_hour` (from draft tables if `draft` is true)
+fn delete_lesson_hours(key: LessonKey, last_hour: u16, draft: bool, db: &rusqlite::Connection) -> Result<(), SQLiteError>{
+    let (weekday, class_id, first_hour, semester, academic_year, week, group_id) = key;
+    let tables = match draft{
+        false => ["Lessons", "LessonTeachers", "LessonBlocks"],
+        true  => ["DraftLessons", "DraftLessonTeachers", "DraftLessonBlocks"]
+    };
+    for table in tables{
+        db.execute(&format!("DELETE FROM {table} 
+            WHERE class_id  = ?1 AND weekday = ?2 
+            AND lesson_hour BETWEEN ?3 AND ?8 AND semester = ?4
+            AND academic_year = ?5 AND week = ?6
+            AND group_id = ?7")
+            , rusqlite::params![class_id, weekday, first_hour, semester, academic_year, week, group_id, last_hour])?;
+    }
+    Ok(())
+}
+
+/// Problems with classroom of lesson: class has more students than classroom's capacity
+/// (checked only for lessons of whole class) or classroom lacks features required by subject
+pub fn get_room_warnings(lesson: &Lesson, db: &rusqlite::Connection) -> Result<Vec<RoomWarning>, SQLiteError>{
+    let (_, class_id, classroom_id, _, subject_id, _, _, _, _, group_id) = *lesson;
+    let mut to_return = vec![];
+    if group_id == 0{
+        let (capacity, students) : (Option<u16>, Option<u16>) = db.query_row("SELECT 
+            (SELECT capacity FROM Classrooms WHERE classroom_id = ?1), 
+            (SELECT students FROM Classes    WHERE class_id     = ?2)", [classroom_id, class_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        if let (Some(capacity), Some(students)) = (capacity, students){
+            if students > capacity{
+                to_return.push(RoomWarning::TooSmall{capacity, students});
+            }
+        }
+    }
+    let mut stmt = db.prepare("SELECT feature FROM SubjectFeatures WHERE subject_id = ?1
+        EXCEPT
+        SELECT feature FROM ClassroomFeatures WHERE classroom_id = ?2
+        ORDER BY feature")?;
+    for feature in stmt.query_map([subject_id, classroom_id], |row| row.get(0))?{
+        to_return.push(RoomWarning::MissingFeature(feature?));
+    }
+    Ok(to_return)
+}
+
+/// Classrooms without lesson in lesson hour of weekday as (classroom_id, classroom_name, capacity).
+/// `week` 0 means that classroom has to be free in every week of week cycle. Only classrooms 
+/// matching `filter` are returned
+pub fn get_free_classrooms(weekday: u8, lesson_hour: u16, semester: u8, academic_year: u8, week: u8, 
+    filter: &ClassroomFilter, db: &rusqlite::Connection) -> Result<Vec<(u16, String, Option<u16>)>, SQLiteError>
+{
+    let mut stmt = db.prepare("SELECT classroom_id FROM Lessons 
+        WHERE weekday = ?1 AND lesson_hour = ?2 AND semester = ?3 AND academic_year = ?4 AND (?5 = 0 OR week = 0 OR week = ?5)")?;
+    let used = stmt.query_map(rusqlite::params![weekday, lesson_hour, semester, academic_year, week], |row| row.get::<usize, u16>(0))?
+        .collect::<Result<Vec<u16>, SQLiteError>>()?;
+    Ok(get_matching_classrooms(filter, db)?.into_iter().filter(|(classroom_id, _, _)| !used.contains(classroom_id)).collect())
+}
+// Classrooms that meet requirements from `filter`
+fn get_matching_classrooms(filter: &ClassroomFilter, db: &rusqlite::Connection) -> Result<Vec<(u16, String, Option<u16>)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT classroom_id, classroom_name, capacity 
+        FROM Classrooms 
+        WHERE ?1 IS NULL OR capacity >= ?1
+        ORDER BY classroom_id")?;
+    let mut to_return = vec![];
+    for classroom in stmt.query_map([filter.min_capacity], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?{
+        let classroom : (u16, String, Option<u16>) = classroom?;
+        let equipment = get_classroom_features(classroom.0, db)?;
+        if filter.features.iter().all(|feature| equipment.contains(feature)){
+            to_return.push(classroom);
+        }
+    }
+    Ok(to_return)
+}
+
+/// Classrooms matching `filter` that are free during lesson hour taking place at `moment`, as
+/// (classroom_id, classroom_name, capacity). Unlike `get_free_classrooms` it uses schedule of that
+/// date (see `get_room_schedule`), so lessons with one-off changes, bookings and events count too and
+/// classroom is free only if none of them overlaps any part of lesson hour. Returns `QueryReturnedNoRows`
+/// if there is no lesson hour or semester at that moment
+pub fn get_free_classrooms_at(moment: NaiveDateTime, filter: &ClassroomFilter, db: &rusqlite::Connection) -> Result<Vec<(u16, String, Option<u16>)>, SQLiteError>{
+    let date = moment.date();
+    get_year_and_semester(date, db)?;
+    let now = (moment.hour() * 60 + moment.minute()) as u16;
+    let (start, end) = get_lesson_hours_for_date(date, db)?.iter()
+        .filter_map(|hour| match (hour.start_hour, hour.start_minute, hour.end_hour, hour.end_minutes){
+            (Some(sh), Some(sm), Some(eh), Some(em)) => Some((sh as u16 * 60 + sm as u16, eh as u16 * 60 + em as u16)),
+            _ => None
+        })
+        .find(|(start, end)| *start <= now && now < *end)
+        .ok_or(SQLiteError::QueryReturnedNoRows)?;
+    let mut to_return = vec![];
+    for classroom in get_matching_classrooms(filter, db)?{
+        if get_room_schedule(classroom.0, date, db)?.iter().all(|entry| entry.end() <= start || end <= entry.start()){
+            to_return.push(classroom);
+        }
+    }
+    Ok(to_return)
+}
+
+/// Lessons (without cancelled ones) and bookings of classroom on `date` sorted by start time
+pub fn get_room_schedule(classroom_id: u16, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<RoomEntry>, SQLiteError>{
+    let mut to_return = vec![];
+    if is_school_day(date, db)?{
+        for lesson in get_lessons_by_classroom_id(classroom_id, date, db)?{
+            if lesson.date != Some(date.to_string()) || lesson.status == LessonStatus::Cancelled{
+                continue;
+            }
+            if let (Some(start_hour), Some(start_minute), Some(end_hour), Some(end_minute)) = 
+            (lesson.lessonh.start_hour, lesson.lessonh.start_minute, lesson.lessonh.end_hour, lesson.lessonh.end_minutes)
+            {
+                let title = match &lesson.group{
+                    Some(group) => format!("{} - {} ({})", lesson.subject.unwrap_or_default(), lesson.class.unwrap_or_default(), group),
+                    None        => format!("{} - {}", lesson.subject.unwrap_or_default(), lesson.class.unwrap_or_default())
+                };
+                to_return.push(RoomEntry{
+                    booking_id : None,
+                    start_hour, start_minute, end_hour, end_minute,
+                    owner      : lesson.teacher.unwrap_or_default(),
+                    title
+                });
+            }
+        }
+    }
+    let mut stmt = db.prepare("SELECT Bookings.booking_id, Bookings.start_hour, Bookings.start_minutes, Bookings.end_hour, Bookings.end_minutes, 
+        Teachers.teacher_name, Bookings.purpose
+        FROM Bookings
+        JOIN Teachers ON Bookings.teacher_id = Teachers.teacher_id
+        WHERE Bookings.classroom_id = ?1 AND Bookings.date = ?2")?;
+    for booking in stmt.query_map(rusqlite::params![classroom_id, date.to_string()], |row| {
+        Ok(
+            RoomEntry{
+                booking_id   : row.get(0)?,
+                start_hour   : row.get(1)?,
+                start_minute : row.get(2)?,
+                end_hour     : row.get(3)?,
+                end_minute   : row.get(4)?,
+                owner        : row.get(5)?,
+                title        : row.get(6)?
+            }
+        )
+    })?{
+        to_return.push(booking?);
+    }
+    let mut stmt = db.prepare("SELECT Events.start_hour, Events.start_minutes, Events.end_hour, Events.end_minutes, 
+        (SELECT GROUP_CONCAT(Teachers.teacher_name, ', ') FROM EventTeachers 
+            JOIN Teachers ON EventTeachers.teacher_id = Teachers.teacher_id 
+            WHERE EventTeachers.event_id = Events.event_id), 
+        Events.name
+        FROM Events
+        JOIN EventClassrooms ON EventClassrooms.event_id = Events.event_id
+        WHERE EventClassrooms.classroom_id = ?1 AND Events.date = ?2")?;
+    for event in stmt.query_map(rusqlite::params![classroom_id, date.to_string()], |row| {
+        Ok(
+            RoomEntry{
+                booking_id   : None,
+                start_hour   : row.get(0)?,
+                start_minute : row.get(1)?,
+                end_hour     : row.get(2)?,
+                end_minute   : row.get(3)?,
+                owner        : row.get::<usize, Option<String>>(4)?.unwrap_or_default(),
+                title        : row.get(5)?
+            }
+        )
+    })?{
+        to_return.push(event?);
+    }
+    to_return.sort_by_key(|entry| (entry.start_hour, entry.start_minute));
+    Ok(to_return)
+}
+
+/// Every bell schedule: (schedule_id, name)
+pub fn get_bell_schedules(db: &rusqlite::Connection) -> Result<Vec<(u16, String)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT schedule_id, name FROM BellSchedules ORDER BY schedule_id")?;
+    let iter = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    iter.collect()
+}
+/// Bell schedule used on `date` or None if regular times are used
+pub fn get_bell_schedule_for_date(date: NaiveDate, db: &rusqlite::Connection) -> Result<Option<(u16, String)>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT BellSchedules.schedule_id, BellSchedules.name 
+        FROM ScheduleDays 
+        JOIN BellSchedules ON ScheduleDays.schedule_id = BellSchedules.schedule_id 
+        WHERE ScheduleDays.date = ?1")?;
+    let mut iter = stmt.query_map([date.to_string()], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    iter.next().transpose()
+}
+/// Lesson hours with times used on `date`
+pub fn get_lesson_hours_for_date(date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedHour>, SQLiteError>{
+    get_bell_times(false, date, db)
+}
+/// Breaks with times used on `date`, break number is stored in `lesson_hour`
+pub fn get_breaks_for_date(date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedHour>, SQLiteError>{
+    get_bell_times(true, date, db)
+}
+fn get_bell_times(breaks: bool, date: NaiveDate, db: &rusqlite::Connection) -> Result<Vec<JoinedHour>, SQLiteError>{
+    let (table, column) = if breaks {(DATED_BREAKS, "Breaks.break_num")} else {(DATED_LESSON_HOURS, "LessonHours.lesson_hour")};
+    let mut stmt = db.prepare(&format!("SELECT {column}, start_hour, start_minutes, end_hour, end_minutes 
+        FROM {table}
+        WHERE {}
+        ORDER BY {column}", bell_times_on(breaks, column, "?1")))?;
+    let iter = stmt.query_map([date.to_string()], |row| {
+        Ok(
+            JoinedHour{
+                lesson_hour  : row.get(0)?,
+                start_hour   : row.get(1)?,
+                start_minute : row.get(2)?,
+                end_hour     : row.get(3)?,
+                end_minutes  : row.get(4)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+
+/// Events of `owner` (or every event if None) on `date` sorted by start time
+pub fn get_events(date: NaiveDate, owner: Option<TimetableOwner>, db: &rusqlite::Connection) -> Result<Vec<SchoolEvent>, SQLiteError>{
+    let (filter, id) = match owner{
+        Some(TimetableOwner::Teacher(teacher_id))     => 
+            ("EXISTS (SELECT 1 FROM EventTeachers WHERE EventTeachers.event_id = Events.event_id AND EventTeachers.teacher_id = ?2)", teacher_id),
+        Some(TimetableOwner::Class(class_id))         => 
+            ("EXISTS (SELECT 1 FROM EventClasses WHERE EventClasses.event_id = Events.event_id AND EventClasses.class_id = ?2)", class_id),
+        Some(TimetableOwner::Classroom(classroom_id)) => 
+            ("EXISTS (SELECT 1 FROM EventClassrooms WHERE EventClassrooms.event_id = Events.event_id AND EventClassrooms.classroom_id = ?2)", classroom_id),
+        None => ("?2 = 0", 0)
+    };
+    let mut stmt = db.prepare(&format!("SELECT Events.event_id, Events.name, Events.event_type, Events.date, 
+        Events.start_hour, Events.start_minutes, Events.end_hour, Events.end_minutes, Events.overrides_lessons,
+        (SELECT GROUP_CONCAT(Classes.class_name, ', ') FROM EventClasses 
+            JOIN Classes ON EventClasses.class_id = Classes.class_id 
+            WHERE EventClasses.event_id = Events.event_id),
+        (SELECT GROUP_CONCAT(Teachers.teacher_name, ', ') FROM EventTeachers 
+            JOIN Teachers ON EventTeachers.teacher_id = Teachers.teacher_id 
+            WHERE EventTeachers.event_id = Events.event_id),
+        (SELECT GROUP_CONCAT(Classrooms.classroom_name, ', ') FROM EventClassrooms 
+            JOIN Classrooms ON EventClassrooms.classroom_id = Classrooms.classroom_id 
+            WHERE EventClassrooms.event_id = Events.event_id)
+        FROM Events
+        WHERE Events.date = ?1 AND {}
+        ORDER BY Events.start_hour, Events.start_minutes, Events.event_id", filter))?;
+    let iter = stmt.query_map(rusqlite::params![date.to_string(), id], |row| {
+        let event_type : String = row.get(2)?;
+        Ok(
+            SchoolEvent{
+                event_id          : row.get(0)?,
+                name              : row.get(1)?,
+                event_type        : EventType::parse(&event_type).unwrap_or(EventType::Other),
+                date              : row.get(3)?,
+                start_hour        : row.get(4)?,
+                start_minute      : row.get(5)?,
+                end_hour          : row.get(6)?,
+                end_minute        : row.get(7)?,
+                overrides_lessons : row.get(8)?,
+                classes           : row.get(9)?,
+                teachers          : row.get(10)?,
+                classrooms        : row.get(11)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+
+/// Lessons of `owner` on `date` (overridden ones are marked as cancelled) and its events.
+/// Lessons are returned only on school days, events on every day
+pub fn get_day_schedule(owner: TimetableOwner, date: NaiveDate, db: &rusqlite::Connection) -> Result<DaySchedule, SQLiteError>{
+    let mut lessons = vec![];
+    if is_school_day(date, db)?{
+        let week = match owner{
+            TimetableOwner::Teacher(teacher_id)     => get_lessons_by_teacher_id(teacher_id, date, db)?,
+            TimetableOwner::Class(class_id)         => get_lessons_by_class_id(class_id, date, db)?,
+            TimetableOwner::Classroom(classroom_id) => get_lessons_by_classroom_id(classroom_id, date, db)?
+        };
+        lessons = week.into_iter().filter(|lesson| lesson.date == Some(date.to_string())).collect();
+    }
+    Ok(DaySchedule{lessons, events: get_events(date, Some(owner), db)?})
+}
+
+/// Saves booking only if classroom isn't used by lesson or other booking at that time
+pub fn insert_booking(booking: Booking, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (booking_id, classroom_id, date, start_hour, start_minute, end_hour, end_minute, ..) = booking;
+    let (start, end) = (start_hour as u16 * 60 + start_minute as u16, end_hour as u16 * 60 + end_minute as u16);
+    let conflicts = get_room_schedule(classroom_id, date, db).map_err(ServerError::DatabaseError)?
+        .into_iter()
+        .filter(|entry| entry.booking_id != Some(booking_id) && entry.start() < end && start < entry.end())
+        .collect::<Vec<RoomEntry>>();
+    if !conflicts.is_empty(){
+        return Err(ServerError::RoomConflict(conflicts));
+    }
+    manipulate_database(MainpulationType::Insert(POST::Booking(Some(booking))), db).map_err(ServerError::DatabaseError)
+}
+
+/// Features of classroom
+pub fn get_classroom_features(classroom_id: u16, db: &rusqlite::Connection) -> Result<Vec<String>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT feature FROM ClassroomFeatures WHERE classroom_id = ?1 ORDER BY feature")?;
+    let iter = stmt.query_map([classroom_id], |row| row.get(0))?;
+    iter.collect()
+}
+
+/// Features that classroom needs to have for lessons of subject
+pub fn get_subject_features(subject_id: u16, db: &rusqlite::Connection) -> Result<Vec<String>, SQLiteError>{
+    let mut stmt = db.prepare("SELECT feature FROM SubjectFeatures WHERE subject_id = ?1 ORDER BY feature")?;
+    let iter = stmt.query_map([subject_id], |row| row.get(0))?;
+    iter.collect()
+}
+
+/// Lessons and duties per week of every teacher in semester (if semester doesn't exist, 
+/// its lessons are counted as if they took place every week)
+pub fn get_teacher_workload(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<Vec<TeacherWorkload>, SQLiteError>{
+    let mut stmt = db.prepare(&format!("
+    SELECT Teachers.teacher_id, Teachers.teacher_name, Teachers.pensum,
+        (SELECT TOTAL(CASE WHEN Lessons.week = 0 THEN 1.0 ELSE 1.0 / COALESCE(Semesters.week_cycle, 1) END) 
+            FROM {} AS Lessons
+            WHERE Lessons.teacher_id = Teachers.teacher_id AND Lessons.semester = ?1 AND Lessons.academic_year = ?2),
+        (SELECT TOTAL(CASE WHEN Duties.week = 0 THEN 1.0 ELSE 1.0 / COALESCE(Semesters.week_cycle, 1) END) 
+            FROM Duties
+            WHERE Duties.teacher_id = Teachers.teacher_id AND Duties.semester = ?1 AND Duties.academic_year = ?2)
+    FROM Teachers
+    LEFT JOIN Semesters ON Semesters.semester = ?1
+    ORDER BY Teachers.teacher_id
+    ", TEACHING))?;
+    let iter = stmt.query_map([semester, academic_year], |row| {
+        Ok(
+            TeacherWorkload{
+                teacher_id   : row.get(0)?,
+                teacher_name : row.get(1)?,
+                pensum       : row.get(2)?,
+                lessons      : row.get(3)?,
+                duties       : row.get(4)?
+            }
+        )
+    })?;
+    iter.collect()
+}
+/// Requirements of classes from `Requirements` compared with `Lessons` in semester (optionally only 
+/// for one class). Subjects that are taught, but aren't required are listed too
+pub fn get_curriculum_compliance(semester: u8, academic_year: u8, class_id: Option<u16>, db: &rusqlite::Connection) -> Result<Vec<CurriculumCompliance>, SQLiteError>{
+    let mut compliance : BTreeMap<(u16, u16), CurriculumCompliance> = BTreeMap::new();
+    let mut stmt = db.prepare("
+    SELECT Requirements.class_id, Classes.class_name, Requirements.subject_id, Subjects.subject_name, Requirements.hours
+    FROM Requirements
+    JOIN Classes  ON Requirements.class_id   = Classes.class_id
+    JOIN Subjects ON Requirements.subject_id = Subjects.subject_id
+    WHERE Requirements.semester = ?1 AND Requirements.academic_year = ?2
+    AND (?3 IS NULL OR Requirements.class_id = ?3)
+    ")?;
+    let iter = stmt.query_map(rusqlite::params![semester, academic_year, class_id], |row| {
+        Ok(
+            CurriculumCompliance{
+                class_id   : row.get(0)?,
+                class      : row.get(1)?,
+                subject_id : row.get(2)?,
+                subject    : row.get(3)?,
+                required   : row.get(4)?,
+                scheduled  : 0.0
+            }
+        )
+    })?;
+    for requirement in iter{
+        let requirement = requirement?;
+        compliance.insert((requirement.class_id, requirement.subject_id), requirement);
+    }
+    let mut stmt = db.prepare("
+    SELECT Lessons.class_id, Classes.class_name, Lessons.subject_id, Subjects.subject_name, Lessons.group_id,
+        TOTAL(CASE WHEN Lessons.week = 0 THEN 1.0 ELSE 1.0 / Semesters.week_cycle END)
+    FROM Lessons
+    JOIN Classes   ON Lessons.class_id   = Classes.class_id
+    JOIN Subjects  ON Lessons.subject_id = Subjects.subject_id
+    JOIN Semesters ON Lessons.semester   = Semesters.semester
+    WHERE Lessons.semester = ?1 AND Lessons.academic_year = ?2
+    AND (?3 IS NULL OR Lessons.class_id = ?3)
+    GROUP BY Lessons.class_id, Lessons.subject_id, Lessons.group_id
+    ")?;
+    let iter = stmt.query_map(rusqlite::params![semester, academic_year, class_id], |row| {
+        Ok((row.get::<usize, u16>(0)?, row.get::<usize, String>(1)?, row.get::<usize, u16>(2)?, row.get::<usize, String>(3)?, 
+            row.get::<usize, u16>(4)?, row.get::<usize, f64>(5)?))
+    })?;
+    // (whole class, group with the most lessons)
+    let mut scheduled : HashMap<(u16, u16), (f64, f64)> = HashMap::new();
+    for row in iter{
+        let (class_id, class, subject_id, subject, group_id, hours) = row?;
+        compliance.entry((class_id, subject_id)).or_insert(CurriculumCompliance{
+            class_id, class, subject_id, subject, required: None, scheduled: 0.0
+        });
+        let (whole_class, group) = scheduled.entry((class_id, subject_id)).or_default();
+        if group_id == 0{
+            *whole_class = hours;
+        }
+        else{
+            *group = group.max(hours);
+        }
+    }
+    for (key, (whole_class, group)) in scheduled{
+        if let Some(entry) = compliance.get_mut(&key){
+            entry.scheduled = whole_class + group;
+        }
+    }
+    Ok(compliance.into_values().collect())
+}
+
+/// Workload report as CSV (with header row)
+pub fn workload_to_csv(workload: &[TeacherWorkload]) -> String{
+    let mut to_return = "teacher_id,teacher_name,lessons,duties,pensum,status\r\n".to_string();
+    for teacher in workload{
+        to_return.push_str(&format!("{},{},{},{},{},{}\r\n", 
+            teacher.teacher_id, csv_field(&teacher.teacher_name), teacher.lessons, teacher.duties,
+            teacher.pensum.map(|pensum| pensum.to_string()).unwrap_or_default(), teacher.status().as_str()));
+    }
+    to_return
+}
+/// Quotes CSV field if it contains separator, quote or new line
+fn csv_field(field: &str) -> String{
+    if field.contains([',', '"', '\n', '\r']){
+        format!("\"{}\"", field.replace('"', "\"\""))
+    }
+    else{
+        field.to_string()
+    }
+}
+
+/// Adds `teacher_id` as co-teacher of lesson, only if teacher is available, 
+/// doesn't have other lesson at that time and isn't main teacher of lesson
+pub fn insert_co_teacher(key: LessonKey, teacher_id: u16, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekd, class_id, lessonh, semester, academic_year, week, group_id) = key;
+    if !is_available(teacher_id, weekd, lessonh, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::TeacherUnavailable{teacher_id, weekday: weekd, lesson_hour: lessonh});
+    }
+    let lesson : Lesson = db.query_row("SELECT classroom_id, teacher_id, subject_id FROM Lessons 
+        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND semester = ?4 AND academic_year = ?5 AND week = ?6 AND group_id = ?7",
+        [weekd.into(), class_id, lessonh, semester.into(), academic_year.into(), week.into(), group_id], |row| {
+            Ok((weekd, class_id, row.get(0)?, row.get(1)?, row.get(2)?, lessonh, semester, academic_year, week, group_id))
+        }).map_err(ServerError::DatabaseError)?;
+    let (_, _, classroom_id, main_teacher, ..) = lesson;
+    if main_teacher == teacher_id{
+        return Err(ServerError::LessonConflict(vec![LessonConflict{kind: ConflictKind::Teacher, weekday: weekd, lesson_hour: lessonh, class_id, classroom_id, teacher_id}]));
+    }
+    let conflicts = get_slot_conflicts(&lesson, &[teacher_id], db).map_err(ServerError::DatabaseError)?
+        .into_iter()
+        .filter(|conflict| conflict.kind == ConflictKind::Teacher)
+        .collect::<Vec<LessonConflict>>();
+    if !conflicts.is_empty(){
+        return Err(ServerError::LessonConflict(conflicts));
+    }
+    manipulate_database(MainpulationType::Insert(POST::CoTeacher(Some((key, teacher_id)))), db).map_err(ServerError::DatabaseError)
+}
+/// Adds co-teacher to lesson of `DraftLessons` if teacher is available and isn't main teacher 
+/// of that lesson. Clashes with other lessons are checked when draft is applied (see `apply_draft`)
+pub fn insert_draft_co_teacher(key: LessonKey, teacher_id: u16, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekd, class_id, lessonh, semester, academic_year, week, group_id) = key;
+    if !is_available(teacher_id, weekd, lessonh, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::TeacherUnavailable{teacher_id, weekday: weekd, lesson_hour: lessonh});
+    }
+    let tx = db.unchecked_transaction().map_err(ServerError::DatabaseError)?;
+    copy_class_to_draft(semester, academic_year, class_id, &tx).map_err(ServerError::DatabaseError)?;
+    let (classroom_id, main_teacher) : (u16, u16) = tx.query_row("SELECT classroom_id, teacher_id FROM DraftLessons 
+        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND semester = ?4 AND academic_year = ?5 AND week = ?6 AND group_id = ?7",
+        [weekd.into(), class_id, lessonh, semester.into(), academic_year.into(), week.into(), group_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(ServerError::DatabaseError)?;
+    if main_teacher == teacher_id{
+        return Err(ServerError::LessonConflict(vec![LessonConflict{kind: ConflictKind::Teacher, weekday: weekd, lesson_hour: lessonh, class_id, classroom_id, teacher_id}]));
+    }
+    let response = manipulate_database(MainpulationType::Insert(POST::DraftCoTeacher(Some((key, teacher_id)))), &tx).map_err(ServerError::DatabaseError)?;
+    tx.commit().map_err(ServerError::DatabaseError)?;
+    Ok(response)
+}
+
+/// Builds timetable for every class that has `Requirements` in given semester
+/// and saves it into `DraftLessons` (replacing draft of these classes). Lessons
+/// of other classes (in draft or published) are left as they are and teachers 
+/// aren't given lessons in lesson hours marked as unavailable
+pub fn generate_draft(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<DraftReport, SQLiteError>{
+    let mut problem = TimetableProblem{
+        weekdays : SCHOOL_DAYS.to_vec(),
+        ..Default::default()
+    };
+    let mut stmt = db.prepare("SELECT class_id, subject_id, teacher_id, classroom_id, hours 
+        FROM Requirements
+        WHERE semester = ?1 AND academic_year = ?2
+        ORDER BY class_id, subject_id")?;
+    for requirement in stmt.query_map([semester, academic_year], |row| {
+        Ok(
+            Requirement{
+                class_id     : row.get(0)?,
+                subject_id   : row.get(1)?,
+                teacher_id   : row.get(2)?,
+                classroom_id : row.get(3)?,
+                hours        : row.get(4)?
+            }
+        )
+    })?{
+        problem.requirements.push(requirement?);
+    }
+    let mut stmt = db.prepare("SELECT lesson_hour FROM LessonHours ORDER BY lesson_hour")?;
+    for lesson_hour in stmt.query_map([], |row| row.get(0))?{
+        problem.lesson_hours.push(lesson_hour?);
+    }
+    let mut stmt = db.prepare("SELECT classroom_id FROM Classrooms ORDER BY classroom_id")?;
+    for classroom in stmt.query_map([], |row| row.get(0))?{
+        problem.classrooms.push(classroom?);
+    }
+    // lessons of other classes (from draft if their class is in it) with co-teachers
+    let mut stmt = db.prepare(&format!("SELECT weekday, lesson_hour, teacher_id, classroom_id 
+        FROM {} AS Teaching
+        WHERE semester = ?1 AND academic_year = ?2
+        AND class_id NOT IN (SELECT class_id FROM Requirements WHERE semester = ?1 AND academic_year = ?2)", draft_teaching()))?;
+    for fixed in stmt.query_map([semester, academic_year], |row| {
+        Ok(
+            FixedLesson{
+                weekday      : row.get(0)?,
+                lesson_hour  : row.get(1)?,
+                teacher_id   : row.get(2)?,
+                classroom_id : row.get(3)?
+            }
+        )
+    })?{
+        problem.fixed.push(fixed?);
+    }
+    let mut stmt = db.prepare("SELECT teacher_id, weekday, lesson_hour FROM Availability WHERE availability = 'unavailable'")?;
+    for slot in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?{
+        problem.unavailable.push(slot?);
+    }
+
+    let solution = generator::solve_timetable(&problem);
+
+    let tx = db.unchecked_transaction()?;
+    for table in ["DraftLessons", "DraftLessonTeachers", "DraftLessonBlocks"]{
+        tx.execute(&format!("DELETE FROM {table} WHERE semester = ?1 AND academic_year = ?2
+            AND class_id IN (SELECT class_id FROM Requirements WHERE semester = ?1 AND academic_year = ?2)"), [semester, academic_year])?;
+    }
+    for placement in &solution.placements{
+        let requirement = &problem.requirements[placement.requirement];
+        tx.execute("INSERT INTO DraftLessons (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)", 
+            [placement.weekday.into(), requirement.class_id, placement.classroom_id, requirement.teacher_id, 
+            requirement.subject_id, placement.lesson_hour, semester.into(), academic_year.into()])?;
+    }
+    tx.commit()?;
+    Ok(
+        DraftReport{
+            placed   : solution.placements.len(), 
+            unplaced : solution.unplaced.into_iter().map(|(i, hours)| (problem.requirements[i], hours)).collect()
+        }
+    )
+}
+
+/// Lessons from `DraftLessons`, optionally only for one class
+pub fn get_draft(semester: u8, academic_year: u8, class_id: Option<u16>, db: &rusqlite::Connection) -> Result<Vec<JoinedLesson>, SQLiteError>{
+    let mut stmt = db.prepare("
+    SELECT 
+        DraftLessons.weekday, Teachers.teacher_name, Classes.class_name, Classrooms.classroom_name, Subjects.subject_name,
+        LessonHours.lesson_hour, LessonHours.start_hour, LessonHours.start_minutes, LessonHours.end_hour, LessonHours.end_minutes,
+        ClassGroups.group_name,
+        (SELECT GROUP_CONCAT(CoTeachers.teacher_name, ', ') 
+            FROM DraftLessonTeachers AS Draft
+            JOIN Teachers AS CoTeachers ON Draft.teacher_id = CoTeachers.teacher_id 
+            WHERE Draft.weekday = DraftLessons.weekday AND Draft.class_id = DraftLessons.class_id 
+            AND Draft.lesson_hour = DraftLessons.lesson_hour AND Draft.semester = DraftLessons.semester 
+            AND Draft.academic_year = DraftLessons.academic_year AND Draft.week = DraftLessons.week AND Draft.group_id = DraftLessons.group_id),
+        DraftLessonBlocks.lesson_hour, DraftLessonBlocks.last_hour
+    FROM DraftLessons
+    JOIN Classrooms  ON DraftLessons.classroom_id = Classrooms.classroom_id 
+    JOIN Teachers    ON DraftLessons.teacher_id   = Teachers.teacher_id
+    JOIN Classes     ON DraftLessons.class_id     = Classes.class_id
+    JOIN Subjects    ON DraftLessons.subject_id   = Subjects.subject_id
+    JOIN LessonHours ON DraftLessons.lesson_hour  = LessonHours.lesson_hour
+    LEFT JOIN ClassGroups ON DraftLessons.group_id = ClassGroups.group_id
+    LEFT JOIN DraftLessonBlocks ON DraftLessonBlocks.class_id = DraftLessons.class_id AND DraftLessonBlocks.weekday = DraftLessons.weekday
+        AND DraftLessonBlocks.semester = DraftLessons.semester AND DraftLessonBlocks.academic_year = DraftLessons.academic_year
+        AND DraftLessonBlocks.week = DraftLessons.week AND DraftLessonBlocks.group_id = DraftLessons.group_id
+        AND DraftLessons.lesson_hour BETWEEN DraftLessonBlocks.lesson_hour AND DraftLessonBlocks.last_hour
+    WHERE DraftLessons.semester = ?1 AND DraftLessons.academic_year = ?2
+    AND (?3 IS NULL OR DraftLessons.class_id = ?3)
+    ORDER BY Classes.class_name, DraftLessons.weekday, DraftLessons.lesson_hour, DraftLessons.group_id
+    ")?;
+    let iter = stmt.query_map(rusqlite::params![semester, academic_year, class_id], |row| {
+        Ok(
+            JoinedLesson{
+                weekday   : row.get(0).ok(),
+                teacher   : row.get(1).ok(),
+                class     : row.get(2).ok(),
+                classroom : row.get(3).ok(),
+                subject   : row.get(4).ok(),
+                lessonh   : JoinedHour{
+                    lesson_hour  : row.get(5).ok(),
+                    start_hour   : row.get(6).ok(),
+                    start_minute : row.get(7).ok(),
+                    end_hour     : row.get(8).ok(),
+                    end_minutes  : row.get(9).ok()
+                },
+                academic_year : None,
+                semester      : None,
+                date          : None,
+                status        : LessonStatus::Regular,
+                group         : row.get(10)?,
+                co_teachers   : row.get(11)?,
+                block         : row.get::<usize, Option<u16>>(12)?.zip(row.get(13)?)
+            }
+        )
+    })?;
+    let mut to_return = vec![];
+    for joined_lesson in iter.flatten(){
+        to_return.push(joined_lesson);
+    }
+    Ok(to_return)
+}
+
+/// Copies lessons (with co-teachers and multi-slot lessons) and duties of semester into draft tables (replacing 
+/// previous draft), so timetable can be changed without showing unfinished changes to students and teachers
+pub fn copy_to_draft(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<String, SQLiteError>{
+    let tx = db.unchecked_transaction()?;
+    for (draft, table, columns) in DRAFT_TABLES.iter().chain([&DRAFT_DUTIES]){
+        tx.execute(&format!("DELETE FROM {draft} WHERE semester = ?1 AND academic_year = ?2"), [semester, academic_year])?;
+        tx.execute(&format!("INSERT INTO {draft} ({columns}) SELECT {columns} FROM {table} WHERE semester = ?1 AND academic_year = ?2"), 
+            [semester, academic_year])?;
+    }
+    tx.commit()?;
+    Ok("msat/201-Created".to_string())
+}
+
+// (draft table, published table, columns) of lessons, their co-teachers and multi-slot lessons
+const DRAFT_TABLES : [(&str, &str, &str); 3] = [
+    ("DraftLessons", "Lessons", "weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id"),
+    ("DraftLessonTeachers", "LessonTeachers", "weekday, class_id, lesson_hour, semester, academic_year, week, group_id, teacher_id"),
+    ("DraftLessonBlocks", "LessonBlocks", "weekday, class_id, lesson_hour, last_hour, semester, academic_year, week, group_id")
+];
+const DRAFT_DUTIES : (&str, &str, &str) = ("DraftDuties", "Duties", "weekday, break_num, teacher_id, place_id, semester, academic_year, week");
+
+/// Copies published lessons of class into draft if draft has no lessons of that class yet. Applying draft replaces 
+/// every lesson of class in draft (see `apply_draft`), so first change of class in draft starts from its published lessons
+fn copy_class_to_draft(semester: u8, academic_year: u8, class_id: u16, db: &rusqlite::Connection) -> Result<(), SQLiteError>{
+    if db.prepare("SELECT 1 FROM DraftLessons WHERE semester = ?1 AND academic_year = ?2 AND class_id = ?3")?
+        .exists(rusqlite::params![semester, academic_year, class_id])?
+    {
+        return Ok(());
+    }
+    for (draft, table, columns) in DRAFT_TABLES{
+        db.execute(&format!("INSERT INTO {draft} ({columns}) SELECT {columns} FROM {table} 
+            WHERE semester = ?1 AND academic_year = ?2 AND class_id = ?3"), rusqlite::params![semester, academic_year, class_id])?;
+    }
+    Ok(())
+}
+/// Copies published duties of semester into draft if draft has no duties yet (like `copy_class_to_draft`)
+fn copy_duties_to_draft(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<(), SQLiteError>{
+    let (draft, table, columns) = DRAFT_DUTIES;
+    if db.prepare(&format!("SELECT 1 FROM {draft} WHERE semester = ?1 AND academic_year = ?2"))?.exists([semester, academic_year])?{
+        return Ok(());
+    }
+    db.execute(&format!("INSERT INTO {draft} ({columns}) SELECT {columns} FROM {table} WHERE semester = ?1 AND academic_year = ?2"), 
+        [semester, academic_year])?;
+    Ok(())
+}
+
+/// Lessons and duties that would be added, removed or changed by applying draft of semester (see `apply_draft`).
+/// Only lessons of classes in draft are compared and duties are compared only if draft has any
+pub fn get_draft_changes(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<DraftChanges, SQLiteError>{
+    // lesson is described as "subject, teacher, classroom + co-teachers (first hour-last hour)"
+    let describe = |lesson: &str, teachers: &str, blocks: &str| format!(
+        "(SELECT subject_name || ', ' || teacher_name || ', ' || classroom_name FROM Subjects, Teachers, Classrooms
+            WHERE subject_id = {lesson}.subject_id AND teacher_id = {lesson}.teacher_id AND classroom_id = {lesson}.classroom_id)
+        || COALESCE(' + ' || (SELECT GROUP_CONCAT(teacher_name, ' + ') FROM (
+            SELECT Teachers.teacher_name FROM {teachers} AS CoTeachers 
+            JOIN Teachers ON CoTeachers.teacher_id = Teachers.teacher_id
+            WHERE CoTeachers.weekday = {lesson}.weekday AND CoTeachers.class_id = {lesson}.class_id 
+            AND CoTeachers.lesson_hour = {lesson}.lesson_hour AND CoTeachers.semester = ?1 AND CoTeachers.academic_year = ?2
+            AND CoTeachers.week = {lesson}.week AND CoTeachers.group_id = {lesson}.group_id
+            ORDER BY Teachers.teacher_name)), '')
+        || COALESCE(' (' || (SELECT Blocks.lesson_hour || '-' || Blocks.last_hour FROM {blocks} AS Blocks
+            WHERE Blocks.weekday = {lesson}.weekday AND Blocks.class_id = {lesson}.class_id 
+            AND Blocks.semester = ?1 AND Blocks.academic_year = ?2 AND Blocks.week = {lesson}.week AND Blocks.group_id = {lesson}.group_id
+            AND {lesson}.lesson_hour BETWEEN Blocks.lesson_hour AND Blocks.last_hour) || ')', '')"
+    );
+    let mut stmt = db.prepare(&format!("
+    WITH 
+        Published AS (SELECT * FROM Lessons WHERE semester = ?1 AND academic_year = ?2 
+            AND class_id IN (SELECT class_id FROM DraftLessons WHERE semester = ?1 AND academic_year = ?2)),
+        Draft     AS (SELECT * FROM DraftLessons WHERE semester = ?1 AND academic_year = ?2),
+        Slots     AS (SELECT weekday, class_id, lesson_hour, week, group_id FROM Published 
+            UNION SELECT weekday, class_id, lesson_hour, week, group_id FROM Draft)
+    SELECT * FROM (
+        SELECT 
+            Slots.weekday, Slots.lesson_hour, Classes.class_name, ClassGroups.group_name,
+            {} AS published,
+            {} AS draft
+        FROM Slots
+        JOIN Classes ON Slots.class_id = Classes.class_id
+        LEFT JOIN ClassGroups ON Slots.group_id = ClassGroups.group_id
+        LEFT JOIN Published AS P ON P.weekday = Slots.weekday AND P.class_id = Slots.class_id AND P.lesson_hour = Slots.lesson_hour 
+            AND P.week = Slots.week AND P.group_id = Slots.group_id
+        LEFT JOIN Draft     AS D ON D.weekday = Slots.weekday AND D.class_id = Slots.class_id AND D.lesson_hour = Slots.lesson_hour 
+            AND D.week = Slots.week AND D.group_id = Slots.group_id
+        ORDER BY Classes.class_name, Slots.weekday, Slots.lesson_hour, Slots.group_id
+    )
+    WHERE published IS NOT draft
+    ", describe("P", "LessonTeachers", "LessonBlocks"), describe("D", "DraftLessonTeachers", "DraftLessonBlocks")))?;
+    let iter = stmt.query_map([semester, academic_year], |row| {
+        let class : String = row.get(2)?;
+        Ok(
+            DraftChange{
+                weekday   : row.get(0)?,
+                hour      : row.get(1)?,
+                owner     : match row.get::<usize, Option<String>>(3)?{
+                    Some(group) => format!("{} - {}", class, group),
+                    None        => class
+                },
+                published : row.get(4)?,
+                draft     : row.get(5)?
+            }
+        )
+    })?;
+    let lessons = iter.collect::<Result<Vec<DraftChange>, SQLiteError>>()?;
+    let mut stmt = db.prepare("
+    WITH 
+        Published AS (SELECT * FROM Duties WHERE semester = ?1 AND academic_year = ?2 
+            AND EXISTS (SELECT 1 FROM DraftDuties WHERE semester = ?1 AND academic_year = ?2)),
+        Draft     AS (SELECT * FROM DraftDuties WHERE semester = ?1 AND academic_year = ?2),
+        Slots     AS (SELECT weekday, break_num, teacher_id, week FROM Published 
+            UNION SELECT weekday, break_num, teacher_id, week FROM Draft)
+    SELECT 
+        Slots.weekday, Slots.break_num, Teachers.teacher_name,
+        (SELECT corridor_name FROM Corridors WHERE corridor = P.place_id),
+        (SELECT corridor_name FROM Corridors WHERE corridor = D.place_id)
+    FROM Slots
+    JOIN Teachers ON Slots.teacher_id = Teachers.teacher_id
+    LEFT JOIN Published AS P ON P.weekday = Slots.weekday AND P.break_num = Slots.break_num 
+        AND P.teacher_id = Slots.teacher_id AND P.week = Slots.week
+    LEFT JOIN Draft     AS D ON D.weekday = Slots.weekday AND D.break_num = Slots.break_num 
+        AND D.teacher_id = Slots.teacher_id AND D.week = Slots.week
+    WHERE P.place_id IS NOT D.place_id
+    ORDER BY Teachers.teacher_name, Slots.weekday, Slots.break_num
+    ")?;
+    let iter = stmt.query_map([semester, academic_year], |row| {
+        Ok(
+            DraftChange{
+                weekday   : row.get(0)?,
+                hour      : row.get(1)?,
+                owner     : row.get(2)?,
+                published : row.get(3)?,
+                draft     : row.get(4)?
+            }
+        )
+    })?;
+    let duties = iter.collect::<Result<Vec<DraftChange>, SQLiteError>>()?;
+    Ok(DraftChanges{lessons, duties})
+}
+
+/// Replaces lessons (with co-teachers and multi-slot lessons) of every class in draft with the draft and duties 
+/// of semester with `DraftDuties` (if there are any). Nothing is changed if draft clashes with lessons of other classes,
+/// or if any lesson is taught by teacher who isn't qualified for it or is unavailable at that time
+pub fn apply_draft(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let tx = db.unchecked_transaction().map_err(ServerError::DatabaseError)?;
+    let mut lessons : Vec<Lesson> = vec![];
+    {
+        let mut stmt = tx.prepare("SELECT weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, week, group_id
+            FROM DraftLessons
+            WHERE semester = ?1 AND academic_year = ?2").map_err(ServerError::DatabaseError)?;
+        let iter = stmt.query_map([semester, academic_year], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, semester, academic_year, row.get(6)?, row.get(7)?))
+        }).map_err(ServerError::DatabaseError)?;
+        for lesson in iter{
+            lessons.push(lesson.map_err(ServerError::DatabaseError)?);
+        }
+    }
+    let duties : usize = tx.query_row("SELECT COUNT(*) FROM DraftDuties WHERE semester = ?1 AND academic_year = ?2", 
+        [semester, academic_year], |row| row.get(0)).map_err(ServerError::DatabaseError)?;
+    if lessons.is_empty() && duties == 0{
+        return Ok("msat/204-No-Content".to_string());
+    }
+    for table in ["Lessons", "LessonTeachers", "LessonBlocks"]{
+        tx.execute(&format!("DELETE FROM {table} 
+            WHERE semester = ?1 AND academic_year = ?2
+            AND class_id IN (SELECT class_id FROM DraftLessons WHERE semester = ?1 AND academic_year = ?2)"), 
+            [semester, academic_year]).map_err(ServerError::DatabaseError)?;
+    }
+    // co-teachers are inserted first, so they are checked together with their lessons
+    tx.execute("INSERT INTO LessonTeachers (weekday, class_id, lesson_hour, semester, academic_year, week, group_id, teacher_id)
+        SELECT weekday, class_id, lesson_hour, semester, academic_year, week, group_id, teacher_id 
+        FROM DraftLessonTeachers WHERE semester = ?1 AND academic_year = ?2", [semester, academic_year])
+        .map_err(ServerError::DatabaseError)?;
+    let co_teachers = {
+        let mut stmt = tx.prepare("SELECT teacher_id, weekday, lesson_hour FROM DraftLessonTeachers WHERE semester = ?1 AND academic_year = ?2")
+            .map_err(ServerError::DatabaseError)?;
+        let iter = stmt.query_map([semester, academic_year], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(ServerError::DatabaseError)?;
+        iter.collect::<Result<Vec<(u16, u8, u16)>, SQLiteError>>().map_err(ServerError::DatabaseError)?
+    };
+    for (teacher_id, weekday, lesson_hour) in co_teachers{
+        if !is_available(teacher_id, weekday, lesson_hour, &tx).map_err(ServerError::DatabaseError)?{
+            return Err(ServerError::TeacherUnavailable{teacher_id, weekday, lesson_hour});
+        }
+    }
+    let mut conflicts = vec![];
+    for lesson in &lessons{
+        let (weekday, _, _, teacher_id, subject_id, lesson_hour, _, _, _, _) = *lesson;
+        if !is_qualified(teacher_id, subject_id, &tx).map_err(ServerError::DatabaseError)?{
+            return Err(ServerError::NotQualified{teacher_id, subject_id});
+        }
+        if !is_available(teacher_id, weekday, lesson_hour, &tx).map_err(ServerError::DatabaseError)?{
+            return Err(ServerError::TeacherUnavailable{teacher_id, weekday, lesson_hour});
+        }
+        conflicts.extend(get_lesson_conflicts(lesson, &tx).map_err(ServerError::DatabaseError)?);
+        manipulate_database(MainpulationType::Insert(POST::Lesson(Some(*lesson))), &tx).map_err(ServerError::DatabaseError)?;
+    }
+    if !conflicts.is_empty(){
+        // dropping transaction rolls everything back
+        return Err(ServerError::LessonConflict(conflicts));
+    }
+    tx.execute("INSERT INTO LessonBlocks (weekday, class_id, lesson_hour, last_hour, semester, academic_year, week, group_id)
+        SELECT weekday, class_id, lesson_hour, last_hour, semester, academic_year, week, group_id 
+        FROM DraftLessonBlocks WHERE semester = ?1 AND academic_year = ?2", [semester, academic_year])
+        .map_err(ServerError::DatabaseError)?;
+    if duties != 0{
+        tx.execute("DELETE FROM Duties WHERE semester = ?1 AND academic_year = ?2", [semester, academic_year])
+            .map_err(ServerError::DatabaseError)?;
+        tx.execute("INSERT INTO Duties (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+            SELECT weekday, break_num, teacher_id, place_id, semester, academic_year, week 
+            FROM DraftDuties WHERE semester = ?1 AND academic_year = ?2", [semester, academic_year])
+            .map_err(ServerError::DatabaseError)?;
+    }
+    for table in ["DraftLessons", "DraftLessonTeachers", "DraftLessonBlocks", "DraftDuties"]{
+        tx.execute(&format!("DELETE FROM {table} WHERE semester = ?1 AND academic_year = ?2"), [semester, academic_year])
+            .map_err(ServerError::DatabaseError)?;
+    }
+    tx.commit().map_err(ServerError::DatabaseError)?;
+    Ok("msat/201-Created".to_string())
+}
+
+// Date moved by one year, formatted like dates saved by dashboard
+const NEXT_YEAR : &str = "strftime('%Y-%m-%dT%H:%M:%fZ', {}, '+1 year')";
+
+/// Starts academic year that follows `academic_year`: creates year named `year_name` with copies of semesters 
+/// that start in old year (dates are moved by one year) and promotes classes to next grade (1a -> 2a). 
+/// Classes of old year are archived with their names, so past timetables don't change, and every promoted
+/// class gets new class with copy of its groups and students (classes of `final_grade` aren't promoted). 
+/// If `copy_timetable` is true, lessons (with co-teachers and multi-slot lessons) of promoted classes and duties 
+/// are copied into draft of new semesters (see `apply_draft`). 
+/// Everything is done in one transaction that is rolled back if `preview` is true
+pub fn rollover_year(academic_year: u8, year_name: &str, final_grade: u8, copy_timetable: bool, preview: bool, db: &rusqlite::Connection) 
+    -> Result<RolloverReport, SQLiteError>
+{
+    let tx = db.unchecked_transaction()?;
+    let year_name = year_name.replace("%20", " ");
+    let (old_name, new_year) : (String, u8) = tx.query_row("SELECT year_name, (SELECT MAX(academic_year) + 1 FROM Years) 
+        FROM Years WHERE academic_year = ?1", [academic_year], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    tx.execute(&format!("INSERT INTO Years (academic_year, year_name, start_date, end_date)
+        SELECT ?1, ?2, {}, {} FROM Years WHERE academic_year = ?3", NEXT_YEAR.replace("{}", "start_date"), NEXT_YEAR.replace("{}", "end_date")), 
+        rusqlite::params![new_year, year_name, academic_year])?;
+    let mut report = RolloverReport{
+        academic_year : new_year,
+        ..Default::default()
+    };
+
+    let semesters = {
+        let mut stmt = tx.prepare("SELECT semester, semester_name FROM Semesters, Years 
+            WHERE Years.academic_year = ?1 AND Semesters.start_date >= Years.start_date AND Semesters.start_date < Years.end_date
+            ORDER BY Semesters.start_date")?;
+        let iter = stmt.query_map([academic_year], |row| Ok((row.get::<usize, u8>(0)?, row.get::<usize, String>(1)?)))?;
+        iter.collect::<Result<Vec<(u8, String)>, SQLiteError>>()?
+    };
+    let first_semester : u8 = tx.query_row("SELECT COALESCE(MAX(semester), 0) + 1 FROM Semesters", [], |row| row.get(0))?;
+    let mut new_semesters = vec![];
+    for (i, (semester, semester_name)) in semesters.into_iter().enumerate(){
+        let new_semester = u8::try_from(i).ok().and_then(|i| first_semester.checked_add(i))
+            .ok_or(SQLiteError::IntegralValueOutOfRange(0, first_semester as i64 + i as i64))?;
+        let new_name = match semester_name.contains(&old_name){
+            true  => semester_name.replace(&old_name, &year_name),
+            false => format!("{} {}", semester_name, year_name)
+        };
+        tx.execute(&format!("INSERT INTO Semesters (semester, semester_name, start_date, end_date, week_cycle)
+            SELECT ?1, ?2, {}, {}, week_cycle FROM Semesters WHERE semester = ?3", NEXT_YEAR.replace("{}", "start_date"), NEXT_YEAR.replace("{}", "end_date")), 
+            rusqlite::params![new_semester, new_name, semester])?;
+        new_semesters.push((semester, new_semester));
+        report.semesters.push((new_semester, new_name));
+    }
+
+    let groups_of = |class_id: u16| -> Result<Vec<(u16, String)>, SQLiteError> {
+        let mut stmt = tx.prepare("SELECT group_id, group_name FROM ClassGroups WHERE class_id = ?1 ORDER BY group_id")?;
+        let iter = stmt.query_map([class_id], |row| Ok((row.get::<usize, u16>(0)?, row.get::<usize, String>(1)?)))?;
+        iter.collect::<Result<Vec<(u16, String)>, SQLiteError>>()
+    };
+    let active = {
+        let mut stmt = tx.prepare("SELECT class_id, class_name FROM Classes WHERE archived = 0 ORDER BY class_id")?;
+        let iter = stmt.query_map([], |row| Ok((row.get::<usize, u16>(0)?, row.get::<usize, String>(1)?)))?;
+        iter.collect::<Result<Vec<(u16, String)>, SQLiteError>>()?
+    };
+    // (class_id, class_id in new year, [(group_id, group_id in new year)]) of classes that have lessons in new year
+    let mut moved = vec![];
+    let mut classes = vec![];
+    for (class_id, class_name) in active{
+        let grade = class_name.chars().take_while(|c| c.is_ascii_digit()).collect::<String>();
+        match grade.parse::<u8>(){
+            Ok(number) => classes.push((class_id, number, class_name[grade.len()..].to_string(), class_name)),
+            // only classes with name starting with grade (e.g. 1a) are promoted, other classes are left as they are
+            Err(_) => {
+                let mut groups = vec![(0, 0)];
+                groups.extend(groups_of(class_id)?.into_iter().map(|(group_id, _)| (group_id, group_id)));
+                moved.push((class_id, class_id, groups));
+            }
+        }
+    }
+    for (class_id, ..) in &classes{
+        tx.execute("UPDATE Classes SET archived = 1 WHERE class_id = ?1", [class_id])?;
+    }
+    for (class_id, grade, section, class_name) in classes{
+        if grade >= final_grade{
+            report.archived.push(class_name);
+            continue;
+        }
+        let new_name = format!("{}{}", grade + 1, section);
+        let new_class : u16 = tx.query_row("SELECT COALESCE(MAX(class_id), 0) + 1 FROM Classes", [], |row| row.get(0))?;
+        tx.execute("INSERT INTO Classes (class_id, class_name, students) SELECT ?1, ?2, students FROM Classes WHERE class_id = ?3", 
+            rusqlite::params![new_class, new_name, class_id])?;
+        let mut new_groups = vec![(0, 0)];
+        for (group_id, group_name) in groups_of(class_id)?{
+            let new_group : u16 = tx.query_row("SELECT COALESCE(MAX(group_id), 0) + 1 FROM ClassGroups", [], |row| row.get(0))?;
+            tx.execute("INSERT INTO ClassGroups (group_id, class_id, group_name) VALUES (?1, ?2, ?3)", 
+                rusqlite::params![new_group, new_class, group_name])?;
+            new_groups.push((group_id, new_group));
+        }
+        moved.push((class_id, new_class, new_groups));
+        report.promoted.push((class_name, new_name));
+    }
+
+    if copy_timetable{
+        for (semester, new_semester) in new_semesters{
+            for (class_id, new_class, groups) in &moved{
+                for (group_id, new_group) in groups{
+                    for (draft, table, columns) in [
+                        ("DraftLessons", "Lessons", "weekday, classroom_id, teacher_id, subject_id, lesson_hour, week"),
+                        ("DraftLessonTeachers", "LessonTeachers", "weekday, lesson_hour, week, teacher_id"),
+                        ("DraftLessonBlocks", "LessonBlocks", "weekday, lesson_hour, last_hour, week")
+                    ]{
+                        let copied = tx.execute(&format!("INSERT INTO {draft} ({columns}, semester, academic_year, class_id, group_id)
+                            SELECT {columns}, ?3, ?4, ?5, ?6 FROM {table}
+                            WHERE semester = ?1 AND academic_year = ?2 AND class_id = ?7 AND group_id = ?8"), 
+                            rusqlite::params![semester, academic_year, new_semester, new_year, new_class, new_group, class_id, group_id])?;
+                        if draft == "DraftLessons"{
+                            report.lessons += copied;
+                        }
+                    }
+                }
+            }
+            report.duties += tx.execute("INSERT INTO DraftDuties 
+                (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+                SELECT weekday, break_num, teacher_id, place_id, ?3, ?4, week 
+                FROM Duties
+                WHERE semester = ?1 AND academic_year = ?2", [semester, academic_year, new_semester, new_year])?;
+        }
+    }
+    if !preview{
+        tx.commit()?;
+    }
+    Ok(report)
+}
+
+/// Fills every corridor in every break of given semester with a teacher
+/// (see `generator::solve_duties`) and saves new duties into `Duties` (or into draft if semester has one, 
+/// see `has_draft`). Duties that are already in database are kept. If semester has week cycle, 
+/// duties are generated for every week of it, otherwise for every week at once (week 0). 
+/// Returns roster of every such week
+pub fn generate_duties(semester: u8, academic_year: u8, db: &rusqlite::Connection) -> Result<Vec<(u8, DutyRoster)>, SQLiteError>{
+    let mut problem = DutyProblem{
+        weekdays : SCHOOL_DAYS.to_vec(),
+        ..Default::default()
+    };
+    let around_break = lesson_hours_around_breaks(db)?;
+    problem.breaks = around_break.iter().map(|(break_num, _, _)| *break_num).collect();
+    let mut stmt = db.prepare("SELECT corridor FROM Corridors ORDER BY corridor")?;
+    for corridor in stmt.query_map([], |row| row.get(0))?{
+        problem.corridors.push(corridor?);
+    }
+    let mut stmt = db.prepare("SELECT teacher_id FROM Teachers ORDER BY teacher_id")?;
+    for teacher in stmt.query_map([], |row| row.get(0))?{
+        problem.teachers.push(teacher?);
+    }
+    let mut stmt = db.prepare("SELECT teacher_id, weekday, break_num FROM DutyExclusions")?;
+    for exclusion in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?{
+        problem.exclusions.push(exclusion?);
+    }
+    // teacher is excluded from break if lesson hour before or after it is unavailable (see `is_available_for_break`)
+    let mut stmt = db.prepare("SELECT teacher_id, weekday, lesson_hour FROM Availability WHERE availability = 'unavailable'")?;
+    let mut unavailable : Vec<(u16, u8, u16)> = vec![];
+    for row in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?{
+        unavailable.push(row?);
+    }
+    for teacher_id in &problem.teachers{
+        for weekday in &problem.weekdays{
+            for (break_num, before, after) in &around_break{
+                if [*before, *after].into_iter().flatten().any(|hour| unavailable.contains(&(*teacher_id, *weekday, hour))){
+                    problem.exclusions.push((*teacher_id, *weekday, *break_num));
+                }
+            }
+        }
+    }
+    let week_cycle : u8 = db.query_row("SELECT COALESCE((SELECT week_cycle FROM Semesters WHERE semester = ?1), 1)", [semester], |row| row.get(0))?;
+    let weeks = match week_cycle > 1{
+        true  => (1..=week_cycle).collect::<Vec<u8>>(),
+        false => vec![0]
+    };
+
+    let mut rosters = vec![];
+    let tx = db.unchecked_transaction()?;
+    // while semester has draft, duties are generated for lessons in draft (published lessons of classes
+    // that aren't in draft) and saved together with other duties of draft
+    let draft = has_draft(semester, academic_year, &tx)?;
+    let (teaching, duties) = match draft{
+        false => (TEACHING.to_string(), "Duties"),
+        true  => {
+            copy_duties_to_draft(semester, academic_year, &tx)?;
+            (draft_teaching(), "DraftDuties")
+        }
+    };
+    for week in weeks{
+        let mut problem = problem.clone();
+        // lessons and duties of every week (week 0) also take place in this week, co-teachers are present like main teachers
+        let mut stmt = tx.prepare(&format!("SELECT Teaching.teacher_id, Teaching.weekday, Teaching.lesson_hour, Classrooms.corridor
+            FROM {teaching} AS Teaching
+            JOIN Classrooms ON Teaching.classroom_id = Classrooms.classroom_id
+            WHERE Teaching.semester = ?1 AND Teaching.academic_year = ?2 AND (Teaching.week = 0 OR ?3 = 0 OR Teaching.week = ?3)"))?;
+        for row in stmt.query_map([semester, academic_year, week], |row| {
+            Ok((row.get::<usize, u16>(0)?, row.get::<usize, u8>(1)?, row.get::<usize, u16>(2)?, row.get::<usize, Option<u16>>(3)?))
+        })?{
+            let (teacher_id, weekday, lesson_hour, corridor) = row?;
+            problem.present.insert((teacher_id, weekday));
+            if let Some(corridor) = corridor{
+                for (break_num, before, after) in &around_break{
+                    if *before == Some(lesson_hour) || *after == Some(lesson_hour){
+                        problem.adjacent.entry((teacher_id, weekday, *break_num)).or_default().push(corridor);
+                    }
+                }
+            }
+        }
+        let mut stmt = tx.prepare(&format!("SELECT weekday, break_num, teacher_id, place_id FROM {duties} 
+            WHERE semester = ?1 AND academic_year = ?2 AND (week = 0 OR ?3 = 0 OR week = ?3)"))?;
+        for duty in stmt.query_map([semester, academic_year, week], |row| {
+            Ok(
+                Duty{
+                    weekday    : row.get(0)?,
+                    break_num  : row.get(1)?,
+                    teacher_id : row.get(2)?,
+                    place_id   : row.get(3)?
+                }
+            )
+        })?{
+            problem.existing.push(duty?);
+        }
+
+        let roster = generator::solve_duties(&problem);
+        for duty in &roster.duties{
+            let duty = (duty.weekday, duty.break_num, duty.teacher_id, duty.place_id, semester, academic_year, week);
+            let post = match draft{
+                false => POST::Duty(Some(duty)),
+                true  => POST::DraftDuty(Some(duty))
+            };
+            manipulate_database(MainpulationType::Insert(post), &tx)?;
+        }
+        rosters.push((week, roster));
+    }
+    tx.commit()?;
+    Ok(rosters)
+}
+
+/// (break_num, lesson hour right before break, lesson hour right after break)
+type AroundBreak = (u8, Option<u16>, Option<u16>);
+/// Every break with lesson hours around it
+fn lesson_hours_around_breaks(db: &rusqlite::Connection) -> Result<Vec<AroundBreak>, SQLiteError>{
+    // (number, start, end) in minutes since midnight
+    let mut stmt = db.prepare("SELECT lesson_hour, start_hour * 60 + start_minutes, end_hour * 60 + end_minutes FROM LessonHours")?;
+    let mut lesson_hours : Vec<(u16, u16, u16)> = vec![];
+    for lesson_hour in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?{
+        lesson_hours.push(lesson_hour?);
+    }
+    let mut stmt = db.prepare("SELECT break_num, start_hour * 60 + start_minutes, end_hour * 60 + end_minutes FROM Breaks ORDER BY break_num")?;
+    let mut around_break : Vec<AroundBreak> = vec![];
+    for row in stmt.query_map([], |row| Ok((row.get::<usize, u8>(0)?, row.get::<usize, u16>(1)?, row.get::<usize, u16>(2)?)))?{
+        let (break_num, start, end) = row?;
+        let before = lesson_hours.iter().filter(|(_, _, e)| *e <= start).max_by_key(|(_, _, e)| *e).map(|l| l.0);
+        let after  = lesson_hours.iter().filter(|(_, s, _)| *s >= end).min_by_key(|(_, s, _)| *s).map(|l| l.0);
+        around_break.push((break_num, before, after));
+    }
+    Ok(around_break)
+}
+
+/// Inserts duty (weekday, break_num, teacher_id, place_id, semester, academic_year, week) 
+/// only if teacher is available during break (see `is_available_for_break`)
+pub fn insert_duty(duty: (u8, u8, u16, u16, u8, u8, u8), db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekday, break_num, teacher_id, ..) = duty;
+    if !is_available_for_break(teacher_id, weekday, break_num, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::TeacherUnavailableForBreak{teacher_id, weekday, break_num});
+    }
+    manipulate_database(MainpulationType::Insert(POST::Duty(Some(duty))), db).map_err(ServerError::DatabaseError)
+}
+/// Inserts duty into `DraftDuties` with the same check as `insert_duty`
+pub fn insert_draft_duty(duty: (u8, u8, u16, u16, u8, u8, u8), db: &rusqlite::Connection) -> Result<String, ServerError>{
+    let (weekday, break_num, teacher_id, ..) = duty;
+    if !is_available_for_break(teacher_id, weekday, break_num, db).map_err(ServerError::DatabaseError)?{
+        return Err(ServerError::TeacherUnavailableForBreak{teacher_id, weekday, break_num});
+    }
+    let (_, _, _, _, semester, academic_year, _) = duty;
+    let tx = db.unchecked_transaction().map_err(ServerError::DatabaseError)?;
+    copy_duties_to_draft(semester, academic_year, &tx).map_err(ServerError::DatabaseError)?;
+    let response = manipulate_database(MainpulationType::Insert(POST::DraftDuty(Some(duty))), &tx).map_err(ServerError::DatabaseError)?;
+    tx.commit().map_err(ServerError::DatabaseError)?;
+    Ok(response)
+}
+
+// (table, column, id) of event participant
+fn event_participant_table(participant: TimetableOwner) -> (&'static str, &'static str, u16){
+    match participant{
+        TimetableOwner::Teacher(teacher_id)     => ("EventTeachers", "teacher_id", teacher_id),
+        TimetableOwner::Class(class_id)         => ("EventClasses", "class_id", class_id),
+        TimetableOwner::Classroom(classroom_id) => ("EventClassrooms", "classroom_id", classroom_id)
+    }
 }
 
 pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connection) -> Result<String, rusqlite::Error>{
@@ -497,53 +3017,196 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
                     db.execute("DELETE FROM Subjects WHERE subject_name = ?1", [subject])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Year { academic_year } => {
-                    db.execute("DELETE FROM Years WHERE academic_year = ?1", [academic_year])?;
+                Delete::Year { academic_year } => {
+                    db.execute("DELETE FROM Years WHERE academic_year = ?1", [academic_year])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Class { class } => {
+                    db.execute("DELETE FROM Classes WHERE class_id = ?1", [class])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Lesson { class, weekd, lessonh, semester, academic_year, week, group_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    // every hour of multi-slot lesson is deleted with it
+                    let (first_hour, last_hour) = get_block_range((weekd, class, lessonh, semester, academic_year, week, group_id), lessonh, false, &tx)?;
+                    delete_lesson_hours((weekd, class, first_hour, semester, academic_year, week, group_id), last_hour, false, &tx)?;
+                    tx.commit()?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Teacher { teacher } => {
+                    db.execute("DELETE From Teachers WHERE teacher_id = ?1", [teacher])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Semester { semester } => {
+                    db.execute("DELETE FROM Semesters WHERE semester = ?1", [semester])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Corridor { corridor } => {
+                    db.execute("DELETE FROM Corridors WHERE corridor = ?1", [corridor])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Classroom { classroom } => {
+                    db.execute("DELETE FROM Classrooms WHERE classroom_id = ?1", [classroom])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::LessonHour { lessonh } => {
+                    db.execute("DELETE FROM LessonHours WHERE lesson_hour = ?1", [lessonh])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Break { break_num } => {
+                    db.execute("DELETE FROM Breaks WHERE break_num = ?1", [break_num])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Duty { weekday, break_num, teacher_id, semester, academic_year, week } => {
+                    db.execute("DELETE FROM Duties 
+                        WHERE weekday     = ?1 
+                        AND break_num     = ?2 
+                        AND teacher_id    = ?3 
+                        AND semester      = ?4
+                        AND academic_year = ?5
+                        AND week          = ?6", [weekday.into(), break_num.into(), teacher_id, semester.into(), academic_year.into(), week.into()])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::DutyExclusion { teacher_id, weekday, break_num } => {
+                    db.execute("DELETE FROM DutyExclusions 
+                        WHERE teacher_id = ?1 
+                        AND weekday      = ?2 
+                        AND break_num    = ?3", [teacher_id, weekday.into(), break_num.into()])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Requirement { class, subject, semester, academic_year } => {
+                    db.execute("DELETE FROM Requirements 
+                        WHERE class_id    = ?1 
+                        AND subject_id    = ?2 
+                        AND semester      = ?3
+                        AND academic_year = ?4", [class, subject, semester.into(), academic_year.into()])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Absence { teacher_id, date } => {
+                    db.execute("DELETE FROM Absences WHERE teacher_id = ?1 AND date = ?2", 
+                        rusqlite::params![teacher_id, date.to_string()])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Substitution { date, class_id, lesson_hour, group_id } => {
+                    db.execute("DELETE FROM Substitutions 
+                        WHERE date      = ?1 
+                        AND class_id    = ?2 
+                        AND lesson_hour = ?3
+                        AND group_id    = ?4", rusqlite::params![date.to_string(), class_id, lesson_hour, group_id])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::CalendarEntry { calendar_id } => {
+                    db.execute("DELETE FROM Calendar WHERE calendar_id = ?1", [calendar_id])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::Group { group_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    tx.execute("DELETE FROM Lessons WHERE group_id = ?1", [group_id])?;
+                    tx.execute("DELETE FROM LessonTeachers WHERE group_id = ?1", [group_id])?;
+                    tx.execute("DELETE FROM LessonBlocks WHERE group_id = ?1", [group_id])?;
+                    for table in ["DraftLessons", "DraftLessonTeachers", "DraftLessonBlocks"]{
+                        tx.execute(&format!("DELETE FROM {table} WHERE group_id = ?1"), [group_id])?;
+                    }
+                    tx.execute("DELETE FROM Substitutions WHERE group_id = ?1", [group_id])?;
+                    tx.execute("DELETE FROM ClassGroups WHERE group_id = ?1", [group_id])?;
+                    tx.commit()?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::CoTeacher { key: (weekday, class_id, lesson_hour, semester, academic_year, week, group_id), teacher_id } => {
+                    db.execute("DELETE FROM LessonTeachers 
+                        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND semester = ?4 
+                        AND academic_year = ?5 AND week = ?6 AND group_id = ?7 AND teacher_id = ?8",
+                        [weekday.into(), class_id, lesson_hour, semester.into(), academic_year.into(), week.into(), group_id, teacher_id])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Class { class } => {
-                    db.execute("DELETE FROM Classes WHERE class_id = ?1", [class])?;
+                Delete::Qualification { teacher_id, subject_id } => {
+                    db.execute("DELETE FROM Qualifications WHERE teacher_id = ?1 AND subject_id = ?2", [teacher_id, subject_id])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Lesson { class, weekd, lessonh, semester, academic_year } => {
-                    db.execute("DELETE FROM Lessons 
-                        WHERE class_id  = ?1 AND weekday = ?2 
-                        AND lesson_hour = ?3 AND semester = ?4
-                        AND academic_year = ?5"
-                        , [class, weekd.into(), lessonh, semester.into(), academic_year.into()])?;
+                Delete::ClassroomFeature { classroom_id, feature } => {
+                    db.execute("DELETE FROM ClassroomFeatures WHERE classroom_id = ?1 AND feature = ?2", 
+                        rusqlite::params![classroom_id, feature.replace("%20", " ")])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Teacher { teacher } => {
-                    db.execute("DELETE From Teachers WHERE teacher_id = ?1", [teacher])?;
+                Delete::Booking { booking_id } => {
+                    db.execute("DELETE FROM Bookings WHERE booking_id = ?1", [booking_id])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Semester { semester } => {
-                    db.execute("DELETE FROM Semesters WHERE semester = ?1", [semester])?;
+                Delete::Event { event_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    tx.execute("DELETE FROM EventClasses WHERE event_id = ?1", [event_id])?;
+                    tx.execute("DELETE FROM EventTeachers WHERE event_id = ?1", [event_id])?;
+                    tx.execute("DELETE FROM EventClassrooms WHERE event_id = ?1", [event_id])?;
+                    tx.execute("DELETE FROM Events WHERE event_id = ?1", [event_id])?;
+                    tx.commit()?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Corridor { corridor } => {
-                    db.execute("DELETE FROM Corridors WHERE corridor = ?1", [corridor])?;
+                Delete::LessonOverride { date, class_id, lesson_hour, group_id } => {
+                    db.execute("DELETE FROM LessonOverrides WHERE date = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND group_id = ?4",
+                        rusqlite::params![date.to_string(), class_id, lesson_hour, group_id])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Classroom { classroom } => {
-                    db.execute("DELETE FROM Classrooms WHERE classroom_id = ?1", [classroom])?;
+                Delete::BellSchedule { schedule_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    tx.execute("DELETE FROM BellScheduleHours WHERE schedule_id = ?1", [schedule_id])?;
+                    tx.execute("DELETE FROM BellScheduleBreaks WHERE schedule_id = ?1", [schedule_id])?;
+                    tx.execute("DELETE FROM ScheduleDays WHERE schedule_id = ?1", [schedule_id])?;
+                    tx.execute("DELETE FROM BellSchedules WHERE schedule_id = ?1", [schedule_id])?;
+                    tx.commit()?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::LessonHour { lessonh } => {
-                    db.execute("DELETE FROM LessonHours WHERE lesson_hour = ?1", [lessonh])?;
+                Delete::ScheduleDay { date } => {
+                    db.execute("DELETE FROM ScheduleDays WHERE date = ?1", [date.to_string()])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Break { break_num } => {
-                    db.execute("DELETE FROM Breaks WHERE break_num = ?1", [break_num])?;
+                Delete::WeekdayHour { weekday, lesson_hour } => {
+                    db.execute("DELETE FROM WeekdayHours WHERE weekday = ?1 AND lesson_hour = ?2", [weekday.into(), lesson_hour])?;
                     Ok("msat/201-Deleted".to_string())
                 }
-                Delete::Duty { weekday, break_num, teacher_id, semester, academic_year } => {
-                    db.execute("DELETE FROM Duties 
+                Delete::DraftLesson { class, weekd, lessonh, semester, academic_year, week, group_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    copy_class_to_draft(semester, academic_year, class, &tx)?;
+                    // every hour of multi-slot lesson is deleted with it
+                    let (first_hour, last_hour) = get_block_range((weekd, class, lessonh, semester, academic_year, week, group_id), lessonh, true, &tx)?;
+                    delete_lesson_hours((weekd, class, first_hour, semester, academic_year, week, group_id), last_hour, true, &tx)?;
+                    tx.commit()?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::DraftCoTeacher { key: (weekday, class_id, lesson_hour, semester, academic_year, week, group_id), teacher_id } => {
+                    let tx = db.unchecked_transaction()?;
+                    copy_class_to_draft(semester, academic_year, class_id, &tx)?;
+                    tx.execute("DELETE FROM DraftLessonTeachers 
+                        WHERE weekday = ?1 AND class_id = ?2 AND lesson_hour = ?3 AND semester = ?4 
+                        AND academic_year = ?5 AND week = ?6 AND group_id = ?7 AND teacher_id = ?8",
+                        [weekday.into(), class_id, lesson_hour, semester.into(), academic_year.into(), week.into(), group_id, teacher_id])?;
+                    tx.commit()?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::DraftDuty { weekday, break_num, teacher_id, semester, academic_year, week } => {
+                    let tx = db.unchecked_transaction()?;
+                    copy_duties_to_draft(semester, academic_year, &tx)?;
+                    tx.execute("DELETE FROM DraftDuties 
                         WHERE weekday     = ?1 
                         AND break_num     = ?2 
                         AND teacher_id    = ?3 
                         AND semester      = ?4
-                        AND academic_year = ?5", [weekday.into(), break_num.into(), teacher_id, semester.into(), academic_year.into()])?;
+                        AND academic_year = ?5
+                        AND week          = ?6", [weekday.into(), break_num.into(), teacher_id, semester.into(), academic_year.into(), week.into()])?;
+                    tx.commit()?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::WeekdayBreak { weekday, break_num } => {
+                    db.execute("DELETE FROM WeekdayBreaks WHERE weekday = ?1 AND break_num = ?2", [weekday, break_num])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::EventParticipant { event_id, participant } => {
+                    let (table, column, id) = event_participant_table(participant);
+                    db.execute(&format!("DELETE FROM {} WHERE event_id = ?1 AND {} = ?2", table, column), [event_id, id])?;
+                    Ok("msat/201-Deleted".to_string())
+                }
+                Delete::SubjectFeature { subject_id, feature } => {
+                    db.execute("DELETE FROM SubjectFeatures WHERE subject_id = ?1 AND feature = ?2", 
+                        rusqlite::params![subject_id, feature.replace("%20", " ")])?;
                     Ok("msat/201-Deleted".to_string())
                 }
             }
@@ -559,22 +3222,45 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
                     )?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Duty(Some((weekd, break_num, teacher_id, place_id, semester, academic_year))) => {
-                    db.execute("INSERT INTO Duties (weekday, break_num, teacher_id, place_id, semester, academic_year)
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
-                    ON CONFLICT (weekday, break_num, teacher_id, semester, academic_year) 
+                POST::Duty(Some((weekd, break_num, teacher_id, place_id, semester, academic_year, week))) => {
+                    db.execute("INSERT INTO Duties (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT (weekday, break_num, teacher_id, semester, academic_year, week) 
                     DO UPDATE SET 
                     place_id = excluded.place_id"
-                        ,[weekd.into(), break_num.into(), teacher_id, place_id, semester.into(), academic_year.into()]
+                        ,[weekd.into(), break_num.into(), teacher_id, place_id, semester.into(), academic_year.into(), week.into()]
                     )?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Class(Some((class_id, class_name))) => {
-                    db.execute("INSERT INTO Classes (class_id, class_name) 
-                        VALUES (?1, ?2)
+                POST::DraftLesson(Some((weekd, class_id, classroom_id, teacher_id, subject_id, lessonh, semester, academic_year, week, group_id))) => {
+                    db.execute(
+                    "INSERT INTO DraftLessons (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                    ON CONFLICT (class_id, weekday, lesson_hour, semester, academic_year, week, group_id)
+                    DO UPDATE SET 
+                    classroom_id = excluded.classroom_id, 
+                    teacher_id = excluded.teacher_id, 
+                    subject_id = excluded.subject_id
+                    ", 
+                    [weekd.into(), class_id, classroom_id, teacher_id, subject_id, lessonh, semester.into(), academic_year.into(), week.into(), group_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::DraftDuty(Some((weekd, break_num, teacher_id, place_id, semester, academic_year, week))) => {
+                    db.execute("INSERT INTO DraftDuties (weekday, break_num, teacher_id, place_id, semester, academic_year, week)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT (weekday, break_num, teacher_id, semester, academic_year, week) 
+                    DO UPDATE SET 
+                    place_id = excluded.place_id"
+                        ,[weekd.into(), break_num.into(), teacher_id, place_id, semester.into(), academic_year.into(), week.into()]
+                    )?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Class(Some((class_id, class_name, students))) => {
+                    db.execute("INSERT INTO Classes (class_id, class_name, students) 
+                        VALUES (?1, ?2, ?3)
                         ON CONFLICT (class_id)
-                        DO UPDATE SET class_name = excluded.class_name"
-                        , [class_id.to_string(), class_name.replace("%20", " ")])?;
+                        DO UPDATE SET class_name = excluded.class_name, students = COALESCE(excluded.students, Classes.students)"
+                        , rusqlite::params![class_id, class_name.replace("%20", " "), students])?;
                     return Ok("msat/201-Created".to_string())
                 }
                 POST::Break(Some((break_num, start_hour, start_minute, end_hour, end_minute))) => {
@@ -586,25 +3272,25 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
                         , [break_num, start_hour, start_minute, end_hour, end_minute])?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Lesson(Some((weekd, class_id, classroom_id, teacher_id, subject_id, lessonh, semester, academic_year))) => {
+                POST::Lesson(Some((weekd, class_id, classroom_id, teacher_id, subject_id, lessonh, semester, academic_year, week, group_id))) => {
                     db.execute(
-                    "INSERT INTO Lessons (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year)
-                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-                    ON CONFLICT (weekday, class_id, lesson_hour, semester, academic_year)
+                    "INSERT INTO Lessons (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year, week, group_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                    ON CONFLICT (weekday, class_id, lesson_hour, semester, academic_year, week, group_id)
                     DO UPDATE SET 
                     classroom_id = excluded.classroom_id, 
                     teacher_id = excluded.teacher_id, 
                     subject_id = excluded.subject_id
                     ", 
-                    [weekd.into(), class_id, classroom_id, teacher_id, subject_id, lessonh, semester.into(), academic_year.into()])?;
+                    [weekd.into(), class_id, classroom_id, teacher_id, subject_id, lessonh, semester.into(), academic_year.into(), week.into(), group_id])?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Teacher(Some((teacher_id, teacher_name))) => {
-                    db.execute("INSERT INTO Teachers (teacher_id, teacher_name) 
-                        VALUES (?1, ?2)
+                POST::Teacher(Some((teacher_id, teacher_name, pensum))) => {
+                    db.execute("INSERT INTO Teachers (teacher_id, teacher_name, pensum) 
+                        VALUES (?1, ?2, ?3)
                         ON CONFLICT (teacher_id)
-                        DO UPDATE SET teacher_name = excluded.teacher_name"
-                        , [teacher_id.to_string(), teacher_name.replace("%20", " ")])?;
+                        DO UPDATE SET teacher_name = excluded.teacher_name, pensum = COALESCE(excluded.pensum, Teachers.pensum)"
+                        , rusqlite::params![teacher_id, teacher_name.replace("%20", " "), pensum])?;
                     return Ok("msat/201-Created".to_string())
                 }
                 POST::Subject(Some((subject_id, subject_name))) => {
@@ -615,23 +3301,25 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
                         , [subject_id.to_string(), subject_name.replace("%20", " ")])?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Semester(Some((semester, semester_name, start_date, end_date))) => {
-                    db.execute("INSERT INTO Semesters (semester, semester_name, start_date, end_date)
-                    VALUES (?1, ?2, ?3, ?4)
+                POST::Semester(Some((semester, semester_name, start_date, end_date, week_cycle))) => {
+                    db.execute("INSERT INTO Semesters (semester, semester_name, start_date, end_date, week_cycle)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
                     ON CONFLICT (semester)
                     DO UPDATE SET 
                     semester_name = excluded.semester_name, 
                     start_date = excluded.start_date, 
-                    end_date = excluded.end_date"
-                        , [semester.to_string(), semester_name.replace("%20", " "), start_date, end_date])?;
+                    end_date = excluded.end_date,
+                    week_cycle = excluded.week_cycle"
+                        , [semester.to_string(), semester_name.replace("%20", " "), start_date, end_date, week_cycle.to_string()])?;
                     return Ok("msat/201-Created".to_string())
                 }
-                POST::Classroom(Some((classroom_id, classroom_name))) => {
-                    db.execute("INSERT INTO Classrooms (classroom_id, classroom_name) 
-                        VALUES (?1, ?2)
+                POST::Classroom(Some((classroom_id, classroom_name, corridor, capacity))) => {
+                    db.execute("INSERT INTO Classrooms (classroom_id, classroom_name, corridor, capacity) 
+                        VALUES (?1, ?2, ?3, ?4)
                         ON CONFLICT (classroom_id)
-                        DO UPDATE SET classroom_name = excluded.classroom_name"
-                        , [classroom_id.to_string(), classroom_name.replace("%20", " ")])?;
+                        DO UPDATE SET classroom_name = excluded.classroom_name, corridor = COALESCE(excluded.corridor, Classrooms.corridor), 
+                        capacity = COALESCE(excluded.capacity, Classrooms.capacity)"
+                        , rusqlite::params![classroom_id, classroom_name.replace("%20", " "), corridor, capacity])?;
                     return Ok("msat/201-Created".to_string())
                 }
                 POST::Corridors(Some((corridor_id, corridor_name))) => {
@@ -654,6 +3342,216 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
                     [lesson_num, start_hour.into(), start_minute.into(), end_hour.into(), end_minute.into()])?;
                     return Ok("msat/201-Created".to_string())
                 }
+                POST::Requirement(Some((class_id, subject_id, teacher_id, classroom_id, hours, semester, academic_year))) => {
+                    db.execute("INSERT INTO Requirements (class_id, subject_id, teacher_id, classroom_id, hours, semester, academic_year)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                    ON CONFLICT (class_id, subject_id, semester, academic_year)
+                    DO UPDATE SET 
+                    teacher_id   = excluded.teacher_id,
+                    classroom_id = excluded.classroom_id,
+                    hours        = excluded.hours",
+                    rusqlite::params![class_id, subject_id, teacher_id, classroom_id, hours, semester, academic_year])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::DutyExclusion(Some((teacher_id, weekday, break_num))) => {
+                    db.execute("INSERT OR IGNORE INTO DutyExclusions (teacher_id, weekday, break_num)
+                    VALUES (?1, ?2, ?3)", [teacher_id, weekday.into(), break_num.into()])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Absence(Some((teacher_id, date))) => {
+                    db.execute("INSERT OR IGNORE INTO Absences (teacher_id, date)
+                    VALUES (?1, ?2)", rusqlite::params![teacher_id, date.to_string()])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Substitution(Some((date, class_id, lesson_hour, teacher_id, group_id))) => {
+                    db.execute("INSERT INTO Substitutions (date, class_id, lesson_hour, substitute_teacher_id, group_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT (date, class_id, lesson_hour, group_id)
+                    DO UPDATE SET 
+                    substitute_teacher_id = excluded.substitute_teacher_id",
+                    rusqlite::params![date.to_string(), class_id, lesson_hour, teacher_id, group_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Group(Some((group_id, class_id, group_name))) => {
+                    db.execute("INSERT INTO ClassGroups (group_id, class_id, group_name)
+                    VALUES (?1, ?2, ?3)
+                    ON CONFLICT (group_id)
+                    DO UPDATE SET 
+                    class_id   = excluded.class_id,
+                    group_name = excluded.group_name",
+                    rusqlite::params![group_id, class_id, group_name.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::CoTeacher(Some(((weekday, class_id, lesson_hour, semester, academic_year, week, group_id), teacher_id))) => {
+                    db.execute("INSERT OR IGNORE INTO LessonTeachers (weekday, class_id, lesson_hour, semester, academic_year, week, group_id, teacher_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    [weekday.into(), class_id, lesson_hour, semester.into(), academic_year.into(), week.into(), group_id, teacher_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::DraftCoTeacher(Some(((weekday, class_id, lesson_hour, semester, academic_year, week, group_id), teacher_id))) => {
+                    db.execute("INSERT OR IGNORE INTO DraftLessonTeachers (weekday, class_id, lesson_hour, semester, academic_year, week, group_id, teacher_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    [weekday.into(), class_id, lesson_hour, semester.into(), academic_year.into(), week.into(), group_id, teacher_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Qualification(Some((teacher_id, subject_id))) => {
+                    db.execute("INSERT OR IGNORE INTO Qualifications (teacher_id, subject_id)
+                    VALUES (?1, ?2)", [teacher_id, subject_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::ClassroomFeature(Some((classroom_id, feature))) => {
+                    db.execute("INSERT OR IGNORE INTO ClassroomFeatures (classroom_id, feature)
+                    VALUES (?1, ?2)", rusqlite::params![classroom_id, feature.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Booking(Some((booking_id, classroom_id, date, start_hour, start_minute, end_hour, end_minute, teacher_id, purpose))) => {
+                    db.execute("INSERT INTO Bookings (booking_id, classroom_id, date, start_hour, start_minutes, end_hour, end_minutes, teacher_id, purpose)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                    ON CONFLICT (booking_id)
+                    DO UPDATE SET 
+                    classroom_id  = excluded.classroom_id,
+                    date          = excluded.date,
+                    start_hour    = excluded.start_hour,
+                    start_minutes = excluded.start_minutes,
+                    end_hour      = excluded.end_hour,
+                    end_minutes   = excluded.end_minutes,
+                    teacher_id    = excluded.teacher_id,
+                    purpose       = excluded.purpose",
+                    rusqlite::params![booking_id, classroom_id, date.to_string(), start_hour, start_minute, end_hour, end_minute, teacher_id, 
+                        purpose.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Event(Some((event_id, name, event_type, date, start_hour, start_minute, end_hour, end_minute, overrides_lessons))) => {
+                    db.execute("INSERT INTO Events (event_id, name, event_type, date, start_hour, start_minutes, end_hour, end_minutes, overrides_lessons)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                    ON CONFLICT (event_id)
+                    DO UPDATE SET 
+                    name              = excluded.name,
+                    event_type        = excluded.event_type,
+                    date              = excluded.date,
+                    start_hour        = excluded.start_hour,
+                    start_minutes     = excluded.start_minutes,
+                    end_hour          = excluded.end_hour,
+                    end_minutes       = excluded.end_minutes,
+                    overrides_lessons = excluded.overrides_lessons",
+                    rusqlite::params![event_id, name.replace("%20", " "), event_type.as_str(), date.to_string(), start_hour, start_minute, 
+                        end_hour, end_minute, overrides_lessons])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::LessonOverride(Some((date, class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id))) => {
+                    db.execute("INSERT INTO LessonOverrides (date, class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                    ON CONFLICT (date, class_id, lesson_hour, group_id)
+                    DO UPDATE SET 
+                    cancelled       = excluded.cancelled,
+                    classroom_id    = excluded.classroom_id,
+                    new_lesson_hour = excluded.new_lesson_hour,
+                    teacher_id      = excluded.teacher_id",
+                    rusqlite::params![date.to_string(), class_id, lesson_hour, group_id, cancelled, classroom_id, new_lesson_hour, teacher_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::BellSchedule(Some((schedule_id, name))) => {
+                    db.execute("INSERT INTO BellSchedules (schedule_id, name)
+                    VALUES (?1, ?2)
+                    ON CONFLICT (schedule_id)
+                    DO UPDATE SET 
+                    name = excluded.name",
+                    rusqlite::params![schedule_id, name.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::BellScheduleHour(Some((schedule_id, lesson_hour, start_hour, start_minute, end_hour, end_minute))) => {
+                    db.execute("INSERT INTO BellScheduleHours (schedule_id, lesson_hour, start_hour, start_minutes, end_hour, end_minutes)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (schedule_id, lesson_hour)
+                    DO UPDATE SET 
+                    start_hour    = excluded.start_hour,
+                    start_minutes = excluded.start_minutes,
+                    end_hour      = excluded.end_hour,
+                    end_minutes   = excluded.end_minutes",
+                    rusqlite::params![schedule_id, lesson_hour, start_hour, start_minute, end_hour, end_minute])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::BellScheduleBreak(Some((schedule_id, break_num, start_hour, start_minute, end_hour, end_minute))) => {
+                    db.execute("INSERT INTO BellScheduleBreaks (schedule_id, break_num, start_hour, start_minutes, end_hour, end_minutes)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (schedule_id, break_num)
+                    DO UPDATE SET 
+                    start_hour    = excluded.start_hour,
+                    start_minutes = excluded.start_minutes,
+                    end_hour      = excluded.end_hour,
+                    end_minutes   = excluded.end_minutes",
+                    rusqlite::params![schedule_id, break_num, start_hour, start_minute, end_hour, end_minute])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::WeekdayHour(Some((weekday, lesson_hour, start_hour, start_minute, end_hour, end_minute))) => {
+                    db.execute("INSERT INTO WeekdayHours (weekday, lesson_hour, start_hour, start_minutes, end_hour, end_minutes)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (weekday, lesson_hour)
+                    DO UPDATE SET 
+                    start_hour    = excluded.start_hour,
+                    start_minutes = excluded.start_minutes,
+                    end_hour      = excluded.end_hour,
+                    end_minutes   = excluded.end_minutes",
+                    rusqlite::params![weekday, lesson_hour, start_hour, start_minute, end_hour, end_minute])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::WeekdayBreak(Some((weekday, break_num, start_hour, start_minute, end_hour, end_minute))) => {
+                    db.execute("INSERT INTO WeekdayBreaks (weekday, break_num, start_hour, start_minutes, end_hour, end_minutes)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (weekday, break_num)
+                    DO UPDATE SET 
+                    start_hour    = excluded.start_hour,
+                    start_minutes = excluded.start_minutes,
+                    end_hour      = excluded.end_hour,
+                    end_minutes   = excluded.end_minutes",
+                    rusqlite::params![weekday, break_num, start_hour, start_minute, end_hour, end_minute])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::ScheduleDay(Some((date, schedule_id))) => {
+                    db.execute("INSERT INTO ScheduleDays (date, schedule_id)
+                    VALUES (?1, ?2)
+                    ON CONFLICT (date)
+                    DO UPDATE SET 
+                    schedule_id = excluded.schedule_id",
+                    rusqlite::params![date.to_string(), schedule_id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::EventParticipant(Some((event_id, participant))) => {
+                    let (table, column, id) = event_participant_table(participant);
+                    db.execute(&format!("INSERT OR IGNORE INTO {} (event_id, {}) VALUES (?1, ?2)", table, column), [event_id, id])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::SubjectFeature(Some((subject_id, feature))) => {
+                    db.execute("INSERT OR IGNORE INTO SubjectFeatures (subject_id, feature)
+                    VALUES (?1, ?2)", rusqlite::params![subject_id, feature.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Availability(Some((teacher_id, weekday, lesson_hour, Availability::Neutral))) => {
+                    db.execute("DELETE FROM Availability WHERE teacher_id = ?1 AND weekday = ?2 AND lesson_hour = ?3",
+                    rusqlite::params![teacher_id, weekday, lesson_hour])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::Availability(Some((teacher_id, weekday, lesson_hour, availability))) => {
+                    db.execute("INSERT INTO Availability (teacher_id, weekday, lesson_hour, availability)
+                    VALUES (?1, ?2, ?3, ?4)
+                    ON CONFLICT (teacher_id, weekday, lesson_hour)
+                    DO UPDATE SET 
+                    availability = excluded.availability",
+                    rusqlite::params![teacher_id, weekday, lesson_hour, availability.as_str()])?;
+                    Ok("msat/201-Created".to_string())
+                }
+                POST::CalendarEntry(Some((calendar_id, start_date, end_date, day_type, name))) => {
+                    db.execute("INSERT INTO Calendar (calendar_id, start_date, end_date, day_type, name)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT (calendar_id)
+                    DO UPDATE SET 
+                    start_date = excluded.start_date,
+                    end_date   = excluded.end_date,
+                    day_type   = excluded.day_type,
+                    name       = excluded.name",
+                    rusqlite::params![calendar_id, start_date.to_string(), end_date.to_string(), day_type.as_str(), name.replace("%20", " ")])?;
+                    Ok("msat/201-Created".to_string())
+                }
                 _ => {
                     return Ok("msat/500-Internal-Server-Error&error=error+occured+while+inserting+values".to_string());
                 }
@@ -823,6 +3721,29 @@ pub fn manipulate_database(manipulation: MainpulationType, db: &rusqlite::Connec
 #[cfg(test)]
 mod tests{
     use super::*;
+    /// In-memory database with few rows in every
+    /// table that lessons and duties refer to
+    fn test_db() -> Database{
+        let db = Database::open_in_memory().unwrap();
+        create_tables(&db).unwrap();
+        for id in 1..=3u16{
+            for post in [
+                POST::Class      (Some((id, format!("class{}", id), None))),
+                POST::Classroom  (Some((id, format!("classroom{}", id), None, None))),
+                POST::Teacher    (Some((id, format!("teacher{}", id), None))),
+                POST::Subject    (Some((id, format!("subject{}", id)))),
+                POST::Corridors  (Some((id, format!("corridor{}", id)))),
+                POST::LessonHours(Some((id, 7 + id as u8, 0, 7 + id as u8, 45)))
+            ]{
+                manipulate_database(MainpulationType::Insert(post), &db).unwrap();
+            }
+        }
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some(
+            (1, "semester1".to_string(), "2025-09-01T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 1)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Year(Some(
+            (1, "year1".to_string(), "2025-09-01T00:00:00.000Z".to_string(), "2026-06-30T00:00:00.000Z".to_string())))), &db).unwrap();
+        db
+    }
     #[test]
     fn test(){
         let mut args = HashMap::new();
@@ -836,4 +3757,901 @@ mod tests{
             ,
             Request::from_str("/?msat/50&password=test&method=PAS+0").parse());
     }
+    #[test]
+    fn teacher_conflict(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        // same class and hour is an update, not a clash
+        assert!(insert_lesson((1, 1, 2, 1, 2, 1, 1, 1, 0, 0), &db).is_ok());
+        assert_eq!(
+            Err(ServerError::LessonConflict(vec![LessonConflict{
+                kind: ConflictKind::Teacher, weekday: 1, lesson_hour: 1, class_id: 1, classroom_id: 2, teacher_id: 1
+            }])),
+            insert_lesson((1, 2, 3, 1, 1, 1, 1, 1, 0, 0), &db)
+        );
+        assert!(insert_lesson((1, 2, 3, 1, 1, 2, 1, 1, 0, 0), &db).is_ok());
+    }
+    #[test]
+    fn classroom_conflict(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        let conflicts = get_lesson_conflicts(&(1, 2, 1, 2, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(1, conflicts.len());
+        assert_eq!(ConflictKind::Classroom, conflicts[0].kind);
+        // rows written before the check existed are still reported
+        manipulate_database(MainpulationType::Insert(POST::Lesson(Some((1, 2, 1, 1, 1, 1, 1, 1, 0, 0)))), &db).unwrap();
+        let collisions = get_collisions(1, 1, &db).unwrap();
+        assert_eq!(2, collisions.len());
+        assert_eq!([(1, "class1".to_string()), (2, "class2".to_string())], collisions[0].classes);
+    }
+    #[test]
+    fn draft_timetable(){
+        let db = test_db();
+        // class 3 isn't generated, so teacher 1 is busy on monday's first lesson
+        insert_lesson((1, 3, 3, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        for (class, subject, teacher, hours) in [(1, 1, 1, 4), (1, 2, 2, 3), (2, 1, 1, 4), (2, 3, 3, 5)]{
+            manipulate_database(MainpulationType::Insert(POST::Requirement(Some((class, subject, teacher, None, hours, 1, 1)))), &db).unwrap();
+        }
+        assert_eq!(DraftReport{placed: 16, unplaced: vec![]}, generate_draft(1, 1, &db).unwrap());
+        assert_eq!(7, get_draft(1, 1, Some(1), &db).unwrap().len());
+        assert_eq!(Ok("msat/201-Created".to_string()), apply_draft(1, 1, &db));
+        assert!(get_draft(1, 1, None, &db).unwrap().is_empty());
+        assert!(get_collisions(1, 1, &db).unwrap().is_empty());
+        let count : u16 = db.query_row("SELECT COUNT(*) FROM Lessons", [], |row| row.get(0)).unwrap();
+        assert_eq!(17, count);
+    }
+    #[test]
+    fn draft_keeps_other_classes(){
+        let db = test_db();
+        // teacher 1 is co-teacher of published lesson of class 3 and teaches class 2 in draft
+        insert_lesson((1, 3, 3, 3, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_co_teacher((1, 3, 1, 1, 1, 0, 0), 1, &db).unwrap();
+        insert_draft_lesson((2, 2, 2, 1, 1, 1, 1, 1, 0, 0), 1, &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Requirement(Some((1, 1, 1, None, 15, 1, 1)))), &db).unwrap();
+        let report = generate_draft(1, 1, &db).unwrap();
+        assert_eq!(13, report.placed);
+        assert_eq!(2, report.unplaced[0].1);
+        assert_eq!(1, get_draft(1, 1, Some(2), &db).unwrap().len());
+        let class1 = get_draft(1, 1, Some(1), &db).unwrap();
+        assert!(class1.iter().all(|l| l.lessonh.lesson_hour != Some(1) || !matches!(l.weekday, Some(1) | Some(2))));
+    }
+    #[test]
+    fn draft_respects_availability(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 2, Availability::Unavailable)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Requirement(Some((1, 1, 1, None, 15, 1, 1)))), &db).unwrap();
+        let report = generate_draft(1, 1, &db).unwrap();
+        assert_eq!(14, report.placed);
+        assert_eq!(1, report.unplaced[0].1);
+        assert!(get_draft(1, 1, None, &db).unwrap().iter().all(|l| !(l.weekday == Some(1) && l.lessonh.lesson_hour == Some(2))));
+
+        // rows that got into draft without checks are rejected and nothing is applied
+        db.execute("INSERT INTO DraftLessons (weekday, class_id, classroom_id, teacher_id, subject_id, lesson_hour, semester, academic_year)
+            VALUES (1, 1, 1, 1, 1, 2, 1, 1)", []).unwrap();
+        assert_eq!(Err(ServerError::TeacherUnavailable{teacher_id: 1, weekday: 1, lesson_hour: 2}), apply_draft(1, 1, &db));
+        db.execute("DELETE FROM DraftLessons WHERE weekday = 1 AND lesson_hour = 2", []).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Qualification(Some((1, 2)))), &db).unwrap();
+        assert_eq!(Err(ServerError::NotQualified{teacher_id: 1, subject_id: 1}), apply_draft(1, 1, &db));
+        let count : u16 = db.query_row("SELECT COUNT(*) FROM Lessons", [], |row| row.get(0)).unwrap();
+        assert_eq!(0, count);
+        assert_eq!(14, get_draft(1, 1, None, &db).unwrap().len());
+    }
+    #[test]
+    fn duty_roster(){
+        let db = test_db();
+        // corridor of every classroom has the same id as classroom
+        for id in 1..=3u16{
+            manipulate_database(MainpulationType::Insert(POST::Classroom(Some((id, format!("classroom{}", id), Some(id), None)))), &db).unwrap();
+        }
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 9, 0)))), &db).unwrap();
+        for weekday in SCHOOL_DAYS{
+            insert_lesson((weekday, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+            insert_lesson((weekday, 1, 1, 1, 1, 2, 1, 1, 0, 0), &db).unwrap();
+            insert_lesson((weekday, 2, 2, 2, 1, 2, 1, 1, 0, 0), &db).unwrap();
+            insert_lesson((weekday, 3, 3, 3, 1, 3, 1, 1, 0, 0), &db).unwrap();
+        }
+        manipulate_database(MainpulationType::Insert(POST::DutyExclusion(Some((3, 0, 0)))), &db).unwrap();
+        let rosters = generate_duties(1, 1, &db).unwrap();
+        // semester without week cycle has duties for every week
+        assert_eq!(vec![0], rosters.iter().map(|(week, _)| *week).collect::<Vec<u8>>());
+        let roster = &rosters[0].1;
+        // only teacher 1 can stay in first corridor and teacher 2 in second one
+        assert_eq!(10, roster.duties.len());
+        assert_eq!(5, roster.unfilled.len());
+        assert!(roster.duties.iter().all(|d| d.teacher_id == d.place_id));
+        let count : u16 = db.query_row("SELECT COUNT(*) FROM Duties", [], |row| row.get(0)).unwrap();
+        assert_eq!(10, count);
+    }
+    #[test]
+    fn duty_roster_week_cycle(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some(
+            (1, "semester1".to_string(), "2025-09-01T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 2)))), &db).unwrap();
+        for id in 1..=3u16{
+            manipulate_database(MainpulationType::Insert(POST::Classroom(Some((id, format!("classroom{}", id), Some(id), None)))), &db).unwrap();
+        }
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 9, 0)))), &db).unwrap();
+        // teacher 2 is only co-teacher and teacher 1 can't have duties
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 1, 0), &db).unwrap();
+        insert_co_teacher((1, 1, 1, 1, 1, 1, 0), 2, &db).unwrap();
+        insert_lesson((1, 2, 1, 3, 1, 1, 1, 1, 2, 0), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::DutyExclusion(Some((1, 0, 0)))), &db).unwrap();
+        let rosters = generate_duties(1, 1, &db).unwrap();
+        let duties = rosters.iter()
+            .flat_map(|(week, roster)| roster.duties.iter().map(|d| (*week, d.weekday, d.teacher_id, d.place_id)))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, 1, 2, 1), (2, 1, 3, 1)], duties);
+        let mut stmt = db.prepare("SELECT week, teacher_id FROM Duties ORDER BY week").unwrap();
+        let saved = stmt.query_map([], |row| Ok((row.get::<usize, u8>(0)?, row.get::<usize, u16>(1)?))).unwrap()
+            .collect::<Result<Vec<_>, SQLiteError>>().unwrap();
+        assert_eq!(vec![(1, 2), (2, 3)], saved);
+    }
+    #[test]
+    fn substitutions(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 2, 2, 2, 1, 2, 1, 1, 0, 0), &db).unwrap();
+        // 2025-10-06 is Monday
+        let monday    = parse_date("2025-10-06").unwrap();
+        let wednesday = parse_date("2025-10-08").unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Absence(Some((1, monday)))), &db).unwrap();
+
+        let lessons = get_lessons_by_class_id(1, wednesday, &db).unwrap();
+        assert_eq!(1, lessons.len());
+        assert_eq!(LessonStatus::Cancelled, lessons[0].status);
+        assert_eq!(Some("2025-10-06".to_string()), lessons[0].date);
+        assert!(get_lessons_by_teacher_id(1, wednesday, &db).unwrap().is_empty());
+
+        manipulate_database(MainpulationType::Insert(POST::Qualification(Some((2, 1)))), &db).unwrap();
+        let candidates = suggest_substitutes(monday, 1, 0, 1, &db).unwrap();
+        assert_eq!(vec![(2, true), (3, false)], candidates.iter().map(|c| (c.teacher_id, c.qualified)).collect::<Vec<_>>());
+
+        manipulate_database(MainpulationType::Insert(POST::Substitution(Some((monday, 1, 1, 3, 0)))), &db).unwrap();
+        let lessons = get_lessons_by_class_id(1, monday, &db).unwrap();
+        assert_eq!(LessonStatus::Substitution, lessons[0].status);
+        assert_eq!(Some("teacher3".to_string()), lessons[0].teacher);
+        let lessons = get_lessons_by_teacher_id(3, monday, &db).unwrap();
+        assert_eq!(1, lessons.len());
+        assert_eq!(Some("class1".to_string()), lessons[0].class);
+        assert_eq!(Some("teacher3".to_string()), get_cover_lessons(monday, &db).unwrap()[0].substitute);
+
+        // absence affects only one week
+        let lessons = get_lessons_by_class_id(1, parse_date("2025-10-13").unwrap(), &db).unwrap();
+        assert_eq!(LessonStatus::Regular, lessons[0].status);
+        assert_eq!(Some("teacher1".to_string()), lessons[0].teacher);
+    }
+    #[test]
+    fn school_calendar(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        let monday = parse_date("2025-10-06").unwrap();
+        manipulate_database(MainpulationType::Insert(POST::CalendarEntry(Some(
+            (1, monday, parse_date("2025-10-07").unwrap(), DayType::Holiday, "holiday1".to_string())))), &db).unwrap();
+        assert!(!is_school_day(monday, &db).unwrap());
+        assert!(get_lessons_by_class_id(1, monday, &db).unwrap().is_empty());
+        assert!(get_schedule_for_date(monday, &db).unwrap().is_empty());
+        assert_eq!(Some("holiday1".to_string()), get_calendar_entry(parse_date("2025-10-07").unwrap(), &db).unwrap().map(|e| e.name));
+
+        assert!(is_school_day(parse_date("2025-10-08").unwrap(), &db).unwrap());
+        // weekend and day outside of semester
+        assert!(!is_school_day(parse_date("2025-10-11").unwrap(), &db).unwrap());
+        assert!(!is_school_day(parse_date("2026-03-02").unwrap(), &db).unwrap());
+
+        let next_monday = parse_date("2025-10-13").unwrap();
+        assert_eq!(1, get_schedule_for_date(next_monday, &db).unwrap().len());
+        manipulate_database(MainpulationType::Insert(POST::CalendarEntry(Some(
+            (2, next_monday, next_monday, DayType::Exam, "exam1".to_string())))), &db).unwrap();
+        assert!(is_school_day(next_monday, &db).unwrap());
+        assert!(get_schedule_for_date(next_monday, &db).unwrap().is_empty());
+    }
+    #[test]
+    fn date_queries(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some(
+            (2, "semester2".to_string(), "2026-02-01T00:00:00.000Z".to_string(), "2026-06-30T00:00:00.000Z".to_string(), 1)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 9, 0)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Duty(Some((1, 1, 1, 1, 1, 1, 0)))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 2, 2, 2, 1, 2, 1, 0, 0), &db).unwrap();
+        let autumn = parse_date("2025-10-06").unwrap();
+        let spring = parse_date("2026-03-02").unwrap();
+
+        assert_eq!(Ok((1, 1)), get_year_and_semester(autumn, &db));
+        assert_eq!(Ok((1, 2)), get_year_and_semester(spring, &db));
+        assert_eq!(Err(SQLiteError::QueryReturnedNoRows), get_year_and_semester(parse_date("2024-10-07").unwrap(), &db));
+
+        assert_eq!(Some("subject1".to_string()), get_lessons_by_class_id(1, autumn, &db).unwrap()[0].subject);
+        assert_eq!(Some("subject2".to_string()), get_lessons_by_class_id(1, spring, &db).unwrap()[0].subject);
+        assert_eq!(1, get_duties_for_teacher(1, autumn, &db).unwrap().len());
+        assert!(get_duties_for_teacher(1, spring, &db).unwrap().is_empty());
+
+        let lessons = get_lessons_between(autumn, parse_date("2025-10-20").unwrap(), |date| get_lessons_by_class_id(1, date, &db)).unwrap();
+        assert_eq!(vec!["2025-10-06", "2025-10-13", "2025-10-20"], lessons.iter().flat_map(|l| l.date.as_deref()).collect::<Vec<_>>());
+        assert_eq!(Ok(3), get_timetable_between(TimetableOwner::Class(1), autumn, parse_date("2025-10-20").unwrap(), &db).map(|l| l.len()));
+    }
+    #[test]
+    fn week_rotation(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some(
+            (1, "semester1".to_string(), "2025-09-02T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 2)))), &db).unwrap();
+        // semester starts in week of Monday 2025-09-01
+        let week_a = parse_date("2025-10-15").unwrap();
+        let week_b = parse_date("2025-10-08").unwrap();
+        assert_eq!(Ok(1), get_cycle_week(parse_date("2025-09-03").unwrap(), &db));
+        assert_eq!(Ok(1), get_cycle_week(week_a, &db));
+        assert_eq!(Ok(2), get_cycle_week(week_b, &db));
+
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 1, 0), &db).unwrap();
+        insert_lesson((1, 1, 2, 2, 2, 1, 1, 1, 2, 0), &db).unwrap();
+        assert_eq!(Some("subject1".to_string()), get_lessons_by_class_id(1, week_a, &db).unwrap()[0].subject);
+        assert_eq!(Some("subject2".to_string()), get_lessons_by_class_id(1, week_b, &db).unwrap()[0].subject);
+        assert_eq!(1, get_lessons_by_teacher_id(1, week_a, &db).unwrap().len());
+        assert!(get_lessons_by_teacher_id(1, week_b, &db).unwrap().is_empty());
+
+        // every-week lesson overlaps with both weeks of class 1
+        let conflicts = get_lesson_conflicts(&(1, 1, 3, 3, 3, 1, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![ConflictKind::Class, ConflictKind::Class], conflicts.iter().map(|c| c.kind).collect::<Vec<_>>());
+        // teacher 1 is free on week B
+        assert!(insert_lesson((1, 2, 3, 1, 3, 1, 1, 1, 2, 0), &db).is_ok());
+        assert!(insert_lesson((1, 3, 3, 1, 3, 1, 1, 1, 1, 0), &db).is_err());
+    }
+    #[test]
+    fn week_column_migration(){
+        let db = Database::open_in_memory().unwrap();
+        db.execute_batch("PRAGMA foreign_keys = OFF;
+            CREATE TABLE Lessons (weekday INTEGER NOT NULL, class_id INTEGER NOT NULL, classroom_id INTEGER NOT NULL, 
+                teacher_id INTEGER NOT NULL, subject_id INTEGER NOT NULL, lesson_hour INTEGER NOT NULL, semester INTEGER NOT NULL, 
+                academic_year INTEGER NOT NULL, PRIMARY KEY (class_id, weekday, lesson_hour, semester, academic_year));
+            INSERT INTO Lessons VALUES (1, 1, 1, 1, 1, 1, 1, 1);").unwrap();
+        create_tables(&db).unwrap();
+        let (week, group_id) : (u8, u16) = db.query_row("SELECT week, group_id FROM Lessons WHERE class_id = 1", [], 
+            |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!((0, 0), (week, group_id));
+    }
+    #[test]
+    fn class_groups(){
+        let db = test_db();
+        for (group_id, group_name) in [(1, "group1"), (2, "group2")]{
+            manipulate_database(MainpulationType::Insert(POST::Group(Some((group_id, 1, group_name.to_string())))), &db).unwrap();
+        }
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 1), &db).unwrap();
+        insert_lesson((1, 1, 2, 2, 2, 1, 1, 1, 0, 2), &db).unwrap();
+        // group 1 belongs to class 1
+        assert_eq!(Err(ServerError::GroupNotInClass{group_id: 1, class_id: 2}), insert_lesson((1, 2, 3, 3, 3, 1, 1, 1, 0, 1), &db));
+        assert_eq!(Err(ServerError::GroupNotInClass{group_id: 1, class_id: 2}), insert_draft_lesson((1, 2, 3, 3, 3, 1, 1, 1, 0, 1), 1, &db));
+        // whole class can't have lesson at the same time as its groups
+        let conflicts = get_lesson_conflicts(&(1, 1, 3, 3, 3, 1, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![ConflictKind::Class, ConflictKind::Class], conflicts.iter().map(|c| c.kind).collect::<Vec<_>>());
+        // group 2 can't be taught by teacher of group 1
+        let conflicts = get_lesson_conflicts(&(1, 1, 3, 1, 2, 1, 1, 1, 0, 2), &db).unwrap();
+        assert_eq!(vec![ConflictKind::Teacher], conflicts.iter().map(|c| c.kind).collect::<Vec<_>>());
+
+        let monday = parse_date("2025-10-06").unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Absence(Some((2, monday)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Substitution(Some((monday, 1, 1, 3, 2)))), &db).unwrap();
+        let lessons = get_lessons_by_class_id(1, monday, &db).unwrap();
+        assert_eq!(
+            vec![(Some("group1".to_string()), Some("teacher1".to_string())), (Some("group2".to_string()), Some("teacher3".to_string()))],
+            lessons.into_iter().map(|l| (l.group, l.teacher)).collect::<Vec<_>>()
+        );
+
+        manipulate_database(MainpulationType::Delete(Delete::Group { group_id: 2 }), &db).unwrap();
+        assert_eq!(vec![(1, "group1".to_string())], get_groups(1, &db).unwrap());
+        assert_eq!(1, get_lessons_by_class_id(1, monday, &db).unwrap().len());
+    }
+    #[test]
+    fn co_teaching(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 2, 2, 2, 2, 1, 1, 1, 0, 0), &db).unwrap();
+        // teacher 2 is busy with class 2
+        assert!(insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 2, &db).is_err());
+        // teacher 1 already teaches this lesson
+        assert!(matches!(insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 1, &db), Err(ServerError::LessonConflict(_))));
+        insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 3, &db).unwrap();
+
+        let monday = parse_date("2025-10-06").unwrap();
+        let lessons = get_lessons_by_teacher_id(3, monday, &db).unwrap();
+        assert_eq!(vec![Some("class1".to_string())], lessons.iter().map(|l| l.class.clone()).collect::<Vec<_>>());
+        assert_eq!(Some("teacher3".to_string()), lessons[0].co_teachers);
+        assert!(!suggest_substitutes(monday, 2, 0, 1, &db).unwrap().iter().any(|c| c.teacher_id == 3));
+        // co-teacher is booked for lessons of other classes
+        let conflicts = get_lesson_conflicts(&(1, 3, 3, 3, 3, 1, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![(ConflictKind::Teacher, 1)], conflicts.iter().map(|c| (c.kind, c.class_id)).collect::<Vec<_>>());
+        // lesson with co-teacher clashes with lesson of co-teacher
+        db.execute("DELETE FROM LessonTeachers", []).unwrap();
+        insert_lesson((1, 3, 3, 3, 3, 1, 1, 1, 0, 0), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::CoTeacher(Some(((1, 1, 1, 1, 1, 0, 0), 3)))), &db).unwrap();
+        assert_eq!(1, get_collisions(1, 1, &db).unwrap().len());
+
+        manipulate_database(MainpulationType::Delete(Delete::Lesson { class: 1, weekd: 1, lessonh: 1, semester: 1, academic_year: 1, week: 0, group_id: 0 }), &db).unwrap();
+        let count : u16 = db.query_row("SELECT COUNT(*) FROM LessonTeachers", [], |row| row.get(0)).unwrap();
+        assert_eq!(0, count);
+    }
+    #[test]
+    fn co_teacher_becomes_main_teacher(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 2, &db).unwrap();
+        insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 3, &db).unwrap();
+        insert_lesson((1, 1, 1, 2, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![3], get_co_teacher_ids((1, 1, 1, 1, 1, 0, 0), &db).unwrap());
+        assert_eq!(1.0, get_teacher_workload(1, 1, &db).unwrap()[1].lessons);
+        // remaining co-teacher must be available
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((3, 1, 1, Availability::Unavailable)))), &db).unwrap();
+        assert_eq!(Err(ServerError::TeacherUnavailable{teacher_id: 3, weekday: 1, lesson_hour: 1}), insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db));
+        assert_eq!(vec![3], get_co_teacher_ids((1, 1, 1, 1, 1, 0, 0), &db).unwrap());
+    }
+    #[test]
+    fn teacher_workload(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some((1, "semester1".to_string(), 
+            "2025-09-01T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 2)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Teacher(Some((1, "Smith, John".to_string(), Some(2))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Teacher(Some((2, "teacher2".to_string(), Some(2))))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 1, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 3, 1, 1, 2, 0), &db).unwrap();
+        insert_lesson((2, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 2, &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Duty(Some((1, 1, 2, 1, 1, 1, 1)))), &db).unwrap();
+
+        let workload = get_teacher_workload(1, 1, &db).unwrap();
+        assert_eq!(
+            vec![(3.0, 0.0, LoadStatus::Over), (1.0, 0.5, LoadStatus::Under), (0.0, 0.0, LoadStatus::Unknown)],
+            workload.iter().map(|t| (t.lessons, t.duties, t.status())).collect::<Vec<_>>()
+        );
+        let csv = workload_to_csv(&workload);
+        assert_eq!(Some("1,\"Smith, John\",3,0,2,over"), csv.lines().nth(1));
+        // duties count towards pensum
+        manipulate_database(MainpulationType::Insert(POST::Duty(Some((1, 1, 2, 1, 1, 1, 2)))), &db).unwrap();
+        assert_eq!(LoadStatus::Full, get_teacher_workload(1, 1, &db).unwrap()[1].status());
+        // updating name without pensum keeps pensum
+        manipulate_database(MainpulationType::Insert(POST::Teacher(Some((2, "teacher2".to_string(), None)))), &db).unwrap();
+        assert_eq!(Some(2), get_teacher_workload(1, 1, &db).unwrap()[1].pensum);
+        // teachers are listed even without semester
+        assert_eq!(3, get_teacher_workload(2, 1, &db).unwrap().len());
+    }
+    #[test]
+    fn curriculum_compliance(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some((1, "semester1".to_string(), 
+            "2025-09-01T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 2)))), &db).unwrap();
+        for group_id in [1, 2]{
+            manipulate_database(MainpulationType::Insert(POST::Group(Some((group_id, 1, format!("group{}", group_id))))), &db).unwrap();
+        }
+        for (subject, hours) in [(1, 2), (2, 3)]{
+            manipulate_database(MainpulationType::Insert(POST::Requirement(Some((1, subject, 1, None, hours, 1, 1)))), &db).unwrap();
+        }
+        // subject 1: one lesson for whole class and one lesson for each group
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 0, 1), &db).unwrap();
+        insert_lesson((1, 1, 2, 2, 1, 2, 1, 1, 0, 2), &db).unwrap();
+        // subject 2: lesson every second week
+        insert_lesson((2, 1, 1, 1, 2, 1, 1, 1, 1, 0), &db).unwrap();
+        // subject 3 isn't required
+        insert_lesson((3, 1, 1, 1, 3, 1, 1, 1, 0, 0), &db).unwrap();
+
+        let compliance = get_curriculum_compliance(1, 1, Some(1), &db).unwrap();
+        assert_eq!(
+            vec![(1, Some(2), 2.0, LoadStatus::Full), (2, Some(3), 0.5, LoadStatus::Under), (3, None, 1.0, LoadStatus::Over)],
+            compliance.iter().map(|c| (c.subject_id, c.required, c.scheduled, c.status())).collect::<Vec<_>>()
+        );
+        assert!(get_curriculum_compliance(1, 1, Some(2), &db).unwrap().is_empty());
+    }
+    #[test]
+    fn qualifications(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Qualification(Some((1, 1)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Qualification(Some((2, 2)))), &db).unwrap();
+        // teacher 3 has no qualifications and isn't restricted
+        assert!(is_qualified(3, 1, &db).unwrap());
+        assert_eq!(vec![1, 3], get_qualified_teachers(1, &db).unwrap().iter().map(|t| t.0).collect::<Vec<_>>());
+        assert_eq!(vec![(1, "subject1".to_string())], get_qualifications(1, &db).unwrap());
+
+        assert_eq!(Err(ServerError::NotQualified{teacher_id: 2, subject_id: 1}), insert_lesson((1, 1, 1, 2, 1, 1, 1, 1, 0, 0), &db));
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+
+        let monday = parse_date("2025-10-06").unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Absence(Some((1, monday)))), &db).unwrap();
+        let candidates = suggest_substitutes(monday, 1, 0, 1, &db).unwrap();
+        assert_eq!(vec![(3, false)], candidates.iter().map(|c| (c.teacher_id, c.qualified)).collect::<Vec<_>>());
+
+        manipulate_database(MainpulationType::Delete(Delete::Qualification{teacher_id: 2, subject_id: 2}), &db).unwrap();
+        assert!(is_qualified(2, 1, &db).unwrap());
+    }
+    #[test]
+    fn teacher_availability(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 1, Availability::Unavailable)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 2, Availability::Preferred)))), &db).unwrap();
+        assert_eq!(Err(ServerError::TeacherUnavailable{teacher_id: 1, weekday: 1, lesson_hour: 1}), insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db));
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 0, 0), &db).unwrap();
+        // lesson hour before break is unavailable
+        assert_eq!(Err(ServerError::TeacherUnavailableForBreak{teacher_id: 1, weekday: 1, break_num: 1}), insert_duty((1, 1, 1, 1, 1, 1, 0), &db));
+        assert!(get_availability_violations(1, 1, &db).unwrap().is_empty());
+
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 2, Availability::Unavailable)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 1, Availability::Neutral)))), &db).unwrap();
+        // lesson hour after break is unavailable
+        assert_eq!(Err(ServerError::TeacherUnavailableForBreak{teacher_id: 1, weekday: 1, break_num: 1}), insert_duty((1, 1, 1, 2, 1, 1, 0), &db));
+        let violations = get_availability_violations(1, 1, &db).unwrap();
+        assert_eq!(vec![(1, 1, 2)], violations.iter().map(|v| (v.teacher_id, v.weekday, v.lesson_hour)).collect::<Vec<_>>());
+        assert_eq!(vec![(1, 2, Availability::Unavailable)], get_availability(1, &db).unwrap());
+        insert_duty((1, 1, 2, 1, 1, 1, 0), &db).unwrap();
+
+        // unavailable teachers aren't suggested as substitutes, teachers who prefer lesson hour come first
+        let monday = parse_date("2025-10-06").unwrap();
+        insert_lesson((1, 2, 2, 2, 2, 3, 1, 1, 0, 0), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Absence(Some((2, monday)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((3, 1, 3, Availability::Preferred)))), &db).unwrap();
+        assert_eq!(vec![3, 1], suggest_substitutes(monday, 2, 0, 3, &db).unwrap().iter().map(|c| c.teacher_id).collect::<Vec<_>>());
+        manipulate_database(MainpulationType::Insert(POST::Availability(Some((1, 1, 3, Availability::Unavailable)))), &db).unwrap();
+        assert_eq!(vec![3], suggest_substitutes(monday, 2, 0, 3, &db).unwrap().iter().map(|c| c.teacher_id).collect::<Vec<_>>());
+    }
+    #[test]
+    fn room_warnings(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Class(Some((1, "class1".to_string(), Some(30))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((1, "classroom1".to_string(), None, Some(20))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((2, "classroom2".to_string(), None, Some(30))))), &db).unwrap();
+        for feature in ["lab", "projector"]{
+            manipulate_database(MainpulationType::Insert(POST::SubjectFeature(Some((1, feature.to_string())))), &db).unwrap();
+        }
+        manipulate_database(MainpulationType::Insert(POST::ClassroomFeature(Some((2, "lab".to_string())))), &db).unwrap();
+
+        assert_eq!(
+            vec![RoomWarning::TooSmall{capacity: 20, students: 30}, RoomWarning::MissingFeature("lab".to_string()), RoomWarning::MissingFeature("projector".to_string())],
+            get_room_warnings(&(1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap()
+        );
+        // lesson is inserted despite warnings
+        assert_eq!(Ok("msat/201-Created&AMOUNT=1&warning0=missing_feature&feature0=projector".to_string()), insert_lesson((1, 1, 2, 1, 1, 1, 1, 1, 0, 0), &db));
+        assert_eq!(vec!["lab".to_string()], get_classroom_features(2, &db).unwrap());
+
+        manipulate_database(MainpulationType::Delete(Delete::SubjectFeature{subject_id: 1, feature: "projector".to_string()}), &db).unwrap();
+        assert!(get_room_warnings(&(1, 1, 2, 1, 1, 1, 1, 1, 0, 0), &db).unwrap().is_empty());
+        assert_eq!(vec!["lab".to_string()], get_subject_features(1, &db).unwrap());
+
+        // updating names without students, corridor or capacity keeps them
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((1, "classroom1".to_string(), Some(2), Some(20))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Class(Some((1, "1a".to_string(), None)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((1, "room 1".to_string(), None, None)))), &db).unwrap();
+        let classroom : (Option<u16>, Option<u16>) = db.query_row("SELECT corridor, capacity FROM Classrooms WHERE classroom_id = 1", [], 
+            |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!((Some(2), Some(20)), classroom);
+        assert_eq!(vec![RoomWarning::TooSmall{capacity: 20, students: 30}], get_room_warnings(&(1, 1, 1, 1, 2, 1, 1, 1, 0, 0), &db).unwrap());
+    }
+    #[test]
+    fn now_and_next(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 2, 3, 1, 1, 0, 0), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Duty(Some((1, 1, 1, 1, 1, 1, 0)))), &db).unwrap();
+        let at = |date: &str, time: &str| moment_or_now(Some(&date.to_string()), Some(&time.to_string())).unwrap();
+        let names = |now_and_next: NowAndNext| (now_and_next.current.as_str(), now_and_next.next.as_str());
+
+        let during_lesson = get_now_and_next(TimetableOwner::Teacher(1), at("2025-10-06", "08:10"), &db).unwrap();
+        assert!(matches!(&during_lesson.current, Activity::Lesson(lesson) if lesson.subject == Some("subject1".to_string())));
+        assert_eq!(("lesson", "duty"), names(during_lesson));
+        // duties are only for teachers
+        assert_eq!(("lesson", "lesson"), names(get_now_and_next(TimetableOwner::Class(1), at("2025-10-06", "08:10"), &db).unwrap()));
+        assert_eq!(("free_period", "lesson"), names(get_now_and_next(TimetableOwner::Teacher(1), at("2025-10-06", "09:10"), &db).unwrap()));
+        assert_eq!(("before_school", "lesson"), names(get_now_and_next(TimetableOwner::Classroom(1), at("2025-10-06", "07:00"), &db).unwrap()));
+        assert_eq!(("before_school", "lesson"), names(get_now_and_next(TimetableOwner::Teacher(1), at("2025-10-06", "07:59"), &db).unwrap()));
+        assert_eq!(("after_school", "after_school"), names(get_now_and_next(TimetableOwner::Teacher(1), at("2025-10-06", "11:00"), &db).unwrap()));
+        // 2025-10-11 is Saturday
+        assert_eq!(("no_school", "no_school"), names(get_now_and_next(TimetableOwner::Teacher(1), at("2025-10-11", "08:10"), &db).unwrap()));
+    }
+    #[test]
+    fn free_classrooms(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Semester(Some((1, "semester1".to_string(), 
+            "2025-09-01T00:00:00.000Z".to_string(), "2026-01-31T00:00:00.000Z".to_string(), 2)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((2, "classroom2".to_string(), None, Some(30))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Classroom(Some((3, "classroom3".to_string(), None, Some(20))))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::ClassroomFeature(Some((2, "projector".to_string())))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        // classroom 2 is used only in second week of cycle
+        insert_lesson((1, 2, 2, 2, 1, 1, 1, 1, 2, 0), &db).unwrap();
+
+        let ids = |classrooms: Vec<(u16, String, Option<u16>)>| classrooms.iter().map(|c| c.0).collect::<Vec<u16>>();
+        assert_eq!(vec![3], ids(get_free_classrooms(1, 1, 1, 1, 0, &ClassroomFilter::default(), &db).unwrap()));
+        assert_eq!(vec![2, 3], ids(get_free_classrooms(1, 1, 1, 1, 1, &ClassroomFilter::default(), &db).unwrap()));
+        assert_eq!(vec![2], ids(get_free_classrooms(1, 2, 1, 1, 0, &ClassroomFilter{min_capacity: Some(25), features: vec![]}, &db).unwrap()));
+        assert_eq!(vec![2], ids(get_free_classrooms(1, 2, 1, 1, 0, &ClassroomFilter{min_capacity: None, features: vec!["projector".to_string()]}, &db).unwrap()));
+        // 2025-10-06 is Monday of the second week of semester
+        let moment = moment_or_now(Some(&"2025-10-06".to_string()), Some(&"08:10".to_string())).unwrap();
+        assert_eq!(vec![3], ids(get_free_classrooms_at(moment, &ClassroomFilter::default(), &db).unwrap()));
+        let moment = moment_or_now(Some(&"2025-10-06".to_string()), Some(&"08:50".to_string())).unwrap();
+        assert_eq!(Err(SQLiteError::QueryReturnedNoRows), get_free_classrooms_at(moment, &ClassroomFilter::default(), &db));
+    }
+    #[test]
+    fn room_bookings(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        let monday = parse_date("2025-10-06").unwrap();
+        // lesson hour 1 takes place from 8:00 to 8:45
+        let conflict = insert_booking((1, 1, monday, 8, 30, 9, 30, 2, "meeting".to_string()), &db);
+        assert!(matches!(conflict, Err(ServerError::RoomConflict(ref entries)) if entries.len() == 1 && entries[0].booking_id.is_none()));
+        insert_booking((1, 1, monday, 8, 45, 9, 30, 2, "meeting".to_string()), &db).unwrap();
+        assert!(matches!(insert_booking((2, 1, monday, 9, 0, 10, 0, 3, "exam".to_string()), &db), 
+            Err(ServerError::RoomConflict(ref entries)) if entries[0].booking_id == Some(1)));
+        // booking can be moved without clashing with itself
+        insert_booking((1, 1, monday, 9, 0, 9, 45, 2, "meeting".to_string()), &db).unwrap();
+
+        let schedule = get_room_schedule(1, monday, &db).unwrap();
+        assert_eq!(vec![(None, "subject1 - class1".to_string()), (Some(1), "meeting".to_string())], 
+            schedule.iter().map(|entry| (entry.booking_id, entry.title.clone())).collect::<Vec<_>>());
+        // lesson hour 2 (9:00 - 9:45) is free from lessons, but classroom is booked
+        let moment = moment_or_now(Some(&"2025-10-06".to_string()), Some(&"09:10".to_string())).unwrap();
+        assert_eq!(vec![2, 3], get_free_classrooms_at(moment, &ClassroomFilter::default(), &db).unwrap().iter().map(|c| c.0).collect::<Vec<_>>());
+
+        manipulate_database(MainpulationType::Delete(Delete::Booking{booking_id: 1}), &db).unwrap();
+        assert_eq!(1, get_room_schedule(1, monday, &db).unwrap().len());
+    }
+    #[test]
+    fn school_events(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 2, 2, 2, 2, 2, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 3, 3, 3, 3, 3, 1, 1, 0, 0), &db).unwrap();
+        let monday = parse_date("2025-10-06").unwrap();
+        // exam of class 1 supervised by teacher 2 from 8:00 to 9:30
+        manipulate_database(MainpulationType::Insert(POST::Event(Some((1, "exam".to_string(), EventType::Exam, monday, 8, 0, 9, 30, true)))), &db).unwrap();
+        for participant in [TimetableOwner::Class(1), TimetableOwner::Teacher(2), TimetableOwner::Classroom(2)]{
+            manipulate_database(MainpulationType::Insert(POST::EventParticipant(Some((1, participant)))), &db).unwrap();
+        }
+        // parent meeting doesn't cancel lessons of class 3
+        manipulate_database(MainpulationType::Insert(POST::Event(Some((2, "meeting".to_string(), EventType::ParentMeeting, monday, 10, 0, 11, 0, false)))), &db).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::EventParticipant(Some((2, TimetableOwner::Class(3))))), &db).unwrap();
+
+        let statuses = |owner| get_day_schedule(owner, monday, &db).unwrap().lessons.iter().map(|l| l.status).collect::<Vec<_>>();
+        assert_eq!(vec![LessonStatus::Cancelled], statuses(TimetableOwner::Class(1)));
+        assert_eq!(vec![LessonStatus::Cancelled], statuses(TimetableOwner::Teacher(2)));
+        assert_eq!(vec![LessonStatus::Regular], statuses(TimetableOwner::Class(3)));
+
+        let schedule = get_day_schedule(TimetableOwner::Class(1), monday, &db).unwrap();
+        assert_eq!(vec![1], schedule.events.iter().map(|e| e.event_id).collect::<Vec<_>>());
+        assert_eq!(Some("teacher2".to_string()), schedule.events[0].teachers);
+        assert_eq!(2, get_events(monday, None, &db).unwrap().len());
+        // event takes place in classroom 2 during lesson hour 1
+        assert!(insert_booking((1, 2, monday, 8, 0, 8, 45, 1, "meeting".to_string()), &db).is_err());
+        // teacher 2 can't substitute during exam
+        assert_eq!(vec![3], suggest_substitutes(monday, 1, 0, 1, &db).unwrap().iter().map(|c| c.teacher_id).collect::<Vec<_>>());
+
+        manipulate_database(MainpulationType::Delete(Delete::Event{event_id: 1}), &db).unwrap();
+        assert_eq!(vec![LessonStatus::Regular], statuses(TimetableOwner::Class(1)));
+    }
+    #[test]
+    fn lesson_overrides(){
+        let db = test_db();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 2, 2, 2, 2, 1, 1, 1, 0, 0), &db).unwrap();
+        let monday = parse_date("2025-10-06").unwrap();
+        let next_monday = parse_date("2025-10-13").unwrap();
+        let set_override = |class_id, cancelled, classroom_id, new_lesson_hour, teacher_id| {
+            manipulate_database(MainpulationType::Insert(POST::LessonOverride(Some(
+                (monday, class_id, 1, 0, cancelled, classroom_id, new_lesson_hour, teacher_id)))), &db).unwrap();
+        };
+        let lessons = |owner, date| get_day_schedule(owner, date, &db).unwrap().lessons;
+        let conflicts = |lesson_override| match insert_lesson_override(lesson_override, &db){
+            Err(ServerError::LessonConflict(conflicts)) => conflicts.iter().map(|c| (c.kind, c.class_id)).collect::<Vec<_>>(),
+            _ => vec![]
+        };
+
+        // classroom 2 and teacher 2 are used by class 2 at that time
+        assert_eq!(vec![(ConflictKind::Classroom, 2)], conflicts((monday, 1, 1, 0, false, Some(2), None, None)));
+        assert_eq!(vec![(ConflictKind::Teacher, 2)], conflicts((monday, 1, 1, 0, false, None, None, Some(2))));
+        insert_lesson((1, 1, 3, 3, 3, 2, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![(ConflictKind::Class, 1)], conflicts((monday, 1, 1, 0, false, None, Some(2), None)));
+        manipulate_database(MainpulationType::Delete(Delete::Lesson{class: 1, weekd: 1, lessonh: 2, semester: 1, academic_year: 1, week: 0, group_id: 0}), &db).unwrap();
+
+        // class 1 moves to classroom 3 for one day
+        insert_lesson_override((monday, 1, 1, 0, false, Some(3), None, None), &db).unwrap();
+        let moved = lessons(TimetableOwner::Class(1), monday);
+        assert_eq!((LessonStatus::Changed, Some("classroom3".to_string())), (moved[0].status, moved[0].classroom.clone()));
+        assert_eq!(LessonStatus::Regular, lessons(TimetableOwner::Class(1), next_monday)[0].status);
+        assert!(lessons(TimetableOwner::Classroom(1), monday).is_empty());
+        assert_eq!(1, lessons(TimetableOwner::Classroom(3), monday).len());
+        let moment = moment_or_now(Some(&"2025-10-06".to_string()), Some(&"08:10".to_string())).unwrap();
+        assert_eq!(vec![1], get_free_classrooms_at(moment, &ClassroomFilter::default(), &db).unwrap().iter().map(|c| c.0).collect::<Vec<_>>());
+
+        // teacher 3 takes lesson of class 2, so absence of teacher 2 doesn't need cover
+        set_override(2, false, None, None, Some(3));
+        manipulate_database(MainpulationType::Insert(POST::Absence(Some((2, monday)))), &db).unwrap();
+        assert!(lessons(TimetableOwner::Teacher(2), monday).is_empty());
+        assert_eq!(vec![LessonStatus::Substitution], lessons(TimetableOwner::Teacher(3), monday).iter().map(|l| l.status).collect::<Vec<_>>());
+        assert!(get_cover_lessons(monday, &db).unwrap().is_empty());
+        // teacher 3 is busy with lesson of class 2
+        assert!(suggest_substitutes(monday, 1, 0, 1, &db).unwrap().is_empty());
+
+        // lesson of class 1 moved to lesson hour 3
+        set_override(1, false, None, Some(3), None);
+        let moved = lessons(TimetableOwner::Class(1), monday);
+        assert_eq!((Some(3), Some(10)), (moved[0].lessonh.lesson_hour, moved[0].lessonh.start_hour));
+
+        set_override(1, true, None, None, None);
+        assert_eq!(LessonStatus::Cancelled, lessons(TimetableOwner::Class(1), monday)[0].status);
+        assert!(get_room_schedule(1, monday, &db).unwrap().is_empty());
+
+        manipulate_database(MainpulationType::Delete(Delete::LessonOverride{date: monday, class_id: 1, lesson_hour: 1, group_id: 0}), &db).unwrap();
+        assert_eq!(LessonStatus::Regular, lessons(TimetableOwner::Class(1), monday)[0].status);
+    }
+    #[test]
+    fn bell_schedules(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 0, 0), &db).unwrap();
+        insert_duty((1, 1, 1, 1, 1, 1, 0), &db).unwrap();
+        let monday = parse_date("2025-10-06").unwrap();
+        let next_monday = parse_date("2025-10-13").unwrap();
+        // shortened lessons, lesson hour 3 keeps its regular time
+        for post in [
+            POST::BellSchedule     (Some((1, "shortened".to_string()))),
+            POST::BellScheduleHour (Some((1, 1, 8, 0, 8, 30))),
+            POST::BellScheduleHour (Some((1, 2, 8, 40, 9, 10))),
+            POST::BellScheduleBreak(Some((1, 1, 8, 30, 8, 40))),
+            POST::ScheduleDay      (Some((monday, 1)))
+        ]{
+            manipulate_database(MainpulationType::Insert(post), &db).unwrap();
+        }
+        assert_eq!(Some((1, "shortened".to_string())), get_bell_schedule_for_date(monday, &db).unwrap());
+        assert_eq!(None, get_bell_schedule_for_date(next_monday, &db).unwrap());
+        let times = |hours: Vec<JoinedHour>| hours.iter().map(|h| (h.lesson_hour.unwrap(), h.start_hour.unwrap(), h.start_minute.unwrap())).collect::<Vec<_>>();
+        assert_eq!(vec![(1, 8, 0), (2, 8, 40), (3, 10, 0)], times(get_lesson_hours_for_date(monday, &db).unwrap()));
+        assert_eq!(vec![(1, 8, 30)], times(get_breaks_for_date(monday, &db).unwrap()));
+        assert_eq!(vec![(1, 8, 45)], times(get_breaks_for_date(next_monday, &db).unwrap()));
+
+        let start = |date| get_day_schedule(TimetableOwner::Class(1), date, &db).unwrap().lessons
+            .iter().map(|l| (l.lessonh.start_hour.unwrap(), l.lessonh.start_minute.unwrap())).collect::<Vec<_>>();
+        assert_eq!(vec![(8, 40)], start(monday));
+        assert_eq!(vec![(9, 0)], start(next_monday));
+
+        let moment = |time: &str| moment_or_now(Some(&"2025-10-06".to_string()), Some(&time.to_string())).unwrap();
+        assert!(matches!(get_now_and_next(TimetableOwner::Teacher(1), moment("08:35"), &db).unwrap().current, Activity::Duty(_)));
+        assert_eq!(vec![2, 3], get_free_classrooms_at(moment("09:05"), &ClassroomFilter::default(), &db).unwrap().iter().map(|c| c.0).collect::<Vec<_>>());
+
+        manipulate_database(MainpulationType::Delete(Delete::BellSchedule{schedule_id: 1}), &db).unwrap();
+        assert_eq!(vec![(9, 0)], start(monday));
+    }
+    #[test]
+    fn weekday_hours(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((5, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        // lessons on friday are 40 minutes long
+        for post in [POST::WeekdayHour(Some((5, 1, 8, 0, 8, 40))), POST::WeekdayBreak(Some((5, 1, 8, 40, 8, 50)))]{
+            manipulate_database(MainpulationType::Insert(post), &db).unwrap();
+        }
+        let (monday, friday, next_friday) = (parse_date("2025-10-06").unwrap(), parse_date("2025-10-10").unwrap(), parse_date("2025-10-17").unwrap());
+        let end = |date| get_day_schedule(TimetableOwner::Class(1), date, &db).unwrap().lessons
+            .iter().map(|l| (l.lessonh.end_hour.unwrap(), l.lessonh.end_minutes.unwrap())).collect::<Vec<_>>();
+        assert_eq!(vec![(8, 45)], end(monday));
+        assert_eq!(vec![(8, 40)], end(friday));
+        let week = get_lessons_by_class_id(1, monday, &db).unwrap();
+        assert_eq!(vec![(1, 45), (5, 40)], week.iter().map(|l| (l.weekday.unwrap(), l.lessonh.end_minutes.unwrap())).collect::<Vec<_>>());
+        assert_eq!(Some(40), get_breaks_for_date(friday, &db).unwrap()[0].start_minute);
+        assert_eq!(Some(45), get_breaks_for_date(monday, &db).unwrap()[0].start_minute);
+
+        // bell schedule used on date is more important than times of weekday
+        for post in [POST::BellSchedule(Some((1, "shortened".to_string()))), POST::BellScheduleHour(Some((1, 1, 8, 0, 8, 30))), POST::ScheduleDay(Some((friday, 1)))]{
+            manipulate_database(MainpulationType::Insert(post), &db).unwrap();
+        }
+        assert_eq!(vec![(8, 30)], end(friday));
+        assert_eq!(vec![(8, 40)], end(next_friday));
+        // bell schedule doesn't change breaks, so break of friday is used
+        assert_eq!(Some(40), get_breaks_for_date(friday, &db).unwrap()[0].start_minute);
+
+        manipulate_database(MainpulationType::Delete(Delete::WeekdayHour{weekday: 5, lesson_hour: 1}), &db).unwrap();
+        assert_eq!(vec![(8, 45)], end(next_friday));
+    }
+    #[test]
+    fn double_periods(){
+        let db = test_db();
+        insert_lesson((1, 2, 2, 2, 2, 2, 1, 1, 0, 0), &db).unwrap();
+        // teacher 2 is busy in second hour of double period
+        let conflict = insert_lesson_block((1, 1, 1, 2, 2, 1, 1, 1, 0, 0), 2, &db).unwrap_err();
+        assert!(matches!(conflict, ServerError::LessonConflict(_)));
+        assert!(conflict.to_response().contains("&type0=teacher&weekday0=1&lesson_hour0=2&class_id0=2"));
+        let count : u16 = db.query_row("SELECT COUNT(*) FROM Lessons WHERE class_id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(0, count);
+        insert_lesson_block((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), 2, &db).unwrap();
+
+        let monday = parse_date("2025-10-06").unwrap();
+        let lessons = get_lessons_by_class_id(1, monday, &db).unwrap();
+        assert_eq!(vec![(Some(1), Some((1, 2))), (Some(2), Some((1, 2)))], 
+            lessons.iter().map(|l| (l.lessonh.lesson_hour, l.block)).collect::<Vec<_>>());
+        // both hours are booked
+        let conflicts = get_lesson_conflicts(&(1, 3, 1, 3, 3, 2, 1, 1, 0, 0), &db).unwrap();
+        assert_eq!(vec![ConflictKind::Classroom], conflicts.iter().map(|c| c.kind).collect::<Vec<_>>());
+
+        // single lesson replacing one hour of double period replaces all of it
+        insert_lesson((1, 1, 3, 3, 3, 2, 1, 1, 0, 0), &db).unwrap();
+        let lessons = get_lessons_by_class_id(1, monday, &db).unwrap();
+        assert_eq!(vec![(Some(2), None)], lessons.iter().map(|l| (l.lessonh.lesson_hour, l.block)).collect::<Vec<_>>());
+
+        // there are only 3 lesson hours
+        assert_eq!(Err(ServerError::NoLessonHour{lesson_hour: 4}), insert_lesson_block((1, 1, 1, 1, 1, 2, 1, 1, 0, 0), 3, &db));
+        assert_eq!(Err(ServerError::NoLessonHour{lesson_hour: 4}), insert_draft_lesson((1, 1, 1, 1, 1, 3, 1, 1, 0, 0), 2, &db));
+        assert!(matches!(insert_lesson_block((1, 1, 1, 1, 1, 3, 1, 1, 0, 0), u16::MAX, &db), Err(ServerError::InvalidRequest(_))));
+        insert_lesson_block((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), 3, &db).unwrap();
+        manipulate_database(MainpulationType::Delete(Delete::Lesson { class: 1, weekd: 1, lessonh: 3, semester: 1, academic_year: 1, week: 0, group_id: 0 }), &db).unwrap();
+        for table in ["Lessons WHERE class_id = 1", "LessonBlocks"]{
+            let count : u16 = db.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0)).unwrap();
+            assert_eq!(0, count);
+        }
+    }
+    #[test]
+    fn year_rollover(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        for post in [
+            POST::Class(Some((4, "1a".to_string(), Some(20)))), 
+            POST::Class(Some((5, "4a".to_string(), None))), 
+            POST::Group(Some((1, 4, "group1".to_string()))),
+            POST::Duty (Some((1, 1, 1, 1, 1, 1, 0)))
+        ]{
+            manipulate_database(MainpulationType::Insert(post), &db).unwrap();
+        }
+        insert_lesson((1, 4, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 5, 2, 2, 2, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson_block((1, 4, 3, 3, 3, 2, 1, 1, 0, 1), 2, &db).unwrap();
+        insert_co_teacher((1, 4, 2, 1, 1, 0, 1), 1, &db).unwrap();
+        let expected = RolloverReport{
+            academic_year : 2,
+            semesters     : vec![(2, "semester1 year2".to_string())],
+            promoted      : vec![("1a".to_string(), "2a".to_string())],
+            archived      : vec!["4a".to_string()],
+            lessons       : 3,
+            duties        : 1
+        };
+        let class = |class_id| -> (String, Option<u16>, bool) { db.query_row("SELECT class_name, students, archived FROM Classes WHERE class_id = ?1", 
+            [class_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))).unwrap() };
+
+        // preview doesn't change anything
+        assert_eq!(expected, rollover_year(1, "year2", 4, true, true, &db).unwrap());
+        let years : u8 = db.query_row("SELECT COUNT(*) FROM Years", [], |row| row.get(0)).unwrap();
+        assert_eq!(1, years);
+        assert_eq!(("1a".to_string(), Some(20), false), class(4));
+
+        assert_eq!(expected, rollover_year(1, "year2", 4, true, false, &db).unwrap());
+        let start : String = db.query_row("SELECT start_date FROM Semesters WHERE semester = 2", [], |row| row.get(0)).unwrap();
+        assert_eq!("2026-09-01T00:00:00.000Z", start);
+        // classes of old year keep their names, so past timetables don't change
+        assert_eq!(("1a".to_string(), Some(20), true), class(4));
+        assert_eq!(("4a".to_string(), None, true), class(5));
+        assert_eq!(("2a".to_string(), Some(20), false), class(6));
+        let monday = parse_date("2025-10-06").unwrap();
+        assert!(get_lessons_by_class_id(4, monday, &db).unwrap().iter().all(|l| l.class == Some("1a".to_string())));
+        let group : (u16, u16, String) = db.query_row("SELECT group_id, class_id, group_name FROM ClassGroups WHERE class_id = 6", [], 
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))).unwrap();
+        assert_eq!((2, 6, "group1".to_string()), group);
+
+        let draft = get_draft(2, 2, None, &db).unwrap();
+        assert_eq!(vec![(Some("2a".to_string()), None, None), (Some("2a".to_string()), Some("group1".to_string()), Some((2, 3))), 
+            (Some("2a".to_string()), Some("group1".to_string()), Some((2, 3)))], 
+            draft.iter().map(|l| (l.class.clone(), l.group.clone(), l.block)).collect::<Vec<_>>());
+        assert_eq!(Some("teacher1".to_string()), draft[1].co_teachers);
+        apply_draft(2, 2, &db).unwrap();
+        for (table, expected) in [("Lessons", 3), ("LessonTeachers", 1), ("LessonBlocks", 1), ("Duties", 1)]{
+            let count : u16 = db.query_row(&format!("SELECT COUNT(*) FROM {table} WHERE semester = 2 AND academic_year = 2"), [], |row| row.get(0)).unwrap();
+            assert_eq!(expected, count);
+        }
+
+        // archived classes aren't promoted again
+        let report = rollover_year(2, "year3", 4, false, false, &db).unwrap();
+        assert_eq!((vec![("2a".to_string(), "3a".to_string())], vec![], 0), (report.promoted, report.archived, report.lessons));
+
+        // ids of new semesters can't overflow
+        for (semester, start_date, end_date) in [(254, "2040-09-01", "2041-01-31"), (200, "2026-02-01", "2026-06-29")]{
+            manipulate_database(MainpulationType::Insert(POST::Semester(Some((semester, format!("semester{semester}"), 
+                format!("{start_date}T00:00:00.000Z"), format!("{end_date}T00:00:00.000Z"), 1)))), &db).unwrap();
+        }
+        assert_eq!(Err(SQLiteError::IntegralValueOutOfRange(0, 256)), rollover_year(1, "year4", 4, false, false, &db));
+    }
+    #[test]
+    fn unique_class_names_migration(){
+        let db = Database::open_in_memory().unwrap();
+        db.execute_batch("CREATE TABLE Classes (class_id INTEGER PRIMARY KEY, class_name TEXT NOT NULL UNIQUE);
+            INSERT INTO Classes VALUES (1, '1a');").unwrap();
+        create_tables(&db).unwrap();
+        db.execute("UPDATE Classes SET archived = 1 WHERE class_id = 1", []).unwrap();
+        manipulate_database(MainpulationType::Insert(POST::Class(Some((2, "1a".to_string(), None)))), &db).unwrap();
+        // names of classes that aren't archived are still unique
+        assert!(manipulate_database(MainpulationType::Insert(POST::Class(Some((3, "1a".to_string(), None)))), &db).is_err());
+    }
+    #[test]
+    fn draft_starts_from_published_timetable(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 2, 2, 2, 2, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_duty((1, 1, 1, 1, 1, 1, 0), &db).unwrap();
+        let count = |table: &str| -> u16 { db.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0)).unwrap() };
+
+        // first change of class or duties in draft copies its published lessons or duties
+        insert_draft_lesson((1, 1, 3, 3, 3, 3, 1, 1, 0, 0), 1, &db).unwrap();
+        assert_eq!(3, count("DraftLessons"));
+        insert_draft_duty((1, 1, 3, 3, 1, 1, 0), &db).unwrap();
+        assert_eq!(2, count("DraftDuties"));
+        apply_draft(1, 1, &db).unwrap();
+        assert_eq!((4, 2), (count("Lessons"), count("Duties")));
+
+        // generated duties go into draft while semester has one
+        insert_draft_lesson((1, 2, 2, 2, 2, 2, 1, 1, 0, 0), 1, &db).unwrap();
+        let rosters = generate_duties(1, 1, &db).unwrap();
+        // only second corridor is free on monday
+        assert_eq!(vec![(2, 2)], rosters[0].1.duties.iter().map(|d| (d.teacher_id, d.place_id)).collect::<Vec<_>>());
+        assert_eq!((2, 3), (count("Duties"), count("DraftDuties")));
+        apply_draft(1, 1, &db).unwrap();
+        assert_eq!((5, 3), (count("Lessons"), count("Duties")));
+    }
+    #[test]
+    fn draft_versions(){
+        let db = test_db();
+        manipulate_database(MainpulationType::Insert(POST::Break(Some((1, 8, 45, 8, 55)))), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 1, 1, 1, 0, 0), &db).unwrap();
+        insert_lesson((1, 1, 1, 1, 1, 2, 1, 1, 0, 0), &db).unwrap();
+        insert_co_teacher((1, 1, 1, 1, 1, 0, 0), 3, &db).unwrap();
+        insert_co_teacher((1, 1, 2, 1, 1, 0, 0), 2, &db).unwrap();
+        insert_duty((1, 1, 1, 1, 1, 1, 0), &db).unwrap();
+        assert!(!has_draft(1, 1, &db).unwrap());
+        copy_to_draft(1, 1, &db).unwrap();
+        assert!(has_draft(1, 1, &db).unwrap());
+        assert_eq!(DraftChanges::default(), get_draft_changes(1, 1, &db).unwrap());
+
+        insert_draft_lesson((1, 1, 2, 2, 2, 1, 1, 1, 0, 0), 1, &db).unwrap();
+        insert_draft_lesson((1, 1, 3, 3, 3, 2, 1, 1, 0, 0), 2, &db).unwrap();
+        // whole multi-slot lesson is deleted together with its co-teachers
+        manipulate_database(MainpulationType::Delete(Delete::DraftLesson { class: 1, weekd: 1, lessonh: 3, semester: 1, academic_year: 1, week: 0, group_id: 0 }), &db).unwrap();
+        let draft_hours : usize = db.query_row("SELECT COUNT(*) FROM DraftLessons", [], |row| row.get(0)).unwrap();
+        assert_eq!(1, draft_hours);
+        insert_draft_lesson((1, 1, 3, 3, 3, 2, 1, 1, 0, 0), 2, &db).unwrap();
+        assert!(matches!(insert_draft_co_teacher((1, 1, 2, 1, 1, 0, 0), 3, &db), Err(ServerError::LessonConflict(_))));
+        insert_draft_co_teacher((1, 1, 2, 1, 1, 0, 0), 1, &db).unwrap();
+        insert_draft_duty((1, 1, 1, 2, 1, 1, 0), &db).unwrap();
+        // published timetable doesn't change until draft is applied
+        let monday = parse_date("2025-10-06").unwrap();
+        let published = |db: &Database| get_lessons_by_class_id(1, monday, db).unwrap().into_iter()
+            .map(|l| (l.lessonh.lesson_hour.unwrap(), l.teacher.unwrap())).collect::<Vec<_>>();
+        assert_eq!(vec![(1, "teacher1".to_string()), (2, "teacher1".to_string())], published(&db));
+        let draft = get_draft(1, 1, None, &db).unwrap();
+        assert_eq!(vec![None, Some((2, 3)), Some((2, 3))], draft.iter().map(|l| l.block).collect::<Vec<_>>());
+        assert_eq!(Some("teacher1".to_string()), draft[1].co_teachers);
+
+        let lesson = |hour, published: Option<&str>, draft: Option<&str>| DraftChange{
+            weekday   : 1, 
+            hour, 
+            owner     : "class1".to_string(), 
+            published : published.map(str::to_string),
+            draft     : draft.map(str::to_string)
+        };
+        let changes = get_draft_changes(1, 1, &db).unwrap();
+        assert_eq!(vec![
+            lesson(1, Some("subject1, teacher1, classroom1 + teacher3"), Some("subject2, teacher2, classroom2 + teacher3")), 
+            lesson(2, Some("subject1, teacher1, classroom1 + teacher2"), Some("subject3, teacher3, classroom3 + teacher1 (2-3)")), 
+            lesson(3, None, Some("subject3, teacher3, classroom3 (2-3)"))
+        ], changes.lessons);
+        assert_eq!(vec![DraftChange{weekday: 1, hour: 1, owner: "teacher1".to_string(), 
+            published: Some("corridor1".to_string()), draft: Some("corridor2".to_string())}], changes.duties);
+
+        apply_draft(1, 1, &db).unwrap();
+        assert_eq!(vec![(1, "teacher2".to_string()), (2, "teacher3".to_string()), (3, "teacher3".to_string())], published(&db));
+        // co-teachers and multi-slot lessons are replaced with ones from draft
+        let mut stmt = db.prepare("SELECT lesson_hour, teacher_id FROM LessonTeachers ORDER BY lesson_hour").unwrap();
+        let co_teachers = stmt.query_map([], |row| Ok((row.get::<usize, u16>(0)?, row.get::<usize, u16>(1)?))).unwrap()
+            .collect::<Result<Vec<_>, SQLiteError>>().unwrap();
+        assert_eq!(vec![(1, 3), (2, 1)], co_teachers);
+        let block : (u16, u16) = db.query_row("SELECT lesson_hour, last_hour FROM LessonBlocks", [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!((2, 3), block);
+        let place : u16 = db.query_row("SELECT place_id FROM Duties", [], |row| row.get(0)).unwrap();
+        assert_eq!(2, place);
+        assert!(!has_draft(1, 1, &db).unwrap());
+        assert_eq!(DraftChanges::default(), get_draft_changes(1, 1, &db).unwrap());
+    }
 }
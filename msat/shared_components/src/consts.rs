@@ -10,6 +10,8 @@ pub const ERROR   : &str = "[  ERROR  ]";
 pub const SUCCESS : &str = "[   OK   ] ";
 pub const VERSION : u16  = 50;
 pub const SUPPORTED_VERSIONS : [u16; 1] = [50];
+/// Weekdays (1 = Monday) on which lessons take place
+pub const SCHOOL_DAYS : [u8; 5] = [1, 2, 3, 4, 5];
 pub const CLEAR   : &str = 
     if cfg!(windows)
     {
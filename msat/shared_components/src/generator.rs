@@ -0,0 +1,440 @@
+//==============================================
+//                 generator.rs
+// Solvers that build timetables and duty
+// rosters instead of entering every row by 
+// hand. They don't touch database, see
+// backend.rs for loading and saving data
+//==============================================
+
+use std::collections::{
+    HashMap,
+    HashSet
+};
+
+/// How many times solver may place a lesson
+/// before it gives up and returns best result
+const SEARCH_LIMIT : usize = 20_000;
+
+/// Weekly amount of lessons of one subject for one class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Requirement{
+    pub class_id     : u16,
+    pub subject_id   : u16,
+    pub teacher_id   : u16,
+    /// None means that any classroom can be used
+    pub classroom_id : Option<u16>,
+    pub hours        : u8
+}
+
+/// Lesson that stays as it is and only
+/// occupies its teacher and classroom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedLesson{
+    pub weekday      : u8,
+    pub lesson_hour  : u16,
+    pub teacher_id   : u16,
+    pub classroom_id : u16
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimetableProblem{
+    pub requirements : Vec<Requirement>,
+    pub weekdays     : Vec<u8>,
+    pub lesson_hours : Vec<u16>,
+    pub classrooms   : Vec<u16>,
+    pub fixed        : Vec<FixedLesson>,
+    /// (teacher_id, weekday, lesson_hour) in which teacher can't teach
+    pub unavailable  : Vec<(u16, u8, u16)>
+}
+
+/// One lesson of requirement (index into `TimetableProblem::requirements`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement{
+    pub requirement  : usize,
+    pub weekday      : u8,
+    pub lesson_hour  : u16,
+    pub classroom_id : u16
+}
+
+/// Result of generating draft timetable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DraftReport{
+    pub placed   : usize,
+    /// requirements with hours that couldn't be placed
+    pub unplaced : Vec<(Requirement, u8)>
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimetableSolution{
+    pub placements : Vec<Placement>,
+    /// (requirement index, hours that couldn't be placed)
+    pub unplaced   : Vec<(usize, u8)>
+}
+
+/// (weekday, lesson_hour, classroom_id)
+type Candidate = (u8, u16, u16);
+
+#[derive(Default, Clone)]
+struct State{
+    class_busy   : HashSet<(u16, u8, u16)>,
+    teacher_busy : HashSet<(u16, u8, u16)>,
+    room_busy    : HashSet<(u16, u8, u16)>,
+    day_load     : HashMap<(u16, u8), u8>,
+    subject_day  : HashMap<(u16, u16, u8), u8>,
+    placements   : Vec<Placement>
+}
+
+struct Solver<'a>{
+    problem    : &'a TimetableProblem,
+    slots      : Vec<(u8, u16)>,
+    /// rooms that some requirement needs, so they are
+    /// given to requirements without room as last resort
+    reserved   : HashSet<u16>,
+    remaining  : Vec<u8>,
+    state      : State,
+    best       : State,
+    steps      : usize
+}
+
+/// Places every requirement into weekly grid so that no class,
+/// teacher or classroom is used twice at the same time.
+///
+/// Uses depth first search which always continues with requirement
+/// that has the fewest free slots left and tries slots that spread
+/// subject over the week and keep days compact first. If there is no
+/// complete timetable (or it wasn't found in time), requirements are
+/// placed greedily and the ones that don't fit are reported as unplaced.
+pub fn solve_timetable(problem: &TimetableProblem) -> TimetableSolution{
+    let mut state = State::default();
+    for fixed in &problem.fixed{
+        state.teacher_busy.insert((fixed.teacher_id, fixed.weekday, fixed.lesson_hour));
+        state.room_busy.insert((fixed.classroom_id, fixed.weekday, fixed.lesson_hour));
+    }
+    state.teacher_busy.extend(problem.unavailable.iter().copied());
+    let mut slots = vec![];
+    for weekday in &problem.weekdays{
+        for lesson_hour in &problem.lesson_hours{
+            slots.push((*weekday, *lesson_hour));
+        }
+    }
+    let mut solver = Solver{
+        problem,
+        slots,
+        reserved  : problem.requirements.iter().filter_map(|r| r.classroom_id).collect(),
+        remaining : problem.requirements.iter().map(|r| r.hours).collect(),
+        best      : state.clone(),
+        state,
+        steps     : 0
+    };
+    let initial = (solver.state.clone(), solver.remaining.clone());
+    if solver.search(){
+        solver.best = solver.state.clone();
+    }
+    else{
+        (solver.state, solver.remaining) = initial;
+        solver.greedy();
+    }
+
+    let mut placed = vec![0u8; problem.requirements.len()];
+    for placement in &solver.best.placements{
+        placed[placement.requirement] += 1;
+    }
+    TimetableSolution{
+        unplaced   : problem.requirements.iter().enumerate()
+            .filter(|(i, r)| placed[*i] < r.hours)
+            .map(|(i, r)| (i, r.hours - placed[i]))
+            .collect(),
+        placements : solver.best.placements
+    }
+}
+
+impl Solver<'_>{
+    /// requirement with the fewest options left (and its options)
+    fn most_constrained(&self) -> Option<(usize, Vec<Candidate>)>{
+        let mut chosen : Option<(usize, Vec<Candidate>)> = None;
+        for (i, hours) in self.remaining.iter().enumerate(){
+            if *hours == 0{
+                continue;
+            }
+            let candidates = self.candidates(i);
+            if chosen.as_ref().is_none_or(|(_, c)| candidates.len() < c.len()){
+                let dead_end = candidates.is_empty();
+                chosen = Some((i, candidates));
+                if dead_end{
+                    break;
+                }
+            }
+        }
+        chosen
+    }
+    /// returns true when every requirement was placed
+    fn search(&mut self) -> bool{
+        if self.state.placements.len() > self.best.placements.len(){
+            self.best = self.state.clone();
+        }
+        let Some((i, candidates)) = self.most_constrained() else{
+            return true;
+        };
+        for (weekday, lesson_hour, classroom_id) in candidates{
+            if self.steps >= SEARCH_LIMIT{
+                break;
+            }
+            self.steps += 1;
+            self.place(i, weekday, lesson_hour, classroom_id);
+            if self.search(){
+                return true;
+            }
+            self.unplace(i, weekday, lesson_hour, classroom_id);
+        }
+        false
+    }
+    /// places lessons without going back, skipping the ones that don't fit
+    fn greedy(&mut self){
+        while let Some((i, candidates)) = self.most_constrained(){
+            match candidates.first(){
+                Some((weekday, lesson_hour, classroom_id)) => self.place(i, *weekday, *lesson_hour, *classroom_id),
+                None => self.remaining[i] = 0
+            }
+        }
+        if self.state.placements.len() > self.best.placements.len(){
+            self.best = self.state.clone();
+        }
+    }
+    /// free (weekday, lesson_hour, classroom) for requirement, best first
+    fn candidates(&self, i: usize) -> Vec<Candidate>{
+        let requirement = &self.problem.requirements[i];
+        let mut to_return = vec![];
+        for (position, (weekday, lesson_hour)) in self.slots.iter().enumerate(){
+            let slot = (*weekday, *lesson_hour);
+            if self.state.class_busy.contains(&(requirement.class_id, slot.0, slot.1))
+            || self.state.teacher_busy.contains(&(requirement.teacher_id, slot.0, slot.1))
+            {
+                continue;
+            }
+            if let Some(classroom_id) = self.free_room(requirement, slot){
+                let score = (
+                    self.state.subject_day.get(&(requirement.class_id, requirement.subject_id, slot.0)).copied().unwrap_or(0),
+                    position % self.problem.lesson_hours.len().max(1),
+                    self.state.day_load.get(&(requirement.class_id, slot.0)).copied().unwrap_or(0),
+                    position
+                );
+                to_return.push((score, (slot.0, slot.1, classroom_id)));
+            }
+        }
+        to_return.sort_by_key(|(score, _)| *score);
+        to_return.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+    fn free_room(&self, requirement: &Requirement, (weekday, lesson_hour): (u8, u16)) -> Option<u16>{
+        if let Some(classroom_id) = requirement.classroom_id{
+            if self.state.room_busy.contains(&(classroom_id, weekday, lesson_hour)){
+                return None;
+            }
+            return Some(classroom_id);
+        }
+        let free = self.problem.classrooms.iter()
+            .filter(|room| !self.state.room_busy.contains(&(**room, weekday, lesson_hour)));
+        let (reserved, unreserved) : (Vec<&u16>, Vec<&u16>) = free.partition(|room| self.reserved.contains(room));
+        unreserved.first().or(reserved.first()).map(|room| **room)
+    }
+    fn place(&mut self, i: usize, weekday: u8, lesson_hour: u16, classroom_id: u16){
+        let requirement = &self.problem.requirements[i];
+        self.remaining[i] -= 1;
+        self.state.class_busy.insert((requirement.class_id, weekday, lesson_hour));
+        self.state.teacher_busy.insert((requirement.teacher_id, weekday, lesson_hour));
+        self.state.room_busy.insert((classroom_id, weekday, lesson_hour));
+        *self.state.day_load.entry((requirement.class_id, weekday)).or_default() += 1;
+        *self.state.subject_day.entry((requirement.class_id, requirement.subject_id, weekday)).or_default() += 1;
+        self.state.placements.push(Placement{requirement: i, weekday, lesson_hour, classroom_id});
+    }
+    fn unplace(&mut self, i: usize, weekday: u8, lesson_hour: u16, classroom_id: u16){
+        let requirement = &self.problem.requirements[i];
+        self.remaining[i] += 1;
+        self.state.class_busy.remove(&(requirement.class_id, weekday, lesson_hour));
+        self.state.teacher_busy.remove(&(requirement.teacher_id, weekday, lesson_hour));
+        self.state.room_busy.remove(&(classroom_id, weekday, lesson_hour));
+        if let Some(load) = self.state.day_load.get_mut(&(requirement.class_id, weekday)){
+            *load -= 1;
+        }
+        if let Some(count) = self.state.subject_day.get_mut(&(requirement.class_id, requirement.subject_id, weekday)){
+            *count -= 1;
+        }
+        self.state.placements.pop();
+    }
+}
+
+/// Duty in one corridor during one break
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Duty{
+    pub weekday    : u8,
+    pub break_num  : u8,
+    pub teacher_id : u16,
+    pub place_id   : u16
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DutyProblem{
+    pub weekdays   : Vec<u8>,
+    pub breaks     : Vec<u8>,
+    pub corridors  : Vec<u16>,
+    pub teachers   : Vec<u16>,
+    /// (teacher_id, weekday) on which teacher has lessons
+    pub present    : HashSet<(u16, u8)>,
+    /// corridors of lessons right before and after break
+    /// keyed by (teacher_id, weekday, break_num)
+    pub adjacent   : HashMap<(u16, u8, u8), Vec<u16>>,
+    /// (teacher_id, weekday, break_num), 0 means every weekday or break
+    pub exclusions : Vec<(u16, u8, u8)>,
+    /// duties that are already assigned
+    pub existing   : Vec<Duty>
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DutyRoster{
+    /// only newly assigned duties
+    pub duties   : Vec<Duty>,
+    /// (weekday, break_num, place_id) without teacher
+    pub unfilled : Vec<(u8, u8, u16)>
+}
+
+/// Assigns one teacher to every corridor in every break.
+///
+/// Teacher can be assigned only if they have lessons that day,
+/// aren't excluded, don't have other duty in that break and their 
+/// lessons around the break are in the same corridor. Break with 
+/// the fewest such teachers goes first and gets teacher with 
+/// the fewest duties (nearby teachers win ties).
+pub fn solve_duties(problem: &DutyProblem) -> DutyRoster{
+    let mut count : HashMap<u16, usize> = problem.teachers.iter().map(|t| (*t, 0)).collect();
+    let mut busy  : HashSet<(u16, u8, u8)> = HashSet::new();
+    let mut taken : HashSet<(u8, u8, u16)> = HashSet::new();
+    for duty in &problem.existing{
+        *count.entry(duty.teacher_id).or_default() += 1;
+        busy.insert((duty.teacher_id, duty.weekday, duty.break_num));
+        taken.insert((duty.weekday, duty.break_num, duty.place_id));
+    }
+    let mut open = vec![];
+    for weekday in &problem.weekdays{
+        for break_num in &problem.breaks{
+            for place_id in &problem.corridors{
+                if !taken.contains(&(*weekday, *break_num, *place_id)){
+                    open.push((*weekday, *break_num, *place_id));
+                }
+            }
+        }
+    }
+    let excluded = |teacher_id: u16, weekday: u8, break_num: u8| {
+        problem.exclusions.iter().any(|(t, w, b)| 
+            *t == teacher_id && (*w == 0 || *w == weekday) && (*b == 0 || *b == break_num))
+    };
+    let mut roster = DutyRoster::default();
+    while !open.is_empty(){
+        let mut chosen : Option<(usize, Vec<u16>)> = None;
+        for (i, (weekday, break_num, place_id)) in open.iter().enumerate(){
+            let eligible : Vec<u16> = problem.teachers.iter().copied().filter(|teacher_id| {
+                problem.present.contains(&(*teacher_id, *weekday))
+                && !busy.contains(&(*teacher_id, *weekday, *break_num))
+                && !excluded(*teacher_id, *weekday, *break_num)
+                && problem.adjacent.get(&(*teacher_id, *weekday, *break_num))
+                    .is_none_or(|corridors| corridors.iter().all(|c| c == place_id))
+            }).collect();
+            if chosen.as_ref().is_none_or(|(_, e)| eligible.len() < e.len()){
+                chosen = Some((i, eligible));
+            }
+        }
+        let Some((i, eligible)) = chosen else{
+            break;
+        };
+        let (weekday, break_num, place_id) = open.swap_remove(i);
+        let teacher = eligible.into_iter().min_by_key(|teacher_id| (
+            count.get(teacher_id).copied().unwrap_or(0),
+            !problem.adjacent.contains_key(&(*teacher_id, weekday, break_num)),
+            *teacher_id
+        ));
+        match teacher{
+            Some(teacher_id) => {
+                *count.entry(teacher_id).or_default() += 1;
+                busy.insert((teacher_id, weekday, break_num));
+                roster.duties.push(Duty{weekday, break_num, teacher_id, place_id});
+            }
+            None => roster.unfilled.push((weekday, break_num, place_id))
+        }
+    }
+    roster.duties.sort_by_key(|d| (d.weekday, d.break_num, d.place_id));
+    roster.unfilled.sort();
+    roster
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    fn requirement(class_id: u16, subject_id: u16, teacher_id: u16, hours: u8) -> Requirement{
+        Requirement{class_id, subject_id, teacher_id, classroom_id: None, hours}
+    }
+    #[test]
+    fn timetable_without_clashes(){
+        let problem = TimetableProblem{
+            requirements : vec![
+                requirement(1, 1, 1, 3),
+                requirement(2, 1, 1, 3),
+                requirement(1, 2, 2, 2),
+                requirement(2, 2, 2, 2),
+                Requirement{classroom_id: Some(5), ..requirement(3, 3, 3, 4)}
+            ],
+            weekdays     : vec![1, 2],
+            lesson_hours : vec![1, 2, 3],
+            classrooms   : vec![4, 5, 6],
+            fixed        : vec![FixedLesson{weekday: 1, lesson_hour: 1, teacher_id: 3, classroom_id: 4}],
+            unavailable  : vec![(3, 2, 3), (2, 1, 3)]
+        };
+        let solution = solve_timetable(&problem);
+        assert!(solution.unplaced.is_empty());
+        assert_eq!(14, solution.placements.len());
+        let mut used = HashSet::new();
+        for p in &solution.placements{
+            let r = problem.requirements[p.requirement];
+            assert!(used.insert(("class", r.class_id, p.weekday, p.lesson_hour)));
+            assert!(used.insert(("teacher", r.teacher_id, p.weekday, p.lesson_hour)));
+            assert!(used.insert(("room", p.classroom_id, p.weekday, p.lesson_hour)));
+            assert!(!(r.teacher_id == 3 && p.weekday == 1 && p.lesson_hour == 1));
+            assert!(!problem.unavailable.contains(&(r.teacher_id, p.weekday, p.lesson_hour)));
+        }
+    }
+    #[test]
+    fn reports_unplaced_hours(){
+        let problem = TimetableProblem{
+            requirements : vec![requirement(1, 1, 1, 2), requirement(2, 1, 1, 2)],
+            weekdays     : vec![1],
+            lesson_hours : vec![1, 2, 3],
+            classrooms   : vec![1, 2],
+            ..Default::default()
+        };
+        let solution = solve_timetable(&problem);
+        assert_eq!(3, solution.placements.len());
+        assert_eq!(1, solution.unplaced.iter().map(|(_, h)| *h).sum::<u8>());
+    }
+    #[test]
+    fn balanced_duties(){
+        let mut problem = DutyProblem{
+            weekdays   : vec![1, 2],
+            breaks     : vec![1, 2],
+            corridors  : vec![1],
+            teachers   : vec![1, 2, 3],
+            exclusions : vec![(3, 0, 0)],
+            existing   : vec![Duty{weekday: 1, break_num: 1, teacher_id: 1, place_id: 1}],
+            ..Default::default()
+        };
+        for teacher_id in 1..=3{
+            for weekday in 1..=2{
+                problem.present.insert((teacher_id, weekday));
+            }
+        }
+        // teacher 2 has lesson in other corridor right after monday's second break
+        problem.adjacent.insert((2, 1, 2), vec![2]);
+        let roster = solve_duties(&problem);
+        assert!(roster.unfilled.is_empty());
+        assert_eq!(3, roster.duties.len());
+        assert!(roster.duties.iter().all(|d| d.teacher_id != 3));
+        assert!(roster.duties.contains(&Duty{weekday: 1, break_num: 2, teacher_id: 1, place_id: 1}));
+        assert_eq!(2, roster.duties.iter().filter(|d| d.teacher_id == 2).count());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod backend;
 pub mod consts;
+pub mod generator;
 pub mod types;
 pub mod utils;
 pub mod visual;
@@ -80,7 +80,263 @@ pub enum ServerError{
     InvalidRequest(String),
     RequestPasswordError{entered_password: String},
     VersionNotSupported(u16),
-    DatabaseError(rusqlite::Error)
+    DatabaseError(rusqlite::Error),
+    LessonConflict(Vec<LessonConflict>),
+    /// teacher isn't allowed to teach subject (see `Qualifications`)
+    NotQualified{teacher_id: u16, subject_id: u16},
+    /// teacher is marked as unavailable at that time (see `Availability`)
+    TeacherUnavailable{teacher_id: u16, weekday: u8, lesson_hour: u16},
+    /// teacher is unavailable right before or after break (see `backend::is_available_for_break`)
+    TeacherUnavailableForBreak{teacher_id: u16, weekday: u8, break_num: u8},
+    /// classroom is already used by lessons or bookings
+    RoomConflict(Vec<RoomEntry>),
+    /// group doesn't belong to class of lesson (see `ClassGroups`)
+    GroupNotInClass{group_id: u16, class_id: u16},
+    /// lesson covers lesson hour that isn't in `LessonHours`
+    NoLessonHour{lesson_hour: u16}
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConflictKind{
+    Teacher,
+    Classroom,
+    /// class already has lesson at that time in overlapping week of week cycle
+    Class
+}
+
+impl ConflictKind{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Teacher   => "teacher",
+            Self::Classroom => "classroom",
+            Self::Class     => "class"
+        }
+    }
+}
+
+/// Lesson that already occupies resource which
+/// lesson being inserted wants to use
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LessonConflict{
+    pub kind         : ConflictKind,
+    pub weekday      : u8,
+    pub lesson_hour  : u16,
+    pub class_id     : u16,
+    pub classroom_id : u16,
+    pub teacher_id   : u16
+}
+
+/// Type of day in school calendar
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DayType{
+    /// public holiday or school break
+    Holiday,
+    /// school is open, but regular lessons don't take place
+    Exam,
+    DayOff
+}
+
+impl DayType{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Holiday => "holiday",
+            Self::Exam    => "exam",
+            Self::DayOff  => "day_off"
+        }
+    }
+    pub fn parse(day_type: &str) -> Option<Self>{
+        match day_type{
+            "holiday" => Some(Self::Holiday),
+            "exam"    => Some(Self::Exam),
+            "day_off" => Some(Self::DayOff),
+            _         => None
+        }
+    }
+}
+
+/// Type of event from events calendar
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventType{
+    Exam,
+    Trip,
+    ParentMeeting,
+    Other
+}
+
+impl EventType{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Exam          => "exam",
+            Self::Trip          => "trip",
+            Self::ParentMeeting => "parent_meeting",
+            Self::Other         => "other"
+        }
+    }
+    pub fn parse(event_type: &str) -> Option<Self>{
+        match event_type{
+            "exam"           => Some(Self::Exam),
+            "trip"           => Some(Self::Trip),
+            "parent_meeting" => Some(Self::ParentMeeting),
+            "other"          => Some(Self::Other),
+            _                => None
+        }
+    }
+}
+
+/// Exam, trip, parent meeting or other event with its participants
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SchoolEvent{
+    pub event_id          : u16,
+    pub name              : String,
+    pub event_type        : EventType,
+    /// YYYY-MM-DD
+    pub date              : String,
+    pub start_hour        : u8,
+    pub start_minute      : u8,
+    pub end_hour          : u8,
+    pub end_minute        : u8,
+    /// lessons of classes and teachers of event are cancelled during event
+    pub overrides_lessons : bool,
+    /// names of classes (separated by comma)
+    pub classes           : Option<String>,
+    /// names of teachers (separated by comma)
+    pub teachers          : Option<String>,
+    /// names of classrooms (separated by comma)
+    pub classrooms        : Option<String>
+}
+
+/// Lessons and events of teacher, class or classroom on single date
+#[derive(Default)]
+pub struct DaySchedule{
+    pub lessons : Vec<JoinedLesson>,
+    pub events  : Vec<SchoolEvent>
+}
+
+/// Availability of teacher in lesson hour of weekday,
+/// lesson hours without entry are `Neutral`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Availability{
+    /// teacher can't have lessons or duties at that time
+    Unavailable,
+    Preferred,
+    Neutral
+}
+
+impl Availability{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Unavailable => "unavailable",
+            Self::Preferred   => "preferred",
+            Self::Neutral     => "neutral"
+        }
+    }
+    pub fn parse(availability: &str) -> Option<Self>{
+        match availability{
+            "unavailable" => Some(Self::Unavailable),
+            "preferred"   => Some(Self::Preferred),
+            "neutral"     => Some(Self::Neutral),
+            _             => None
+        }
+    }
+}
+
+/// Lesson (or co-teaching) that takes place when teacher is unavailable
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AvailabilityViolation{
+    pub teacher_id   : u16,
+    pub teacher_name : String,
+    pub weekday      : u8,
+    pub lesson_hour  : u16,
+    pub class_id     : u16,
+    pub class        : String,
+    pub week         : u8,
+    pub group_id     : u16
+}
+
+/// Classroom that isn't suitable for lesson (lesson is inserted anyway)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoomWarning{
+    /// class has more students than fit into classroom
+    TooSmall{capacity: u16, students: u16},
+    /// subject requires feature that classroom doesn't have
+    MissingFeature(String)
+}
+
+impl RoomWarning{
+    /// Arguments of warning number `i` that are added to response
+    pub fn to_args(&self, i: usize) -> String{
+        match self{
+            Self::TooSmall{capacity, students} => format!("&warning{i}=too_small&capacity{i}={}&students{i}={}", capacity, students),
+            Self::MissingFeature(feature)      => format!("&warning{i}=missing_feature&feature{i}={}", feature.to_single('_'))
+        }
+    }
+}
+
+/// Lesson or booking that uses classroom on some date
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoomEntry{
+    /// None for lessons
+    pub booking_id   : Option<u16>,
+    pub start_hour   : u8,
+    pub start_minute : u8,
+    pub end_hour     : u8,
+    pub end_minute   : u8,
+    /// teacher of lesson or teacher that made booking
+    pub owner        : String,
+    /// subject and class of lesson or purpose of booking
+    pub title        : String
+}
+
+impl RoomEntry{
+    /// Start in minutes since midnight
+    pub fn start(&self) -> u16{
+        self.start_hour as u16 * 60 + self.start_minute as u16
+    }
+    /// End in minutes since midnight
+    pub fn end(&self) -> u16{
+        self.end_hour as u16 * 60 + self.end_minute as u16
+    }
+    /// Arguments of entry number `i` that are added to response
+    pub fn to_args(&self, i: usize) -> String{
+        let mut args = format!("&start{i}={:02}:{:02}&end{i}={:02}:{:02}&owner{i}={}&title{i}={}", 
+            self.start_hour, self.start_minute, self.end_hour, self.end_minute, self.owner.to_single('_'), self.title.to_single('_'));
+        if let Some(booking_id) = self.booking_id{
+            args.push_str(&format!("&booking_id{i}={}", booking_id));
+        }
+        args
+    }
+}
+
+/// Requirements that classroom has to meet
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ClassroomFilter{
+    pub min_capacity : Option<u16>,
+    /// every feature has to be in `ClassroomFeatures`
+    pub features     : Vec<String>
+}
+
+/// Range of days (inclusive) without regular lessons
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CalendarEntry{
+    pub calendar_id : u16,
+    /// YYYY-MM-DD
+    pub start_date  : String,
+    /// YYYY-MM-DD
+    pub end_date    : String,
+    pub day_type    : DayType,
+    pub name        : String
+}
+
+/// Two lessons already stored in database that
+/// use the same teacher or classroom at the same time
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Collision{
+    pub kind        : ConflictKind,
+    pub weekday     : u8,
+    pub lesson_hour : u16,
+    /// teacher or classroom (depending on `kind`)
+    pub resource    : (u16, String),
+    pub classes     : [(u16, String); 2]
 }
 
 #[derive(Debug, PartialEq)]
@@ -111,7 +367,32 @@ impl SendToClient for ServerError{
             Self::RequestPasswordError {entered_password} => format!("msat/400-Bad-Request&error_msg='WrongPassword={}'", entered_password.to_string().to_single('+')),
             Self::VersionNotSupported  (version_entered) => format!("msat/400-Bad-Request&error_msg='NotSupportedVersion={}&supported={}'", version_entered, 
                 SUPPORTED_VERSIONS.map(|n| n.to_string()).join("+")),
-            Self::DatabaseError(_) => "msat/500-Internal-Server-Error&error_msg='DatabaseError'".to_string()
+            Self::DatabaseError(_) => "msat/500-Internal-Server-Error&error_msg='DatabaseError'".to_string(),
+            Self::LessonConflict(conflicts) => {
+                let mut response = format!("msat/409-Conflict&error_msg='LessonConflict'&AMOUNT={}", conflicts.len());
+                for (i, conflict) in conflicts.iter().enumerate(){
+                    response.push_str(&format!("&type{i}={}&weekday{i}={}&lesson_hour{i}={}&class_id{i}={}&classroom_id{i}={}&teacher_id{i}={}",
+                        conflict.kind.as_str(), conflict.weekday, conflict.lesson_hour, conflict.class_id, conflict.classroom_id, conflict.teacher_id));
+                }
+                response
+            }
+            Self::NotQualified {teacher_id, subject_id} => 
+                format!("msat/409-Conflict&error_msg='NotQualified'&teacher_id={}&subject_id={}", teacher_id, subject_id),
+            Self::TeacherUnavailable {teacher_id, weekday, lesson_hour} => 
+                format!("msat/409-Conflict&error_msg='TeacherUnavailable'&teacher_id={}&weekday={}&lesson_hour={}", teacher_id, weekday, lesson_hour),
+            Self::TeacherUnavailableForBreak {teacher_id, weekday, break_num} => 
+                format!("msat/409-Conflict&error_msg='TeacherUnavailable'&teacher_id={}&weekday={}&break_num={}", teacher_id, weekday, break_num),
+            Self::RoomConflict(entries) => {
+                let mut response = format!("msat/409-Conflict&error_msg='RoomConflict'&AMOUNT={}", entries.len());
+                for (i, entry) in entries.iter().enumerate(){
+                    response.push_str(&entry.to_args(i));
+                }
+                response
+            }
+            Self::GroupNotInClass {group_id, class_id} => 
+                format!("msat/400-Bad-Request&error_msg='GroupNotInClass'&group_id={}&class_id={}", group_id, class_id),
+            Self::NoLessonHour {lesson_hour} => 
+                format!("msat/400-Bad-Request&error_msg='NoLessonHour'&lesson_hour={}", lesson_hour),
         }
     }
 }
@@ -156,6 +437,18 @@ pub struct JoinedHour{
     pub end_hour     : Option<u8>,
     pub end_minutes  : Option<u8>
 }
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LessonStatus{
+    #[default]
+    Regular,
+    /// Lesson is taught by substitute teacher
+    Substitution,
+    /// Teacher is absent and nobody covers the lesson, lesson is 
+    /// cancelled for one day or event takes place at that time
+    Cancelled,
+    /// Lesson was moved to other classroom or lesson hour for one day
+    Changed
+}
 #[derive(Deserialize, Serialize, Default)]
 pub struct JoinedLesson{
     pub weekday       : Option<u8>,
@@ -165,7 +458,173 @@ pub struct JoinedLesson{
     pub subject       : Option<String>,
     pub lessonh       : JoinedHour,
     pub semester      : Option<String>,
-    pub academic_year : Option<String>
+    pub academic_year : Option<String>,
+    /// YYYY-MM-DD
+    pub date          : Option<String>,
+    pub status        : LessonStatus,
+    /// name of class group or None if lesson is for the whole class
+    pub group         : Option<String>,
+    /// names of additional teachers (separated by comma)
+    pub co_teachers   : Option<String>,
+    /// first and last lesson hour of multi-slot lesson (e.g. double period) or None if lesson takes one lesson hour
+    pub block         : Option<(u16, u16)>
+}
+/// Lesson or duty that differs between draft and published timetable (see `backend::get_draft_changes`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftChange{
+    pub weekday   : u8,
+    /// lesson hour of lesson or break number of duty
+    pub hour      : u16,
+    /// class (with group) of lesson or teacher on duty
+    pub owner     : String,
+    /// subject, teacher and classroom of lesson or place of duty, None if it isn't in published timetable
+    pub published : Option<String>,
+    /// the same as `published`, None if it isn't in draft
+    pub draft     : Option<String>
+}
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DraftChanges{
+    pub lessons : Vec<DraftChange>,
+    pub duties  : Vec<DraftChange>
+}
+/// Changes made by academic year rollover (see `backend::rollover_year`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RolloverReport{
+    pub academic_year : u8,
+    /// ids and names of new semesters
+    pub semesters     : Vec<(u8, String)>,
+    /// old and new names of promoted classes
+    pub promoted      : Vec<(String, String)>,
+    /// names of classes that finished school
+    pub archived      : Vec<String>,
+    /// number of lessons copied into draft
+    pub lessons       : usize,
+    /// number of duties copied into draft
+    pub duties        : usize
+}
+/// Lesson of absent teacher that needs to be covered
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CoverLesson{
+    pub class_id    : u16,
+    pub lesson_hour : u16,
+    pub class       : String,
+    pub subject     : String,
+    pub teacher     : String,
+    pub substitute  : Option<String>,
+    /// 0 if lesson is for the whole class
+    pub group_id    : u16,
+    pub group       : Option<String>
+}
+/// Teacher that is free during lesson of absent teacher
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubstituteCandidate{
+    pub teacher_id   : u16,
+    pub teacher_name : String,
+    /// has qualification for subject of lesson
+    pub qualified    : bool
+}
+/// Comparison of scheduled number of lessons with required one
+/// (teacher's pensum or curriculum requirement of class)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LoadStatus{
+    /// required number of lessons isn't set
+    Unknown,
+    Under,
+    Full,
+    Over
+}
+impl LoadStatus{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Unknown => "unknown",
+            Self::Under   => "under",
+            Self::Full    => "full",
+            Self::Over    => "over"
+        }
+    }
+}
+/// Weekly workload of teacher in semester
+#[derive(Debug, PartialEq, Clone)]
+pub struct TeacherWorkload{
+    pub teacher_id   : u16,
+    pub teacher_name : String,
+    /// lessons per week (including co-teaching), lesson that takes 
+    /// place once in N-week cycle counts as 1/N
+    pub lessons      : f64,
+    /// duties per week, counted the same way as lessons
+    pub duties       : f64,
+    /// contractual number of lessons per week
+    pub pensum       : Option<u8>
+}
+impl TeacherWorkload{
+    /// Lessons and duties compared with pensum
+    pub fn status(&self) -> LoadStatus{
+        match self.pensum.and_then(|pensum| (self.lessons + self.duties).partial_cmp(&f64::from(pensum))){
+            None                            => LoadStatus::Unknown,
+            Some(std::cmp::Ordering::Less)    => LoadStatus::Under,
+            Some(std::cmp::Ordering::Equal)   => LoadStatus::Full,
+            Some(std::cmp::Ordering::Greater) => LoadStatus::Over
+        }
+    }
+}
+/// Weekly hours of subject required by curriculum compared with lessons of class
+#[derive(Debug, PartialEq, Clone)]
+pub struct CurriculumCompliance{
+    pub class_id   : u16,
+    pub class      : String,
+    pub subject_id : u16,
+    pub subject    : String,
+    /// None if subject isn't in curriculum of class
+    pub required   : Option<u8>,
+    /// lessons per week (counted like in `TeacherWorkload`), lessons of class
+    /// groups are counted only for group that has the most of them
+    pub scheduled  : f64
+}
+impl CurriculumCompliance{
+    pub fn status(&self) -> LoadStatus{
+        match self.scheduled.partial_cmp(&f64::from(self.required.unwrap_or(0))){
+            None | Some(std::cmp::Ordering::Equal) => LoadStatus::Full,
+            Some(std::cmp::Ordering::Less)         => LoadStatus::Under,
+            Some(std::cmp::Ordering::Greater)      => LoadStatus::Over
+        }
+    }
+}
+/// Whose timetable is looked up
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TimetableOwner{
+    Teacher(u16),
+    Class(u16),
+    Classroom(u16)
+}
+/// What teacher, class or classroom is doing in some part of day
+pub enum Activity{
+    Lesson(JoinedLesson),
+    /// only teachers have duties
+    Duty(JoinedDuty),
+    /// first lesson or duty of the day hasn't started yet
+    BeforeSchool,
+    /// nothing at the moment, but there is lesson or duty later that day
+    FreePeriod,
+    /// no more lessons or duties on that day
+    AfterSchool,
+    /// weekend, holiday or day outside of semester
+    NoSchool
+}
+impl Activity{
+    pub fn as_str(&self) -> &'static str{
+        match self{
+            Self::Lesson(_)    => "lesson",
+            Self::Duty(_)      => "duty",
+            Self::BeforeSchool => "before_school",
+            Self::FreePeriod   => "free_period",
+            Self::AfterSchool  => "after_school",
+            Self::NoSchool     => "no_school"
+        }
+    }
+}
+pub struct NowAndNext{
+    pub current : Activity,
+    pub next    : Activity
 }
 #[derive(Deserialize, Serialize, Default)]
 pub struct JoinedLessonRaw{